@@ -14,7 +14,7 @@ use hubcaps::checks::{
     Action, Annotation, AnnotationLevel, CheckRunOptions, Conclusion, Image, Output,
 };
 use hubcaps::git::GetReferenceResponse;
-use hubcaps::{Credentials, Github, InstallationTokenGenerator, JWTCredentials, Result};
+use hubcaps::{Credentials, Github, JWTCredentials, Result};
 
 fn var(name: &str) -> Result<String> {
     if let Some(v) = env::var(name).ok() {
@@ -39,7 +39,7 @@ fn main() -> Result<()> {
     File::open(&key_file)?.read_to_end(&mut key)?;
     let cred = JWTCredentials::new(app_id.parse().expect("Bad GH_APP_ID"), key)?;
 
-    let mut github = Github::new(USER_AGENT, Credentials::JWT(cred.clone()));
+    let github = Github::new(USER_AGENT, Credentials::JWT(cred));
     let installation = rt
         .block_on(
             github
@@ -47,9 +47,7 @@ fn main() -> Result<()> {
                 .find_repo_installation(user_name.clone(), repo.clone()),
         ).unwrap();
 
-    github.set_credentials(Credentials::InstallationToken(
-        InstallationTokenGenerator::new(installation.id, cred),
-    ));
+    let github = github.installation(installation.id)?;
 
     let repo = github.repo(user_name, repo);
     let reference = repo.git().reference(format!("heads/{}", &branch));
@@ -6,7 +6,7 @@ use std::env;
 
 use tokio::runtime::Runtime;
 
-use hubcaps::hooks::{HookCreateOptions, WebHookContentType};
+use hubcaps::hooks::{HookCreateOptions, WebHookContentType, WebhookEvent};
 use hubcaps::{Credentials, Github, Result};
 
 fn main() -> Result<()> {
@@ -24,6 +24,7 @@ fn main() -> Result<()> {
                     &HookCreateOptions::web()
                         .url("http://localhost:8080")
                         .content_type(WebHookContentType::Json)
+                        .events(vec![WebhookEvent::Push, WebhookEvent::PullRequest])
                         .build(),
                 ),
             );
@@ -26,11 +26,14 @@ fn main() -> Result<()> {
                     .iter(&Default::default())
                     .for_each(move |repo| {
                         println!("{}", repo.name);
-                        let f = repo.languages(github.clone()).map(|langs| {
-                            for (language, bytes_of_code) in langs {
-                                println!("{}: {} bytes", language, bytes_of_code)
-                            }
-                        });
+                        let f = github
+                            .repo(repo.owner.login.as_str(), repo.name.as_str())
+                            .languages()
+                            .map(|langs| {
+                                for (language, bytes_of_code) in langs {
+                                    println!("{}: {} bytes", language, bytes_of_code)
+                                }
+                            });
                         handle.spawn(f.map_err(|_| ()));
                         Ok(())
                     }),
@@ -0,0 +1,1151 @@
+//! Actions interface
+//!
+//! See the [github docs](https://docs.github.com/en/rest/actions) for more information
+
+use std::collections::{BTreeMap, HashMap};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use futures::{future, Future as StdFuture, Stream as StdStream};
+use hyper::client::connect::Connect;
+use hyper::{HeaderMap, Method, StatusCode};
+use serde_json;
+use url::form_urlencoded;
+
+use codeowners::glob;
+use deployments::Deployment;
+use organizations::{AllReposOptions, Organization};
+use secrets::{SelectedRepositories, Visibility};
+use webhooks::WorkflowRun;
+use {fault_from, unfold, Error, Future, Github, Stream};
+
+fn runner_groups(list: RunnerGroupList) -> Vec<RunnerGroup> {
+    list.runner_groups
+}
+
+fn runners(list: RunnerList) -> Vec<Runner> {
+    list.runners
+}
+
+fn artifacts(list: ArtifactList) -> Vec<Artifact> {
+    list.artifacts
+}
+
+/// deletes the artifacts of `artifacts` matching `cleanup` concurrently,
+/// yielding those deleted. nothing is deleted in a dry run
+fn cleanup_artifacts<C>(
+    github: Github<C>,
+    artifacts: Stream<Artifact>,
+    cleanup: ArtifactCleanup,
+) -> Stream<Artifact>
+where
+    C: Clone + Connect + 'static,
+{
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|now| now.as_secs())
+        .unwrap_or_default();
+    let cutoff = cleanup
+        .older_than_days
+        .map(|days| timestamp(now.saturating_sub(u64::from(days) * 24 * 60 * 60)));
+    let dry_run = cleanup.dry_run;
+    let matching = artifacts.filter(move |artifact| cleanup.matches(artifact, cutoff.as_ref()));
+    if dry_run {
+        return Box::new(matching);
+    }
+    Box::new(
+        matching
+            .collect()
+            .map(move |matching| {
+                let deletes = matching
+                    .into_iter()
+                    .map(|artifact| delete_artifact(&github, artifact))
+                    .collect::<Vec<_>>();
+                github.batch().run(deletes)
+            })
+            .flatten_stream(),
+    )
+}
+
+/// deletes an artifact by its url, which names its repository. artifacts
+/// which are already gone count as deleted
+fn delete_artifact<C>(github: &Github<C>, artifact: Artifact) -> Future<Artifact>
+where
+    C: Clone + Connect + 'static,
+{
+    Box::new(
+        github
+            .request_raw(Method::DELETE, &artifact.url, None, HeaderMap::new())
+            .and_then(move |raw| {
+                if raw.status.is_success() || raw.status == StatusCode::NOT_FOUND {
+                    return Ok(artifact);
+                }
+                Err(fault_from(raw.status, &raw.body).into())
+            }),
+    )
+}
+
+/// formats seconds since the epoch as a UTC timestamp, like Github does
+fn timestamp(secs: u64) -> String {
+    let days = secs / 86_400;
+    let secs = secs % 86_400;
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs / 3_600,
+        secs % 3_600 / 60,
+        secs % 60
+    )
+}
+
+/// Provides access to Github Actions operations for a repository
+pub struct Actions<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    owner: String,
+    repo: String,
+}
+
+impl<C: Clone + Connect + 'static> Actions<C> {
+    #[doc(hidden)]
+    pub fn new<O, R>(github: Github<C>, owner: O, repo: R) -> Self
+    where
+        O: Into<String>,
+        R: Into<String>,
+    {
+        Actions {
+            github,
+            owner: owner.into(),
+            repo: repo.into(),
+        }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!("/repos/{}/{}/actions{}", self.owner, self.repo, more)
+    }
+
+    /// list a page of the repository's workflow artifacts, newest first
+    pub fn artifacts(&self, options: &ArtifactListOptions) -> Future<ArtifactList> {
+        let mut uri = vec![self.path("/artifacts")];
+        if let Some(query) = options.serialize() {
+            uri.push(query);
+        }
+        self.github.get(&uri.join("?"))
+    }
+
+    /// provides a stream over all of the repository's workflow artifacts,
+    /// newest first
+    pub fn iter_artifacts(&self) -> Stream<Artifact> {
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&self.path("/artifacts?per_page=100")),
+            artifacts,
+        )
+    }
+
+    /// get a workflow artifact by id
+    pub fn artifact(&self, id: u64) -> Future<Artifact> {
+        self.github.get(&self.path(&format!("/artifacts/{}", id)))
+    }
+
+    /// delete a workflow artifact
+    pub fn delete_artifact(&self, id: u64) -> Future<()> {
+        self.github
+            .delete(&self.path(&format!("/artifacts/{}", id)))
+    }
+
+    /// delete the repository's artifacts matching `cleanup` concurrently,
+    /// yielding those deleted, or those which would be in a dry run
+    pub fn cleanup_artifacts(&self, cleanup: &ArtifactCleanup) -> Stream<Artifact> {
+        cleanup_artifacts(self.github.clone(), self.iter_artifacts(), cleanup.clone())
+    }
+
+    /// get a workflow run by id
+    pub fn run(&self, run_id: u64) -> Future<WorkflowRun> {
+        self.github.get(&self.path(&format!("/runs/{}", run_id)))
+    }
+
+    /// get the billable time of a workflow run, by the operating system of
+    /// its runners, and how long it took
+    pub fn run_timing(&self, run_id: u64) -> Future<RunTiming> {
+        self.github
+            .get(&self.path(&format!("/runs/{}/timing", run_id)))
+    }
+
+    /// get the billable time of a workflow's runs in the current billing
+    /// cycle, by the operating system of their runners. `workflow` is the
+    /// workflow's id or the name of its file, like `ci.yml`
+    pub fn workflow_timing<W>(&self, workflow: W) -> Future<WorkflowTiming>
+    where
+        W: Into<String>,
+    {
+        self.github
+            .get(&self.path(&format!("/workflows/{}/timing", workflow.into())))
+    }
+
+    /// approve a workflow run of a pull request from a fork, which waits
+    /// for approval when the repository requires it for outside collaborators
+    pub fn approve_run(&self, run_id: u64) -> Future<()> {
+        self.github
+            .post_no_response(&self.path(&format!("/runs/{}/approve", run_id)), Vec::new())
+    }
+
+    /// list the deployments of a workflow run waiting for the approval of
+    /// an environment's required reviewers
+    pub fn pending_deployments(&self, run_id: u64) -> Future<Vec<PendingDeployment>> {
+        self.github
+            .get(&self.path(&format!("/runs/{}/pending_deployments", run_id)))
+    }
+
+    /// approve or reject the pending deployments of a workflow run to
+    /// the given environments, returning the deployments which were approved
+    pub fn review_pending_deployments(
+        &self,
+        run_id: u64,
+        review: &PendingDeploymentReview,
+    ) -> Future<Vec<Deployment>> {
+        self.github.post(
+            &self.path(&format!("/runs/{}/pending_deployments", run_id)),
+            json!(review),
+        )
+    }
+
+    /// approve or reject the deployment of a workflow run to an environment
+    /// gated by the custom protection rule of the GitHub App this client
+    /// authenticates as
+    pub fn review_custom_gate(&self, run_id: u64, review: &CustomGateReview) -> Future<()> {
+        self.github.post_no_response(
+            &self.path(&format!("/runs/{}/deployment_protection_rule", run_id)),
+            json!(review),
+        )
+    }
+
+    /// get the number of days artifacts and logs of workflow runs are kept
+    pub fn retention(&self) -> Future<Retention> {
+        self.github
+            .get(&self.path("/permissions/artifact-and-log-retention"))
+    }
+
+    /// set the number of days artifacts and logs of workflow runs are kept.
+    /// it can not exceed the limit set by the organization or enterprise
+    pub fn set_retention(&self, days: u32) -> Future<()> {
+        let retention = RetentionOptions { days };
+        self.github.put_no_response(
+            &self.path("/permissions/artifact-and-log-retention"),
+            json!(retention),
+        )
+    }
+
+    /// get the template of the subject claim of the OIDC tokens the
+    /// repository's workflows are issued
+    pub fn oidc_subject_claim(&self) -> Future<SubjectClaim> {
+        self.github.get(&self.path("/oidc/customization/sub"))
+    }
+
+    /// set the template of the subject claim of the OIDC tokens the
+    /// repository's workflows are issued
+    pub fn set_oidc_subject_claim(&self, claim: &SubjectClaim) -> Future<()> {
+        self.github
+            .put_no_response(&self.path("/oidc/customization/sub"), json!(claim))
+    }
+}
+
+/// Provides access to Github Actions operations for an organization
+pub struct OrgActions<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    org: String,
+}
+
+impl<C: Clone + Connect + 'static> OrgActions<C> {
+    #[doc(hidden)]
+    pub fn new<O>(github: Github<C>, org: O) -> Self
+    where
+        O: Into<String>,
+    {
+        OrgActions {
+            github,
+            org: org.into(),
+        }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!("/orgs/{}/actions{}", self.org, more)
+    }
+
+    /// get a reference to the organization's self-hosted runner groups
+    pub fn runner_groups(&self) -> RunnerGroups<C> {
+        RunnerGroups::new(self.github.clone(), self.path("/runner-groups"))
+    }
+
+    /// delete the artifacts of the organization's unarchived repositories
+    /// matching `cleanup` concurrently, yielding those deleted, or those
+    /// which would be in a dry run
+    pub fn cleanup_artifacts(&self, cleanup: &ArtifactCleanup) -> Stream<Artifact> {
+        let github = self.github.clone();
+        let artifacts = Organization::new(self.github.clone(), self.org.as_str())
+            .all_repos(&AllReposOptions::builder().archived(false).build())
+            .map(move |repo| {
+                Actions::new(github.clone(), repo.owner.login, repo.name).iter_artifacts()
+            })
+            .flatten();
+        cleanup_artifacts(self.github.clone(), Box::new(artifacts), cleanup.clone())
+    }
+
+    /// get the template of the subject claim of the OIDC tokens the
+    /// organization's workflows are issued
+    pub fn oidc_subject_claim(&self) -> Future<OrgSubjectClaim> {
+        self.github.get(&self.path("/oidc/customization/sub"))
+    }
+
+    /// set the template of the subject claim of the OIDC tokens the
+    /// organization's workflows are issued. repositories may override it
+    pub fn set_oidc_subject_claim(&self, claim: &OrgSubjectClaim) -> Future<()> {
+        self.github
+            .put_no_response(&self.path("/oidc/customization/sub"), json!(claim))
+    }
+}
+
+/// Provides access to the self-hosted runner groups of an organization or
+/// enterprise. Organization runner groups are made available to selected
+/// repositories, enterprise runner groups to selected organizations
+pub struct RunnerGroups<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    path: String,
+}
+
+impl<C: Clone + Connect + 'static> RunnerGroups<C> {
+    #[doc(hidden)]
+    pub fn new<P>(github: Github<C>, path: P) -> Self
+    where
+        P: Into<String>,
+    {
+        RunnerGroups {
+            github,
+            path: path.into(),
+        }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!("{}{}", self.path, more)
+    }
+
+    /// list runner groups
+    pub fn list(&self) -> Future<RunnerGroupList> {
+        self.github.get(&self.path(""))
+    }
+
+    /// provides a stream over all pages of runner groups
+    pub fn iter(&self) -> Stream<RunnerGroup> {
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&self.path("")),
+            runner_groups,
+        )
+    }
+
+    /// get a runner group by id
+    pub fn get(&self, id: u64) -> Future<RunnerGroup> {
+        self.github.get(&self.path(&format!("/{}", id)))
+    }
+
+    /// create a runner group
+    pub fn create(&self, options: &RunnerGroupOptions) -> Future<RunnerGroup> {
+        self.github.post(&self.path(""), json!(options))
+    }
+
+    /// update the name, visibility and workflow restrictions of a runner group
+    pub fn update(&self, id: u64, options: &RunnerGroupOptions) -> Future<RunnerGroup> {
+        self.github
+            .patch(&self.path(&format!("/{}", id)), json!(options))
+    }
+
+    /// delete a runner group. its runners move to the default group
+    pub fn delete(&self, id: u64) -> Future<()> {
+        self.github.delete(&self.path(&format!("/{}", id)))
+    }
+
+    /// list the repositories with access to an organization runner group
+    /// of `selected` visibility
+    pub fn repositories(&self, id: u64) -> Future<SelectedRepositories> {
+        self.github
+            .get(&self.path(&format!("/{}/repositories", id)))
+    }
+
+    /// replace the repositories with access to an organization runner group
+    /// of `selected` visibility
+    pub fn set_repositories(&self, id: u64, repository_ids: Vec<u64>) -> Future<()> {
+        let mut payload = BTreeMap::new();
+        payload.insert("selected_repository_ids", repository_ids);
+        self.github
+            .put_no_response(&self.path(&format!("/{}/repositories", id)), json!(payload))
+    }
+
+    /// give a repository access to an organization runner group of
+    /// `selected` visibility
+    pub fn add_repository(&self, id: u64, repository_id: u64) -> Future<()> {
+        self.github.put_no_response(
+            &self.path(&format!("/{}/repositories/{}", id, repository_id)),
+            Vec::new(),
+        )
+    }
+
+    /// revoke a repository's access to an organization runner group
+    pub fn remove_repository(&self, id: u64, repository_id: u64) -> Future<()> {
+        self.github
+            .delete(&self.path(&format!("/{}/repositories/{}", id, repository_id)))
+    }
+
+    /// replace the organizations with access to an enterprise runner group
+    /// of `selected` visibility
+    pub fn set_organizations(&self, id: u64, organization_ids: Vec<u64>) -> Future<()> {
+        let mut payload = BTreeMap::new();
+        payload.insert("selected_organization_ids", organization_ids);
+        self.github.put_no_response(
+            &self.path(&format!("/{}/organizations", id)),
+            json!(payload),
+        )
+    }
+
+    /// give an organization access to an enterprise runner group of
+    /// `selected` visibility
+    pub fn add_organization(&self, id: u64, organization_id: u64) -> Future<()> {
+        self.github.put_no_response(
+            &self.path(&format!("/{}/organizations/{}", id, organization_id)),
+            Vec::new(),
+        )
+    }
+
+    /// revoke an organization's access to an enterprise runner group
+    pub fn remove_organization(&self, id: u64, organization_id: u64) -> Future<()> {
+        self.github
+            .delete(&self.path(&format!("/{}/organizations/{}", id, organization_id)))
+    }
+
+    /// list the self-hosted runners in a runner group
+    pub fn runners(&self, id: u64) -> Future<RunnerList> {
+        self.github.get(&self.path(&format!("/{}/runners", id)))
+    }
+
+    /// provides a stream over all pages of the self-hosted runners in a
+    /// runner group
+    pub fn iter_runners(&self, id: u64) -> Stream<Runner> {
+        unfold(
+            self.github.clone(),
+            self.github
+                .get_pages(&self.path(&format!("/{}/runners", id))),
+            runners,
+        )
+    }
+
+    /// replace the self-hosted runners in a runner group
+    pub fn set_runners(&self, id: u64, runner_ids: Vec<u64>) -> Future<()> {
+        let mut payload = BTreeMap::new();
+        payload.insert("runners", runner_ids);
+        self.github
+            .put_no_response(&self.path(&format!("/{}/runners", id)), json!(payload))
+    }
+
+    /// move a self-hosted runner into a runner group
+    pub fn add_runner(&self, id: u64, runner_id: u64) -> Future<()> {
+        self.github.put_no_response(
+            &self.path(&format!("/{}/runners/{}", id, runner_id)),
+            Vec::new(),
+        )
+    }
+
+    /// move a self-hosted runner out of a runner group, into the default group
+    pub fn remove_runner(&self, id: u64, runner_id: u64) -> Future<()> {
+        self.github
+            .delete(&self.path(&format!("/{}/runners/{}", id, runner_id)))
+    }
+}
+
+// representations
+
+/// A deployment of a workflow run waiting on an environment's protection rules
+#[derive(Debug, Deserialize)]
+pub struct PendingDeployment {
+    pub environment: PendingDeploymentEnvironment,
+    /// minutes to wait before the job may proceed
+    pub wait_timer: u64,
+    pub wait_timer_started_at: Option<String>,
+    /// whether the authenticated user is one of the required reviewers
+    pub current_user_can_approve: bool,
+    pub reviewers: Vec<DeploymentReviewer>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PendingDeploymentEnvironment {
+    pub id: u64,
+    pub node_id: String,
+    pub name: String,
+    pub url: String,
+    pub html_url: String,
+}
+
+/// A user or team required to review deployments to an environment
+#[derive(Debug, Deserialize)]
+pub struct DeploymentReviewer {
+    /// `User` or `Team`
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub reviewer: serde_json::Value,
+}
+
+/// The outcome of a review of pending deployments
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeploymentReviewState {
+    Approved,
+    Rejected,
+}
+
+/// A review of the pending deployments of a workflow run
+#[derive(Debug, Serialize)]
+pub struct PendingDeploymentReview {
+    pub environment_ids: Vec<u64>,
+    pub state: DeploymentReviewState,
+    pub comment: String,
+}
+
+impl PendingDeploymentReview {
+    /// approve the deployments to the given environments
+    pub fn approve<M>(environment_ids: Vec<u64>, comment: M) -> Self
+    where
+        M: Into<String>,
+    {
+        PendingDeploymentReview {
+            environment_ids,
+            state: DeploymentReviewState::Approved,
+            comment: comment.into(),
+        }
+    }
+
+    /// reject the deployments to the given environments
+    pub fn reject<M>(environment_ids: Vec<u64>, comment: M) -> Self
+    where
+        M: Into<String>,
+    {
+        PendingDeploymentReview {
+            environment_ids,
+            state: DeploymentReviewState::Rejected,
+            comment: comment.into(),
+        }
+    }
+}
+
+/// A GitHub App's review of a deployment gated by its custom protection rule
+#[derive(Debug, Serialize)]
+pub struct CustomGateReview {
+    pub environment_name: String,
+    pub state: DeploymentReviewState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+impl CustomGateReview {
+    /// approve the deployment to the given environment
+    pub fn approve<E>(environment_name: E) -> Self
+    where
+        E: Into<String>,
+    {
+        CustomGateReview {
+            environment_name: environment_name.into(),
+            state: DeploymentReviewState::Approved,
+            comment: None,
+        }
+    }
+
+    /// reject the deployment to the given environment
+    pub fn reject<E>(environment_name: E) -> Self
+    where
+        E: Into<String>,
+    {
+        CustomGateReview {
+            environment_name: environment_name.into(),
+            state: DeploymentReviewState::Rejected,
+            comment: None,
+        }
+    }
+
+    /// explain the review on the workflow run
+    pub fn comment<M>(mut self, comment: M) -> Self
+    where
+        M: Into<String>,
+    {
+        self.comment = Some(comment.into());
+        self
+    }
+}
+
+/// How long artifacts and logs of workflow runs are kept
+#[derive(Debug, Deserialize)]
+pub struct Retention {
+    pub days: u32,
+    /// the most days the organization or enterprise allows
+    pub maximum_allowed_days: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+struct RetentionOptions {
+    days: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ArtifactList {
+    pub total_count: u64,
+    pub artifacts: Vec<Artifact>,
+}
+
+/// A file or directory a workflow run uploaded
+#[derive(Clone, Debug, Deserialize)]
+pub struct Artifact {
+    pub id: u64,
+    pub node_id: String,
+    pub name: String,
+    pub size_in_bytes: u64,
+    pub url: String,
+    pub archive_download_url: String,
+    /// expired artifacts can no longer be downloaded
+    pub expired: bool,
+    pub created_at: Option<String>,
+    pub expires_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub workflow_run: Option<ArtifactWorkflowRun>,
+}
+
+/// The workflow run which uploaded an artifact
+#[derive(Clone, Debug, Deserialize)]
+pub struct ArtifactWorkflowRun {
+    pub id: u64,
+    pub repository_id: u64,
+    pub head_repository_id: u64,
+    pub head_branch: Option<String>,
+    pub head_sha: String,
+}
+
+#[derive(Default)]
+pub struct ArtifactListOptions {
+    params: HashMap<&'static str, String>,
+}
+
+impl ArtifactListOptions {
+    pub fn builder() -> ArtifactListOptionsBuilder {
+        ArtifactListOptionsBuilder::default()
+    }
+
+    /// serialize options as a string. returns None if no options are defined
+    pub fn serialize(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded)
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ArtifactListOptionsBuilder(ArtifactListOptions);
+
+impl ArtifactListOptionsBuilder {
+    pub fn per_page(&mut self, n: usize) -> &mut Self {
+        self.0.params.insert("per_page", n.to_string());
+        self
+    }
+
+    pub fn page(&mut self, n: usize) -> &mut Self {
+        self.0.params.insert("page", n.to_string());
+        self
+    }
+
+    /// only list artifacts with exactly this name
+    pub fn name<N>(&mut self, name: N) -> &mut Self
+    where
+        N: Into<String>,
+    {
+        self.0.params.insert("name", name.into());
+        self
+    }
+
+    pub fn build(&self) -> ArtifactListOptions {
+        ArtifactListOptions {
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+/// Which artifacts `cleanup_artifacts` deletes. Artifacts matching every
+/// filter set are deleted, so without filters every artifact is. Expired
+/// artifacts no longer use storage, so they're left alone
+#[derive(Clone, Debug, Default)]
+pub struct ArtifactCleanup {
+    older_than_days: Option<u32>,
+    name: Option<String>,
+    dry_run: bool,
+}
+
+impl ArtifactCleanup {
+    pub fn builder() -> ArtifactCleanupBuilder {
+        ArtifactCleanupBuilder::default()
+    }
+
+    /// whether an artifact should be deleted, given the timestamp artifacts
+    /// created before are old enough to be
+    fn matches(&self, artifact: &Artifact, cutoff: Option<&String>) -> bool {
+        let old = cutoff.map_or(true, |cutoff| {
+            artifact
+                .created_at
+                .as_ref()
+                .map_or(false, |created_at| created_at < cutoff)
+        });
+        let named = self.name.as_ref().map_or(true, |pattern| {
+            glob(pattern.as_bytes(), artifact.name.as_bytes())
+        });
+        !artifact.expired && old && named
+    }
+}
+
+#[derive(Default)]
+pub struct ArtifactCleanupBuilder(ArtifactCleanup);
+
+impl ArtifactCleanupBuilder {
+    /// only delete artifacts created more than this many days ago
+    pub fn older_than_days(&mut self, days: u32) -> &mut Self {
+        self.0.older_than_days = Some(days);
+        self
+    }
+
+    /// only delete artifacts whose names match this pattern, where `*`
+    /// matches any run of characters and `?` any one character
+    pub fn name<N>(&mut self, pattern: N) -> &mut Self
+    where
+        N: Into<String>,
+    {
+        self.0.name = Some(pattern.into());
+        self
+    }
+
+    /// list the artifacts which would be deleted, without deleting them
+    pub fn dry_run(&mut self, dry_run: bool) -> &mut Self {
+        self.0.dry_run = dry_run;
+        self
+    }
+
+    pub fn build(&self) -> ArtifactCleanup {
+        self.0.clone()
+    }
+}
+
+/// Billable time by the operating system of the runners it was spent on.
+/// Time spent on self-hosted runners, and in public repositories, isn't
+/// billable
+#[derive(Debug, Default, Deserialize)]
+pub struct Billable<T> {
+    #[serde(rename = "UBUNTU")]
+    pub ubuntu: Option<T>,
+    #[serde(rename = "MACOS")]
+    pub macos: Option<T>,
+    #[serde(rename = "WINDOWS")]
+    pub windows: Option<T>,
+}
+
+impl<T> Billable<T> {
+    /// the billable time of each operating system time was spent on
+    fn each(&self) -> Vec<&T> {
+        vec![&self.ubuntu, &self.macos, &self.windows]
+            .into_iter()
+            .filter_map(Option::as_ref)
+            .collect()
+    }
+}
+
+/// The billable time of a workflow's runs
+#[derive(Debug, Deserialize)]
+pub struct WorkflowTiming {
+    pub billable: Billable<WorkflowBillable>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WorkflowBillable {
+    pub total_ms: u64,
+}
+
+impl WorkflowTiming {
+    /// the billable milliseconds spent on runners of every operating system
+    pub fn total_ms(&self) -> u64 {
+        self.billable.each().iter().map(|os| os.total_ms).sum()
+    }
+}
+
+/// The billable time of a workflow run
+#[derive(Debug, Deserialize)]
+pub struct RunTiming {
+    pub billable: Billable<RunBillable>,
+    /// how long the run took, from start to finish. absent for runs which
+    /// haven't finished
+    pub run_duration_ms: Option<u64>,
+}
+
+impl RunTiming {
+    /// the billable milliseconds spent on runners of every operating system
+    pub fn total_ms(&self) -> u64 {
+        self.billable.each().iter().map(|os| os.total_ms).sum()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RunBillable {
+    pub total_ms: u64,
+    /// the number of jobs run
+    pub jobs: u64,
+    #[serde(default)]
+    pub job_runs: Vec<JobRun>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JobRun {
+    pub job_id: u64,
+    pub duration_ms: u64,
+}
+
+/// The claims the subject of an organization's OIDC tokens is made of, like
+/// `repo` and `context`, in order
+#[derive(Debug, Deserialize, Serialize)]
+pub struct OrgSubjectClaim {
+    pub include_claim_keys: Vec<String>,
+}
+
+/// The template of the subject claim of a repository's OIDC tokens
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SubjectClaim {
+    /// whether the repository uses Github's default template, ignoring
+    /// the organization's
+    pub use_default: bool,
+    /// the claims the subject is made of, when the repository has its
+    /// own template. `None` when the repository uses the organization's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_claim_keys: Option<Vec<String>>,
+}
+
+impl SubjectClaim {
+    /// use Github's default template
+    pub fn default_template() -> Self {
+        SubjectClaim {
+            use_default: true,
+            include_claim_keys: None,
+        }
+    }
+
+    /// use the organization's template
+    pub fn inherited() -> Self {
+        SubjectClaim {
+            use_default: false,
+            include_claim_keys: None,
+        }
+    }
+
+    /// make the subject of the claims `keys`, in order
+    pub fn custom<K>(keys: Vec<K>) -> Self
+    where
+        K: Into<String>,
+    {
+        SubjectClaim {
+            use_default: false,
+            include_claim_keys: Some(keys.into_iter().map(Into::into).collect()),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RunnerGroupList {
+    pub total_count: u64,
+    pub runner_groups: Vec<RunnerGroup>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RunnerGroup {
+    pub id: u64,
+    pub name: String,
+    pub visibility: Visibility,
+    /// whether this is the group runners are added to by default
+    pub default: bool,
+    /// whether the group is inherited from the enterprise
+    #[serde(default)]
+    pub inherited: bool,
+    pub allows_public_repositories: bool,
+    #[serde(default)]
+    pub restricted_to_workflows: bool,
+    #[serde(default)]
+    pub selected_workflows: Vec<String>,
+    pub runners_url: String,
+    pub selected_repositories_url: Option<String>,
+    pub selected_organizations_url: Option<String>,
+}
+
+/// options for creating or updating a runner group
+#[derive(Debug, Default, Serialize)]
+pub struct RunnerGroupOptions {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<Visibility>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selected_repository_ids: Option<Vec<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selected_organization_ids: Option<Vec<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runners: Option<Vec<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allows_public_repositories: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restricted_to_workflows: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selected_workflows: Option<Vec<String>>,
+}
+
+impl RunnerGroupOptions {
+    pub fn builder<N>(name: N) -> RunnerGroupOptionsBuilder
+    where
+        N: Into<String>,
+    {
+        RunnerGroupOptionsBuilder(RunnerGroupOptions {
+            name: name.into(),
+            ..Default::default()
+        })
+    }
+}
+
+pub struct RunnerGroupOptionsBuilder(RunnerGroupOptions);
+
+impl RunnerGroupOptionsBuilder {
+    /// which repositories or organizations may use the group's runners
+    pub fn visibility(&mut self, visibility: Visibility) -> &mut Self {
+        self.0.visibility = Some(visibility);
+        self
+    }
+
+    /// the repositories which may use the runners of an organization
+    /// runner group of `selected` visibility. only used on creation
+    pub fn selected_repository_ids(&mut self, ids: Vec<u64>) -> &mut Self {
+        self.0.selected_repository_ids = Some(ids);
+        self
+    }
+
+    /// the organizations which may use the runners of an enterprise
+    /// runner group of `selected` visibility. only used on creation
+    pub fn selected_organization_ids(&mut self, ids: Vec<u64>) -> &mut Self {
+        self.0.selected_organization_ids = Some(ids);
+        self
+    }
+
+    /// the self-hosted runners to move into the group. only used on creation
+    pub fn runners(&mut self, ids: Vec<u64>) -> &mut Self {
+        self.0.runners = Some(ids);
+        self
+    }
+
+    /// whether public repositories may use the group's runners
+    pub fn allows_public_repositories(&mut self, allow: bool) -> &mut Self {
+        self.0.allows_public_repositories = Some(allow);
+        self
+    }
+
+    /// restrict the group's runners to the workflows given by
+    /// `selected_workflows`
+    pub fn restricted_to_workflows(&mut self, restricted: bool) -> &mut Self {
+        self.0.restricted_to_workflows = Some(restricted);
+        self
+    }
+
+    /// the workflows which may use the group's runners, like
+    /// `octo-org/octo-repo/.github/workflows/deploy.yaml@main`
+    pub fn selected_workflows<W>(&mut self, workflows: Vec<W>) -> &mut Self
+    where
+        W: Into<String>,
+    {
+        self.0.selected_workflows = Some(workflows.into_iter().map(|w| w.into()).collect());
+        self
+    }
+
+    pub fn build(&self) -> RunnerGroupOptions {
+        RunnerGroupOptions {
+            name: self.0.name.clone(),
+            visibility: self.0.visibility,
+            selected_repository_ids: self.0.selected_repository_ids.clone(),
+            selected_organization_ids: self.0.selected_organization_ids.clone(),
+            runners: self.0.runners.clone(),
+            allows_public_repositories: self.0.allows_public_repositories,
+            restricted_to_workflows: self.0.restricted_to_workflows,
+            selected_workflows: self.0.selected_workflows.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RunnerList {
+    pub total_count: u64,
+    pub runners: Vec<Runner>,
+}
+
+/// A self-hosted runner
+#[derive(Debug, Deserialize)]
+pub struct Runner {
+    pub id: u64,
+    pub name: String,
+    pub os: String,
+    /// `online` or `offline`
+    pub status: String,
+    pub busy: bool,
+    pub labels: Vec<RunnerLabel>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RunnerLabel {
+    pub id: Option<u64>,
+    pub name: String,
+    /// `read-only` for labels Github applies, `custom` otherwise
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pending_deployment_review_reqs() {
+        let review = PendingDeploymentReview::reject(vec![161171787], "not yet");
+        assert_eq!(
+            serde_json::to_string(&review).unwrap(),
+            r#"{"environment_ids":[161171787],"state":"rejected","comment":"not yet"}"#
+        );
+    }
+
+    #[test]
+    fn custom_gate_review_reqs() {
+        let review = CustomGateReview::approve("production").comment("all checks passed");
+        assert_eq!(
+            serde_json::to_string(&review).unwrap(),
+            r#"{"environment_name":"production","state":"approved","comment":"all checks passed"}"#
+        );
+    }
+
+    #[test]
+    fn timestamps() {
+        assert_eq!(timestamp(0), "1970-01-01T00:00:00Z");
+        assert_eq!(timestamp(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(timestamp(1_600_000_000), "2020-09-13T12:26:40Z");
+    }
+
+    #[test]
+    fn cleanup_matches() {
+        let artifact = |name: &str, created_at: &str, expired| Artifact {
+            id: 11,
+            node_id: "MDg6QXJ0aWZhY3QxMQ==".into(),
+            name: name.into(),
+            size_in_bytes: 556,
+            url: "https://api.github.com/repos/octo-org/octo-docs/actions/artifacts/11".into(),
+            archive_download_url:
+                "https://api.github.com/repos/octo-org/octo-docs/actions/artifacts/11/zip".into(),
+            expired,
+            created_at: Some(created_at.into()),
+            expires_at: None,
+            updated_at: None,
+            workflow_run: None,
+        };
+        let cutoff = "2020-09-13T12:26:40Z".to_string();
+        let cleanup = ArtifactCleanup::builder()
+            .older_than_days(30)
+            .name("coverage-*")
+            .build();
+        assert!(cleanup.matches(
+            &artifact("coverage-linux", "2020-08-01T00:00:00Z", false),
+            Some(&cutoff)
+        ));
+        assert!(!cleanup.matches(
+            &artifact("coverage-linux", "2020-09-14T00:00:00Z", false),
+            Some(&cutoff)
+        ));
+        assert!(!cleanup.matches(
+            &artifact("coverage-linux", "2020-08-01T00:00:00Z", true),
+            Some(&cutoff)
+        ));
+        assert!(!cleanup.matches(
+            &artifact("logs", "2020-08-01T00:00:00Z", false),
+            Some(&cutoff)
+        ));
+    }
+
+    #[test]
+    fn run_timing_total() {
+        let timing: RunTiming = serde_json::from_str(
+            r#"{
+              "billable": {
+                "UBUNTU": {"total_ms": 180000, "jobs": 1, "job_runs": [{"job_id": 1, "duration_ms": 180000}]},
+                "MACOS": {"total_ms": 240000, "jobs": 4, "job_runs": []},
+                "WINDOWS": {"total_ms": 0, "jobs": 0}
+              },
+              "run_duration_ms": 500000
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(timing.total_ms(), 420000);
+        assert_eq!(
+            timing.billable.ubuntu.unwrap().job_runs[0].duration_ms,
+            180000
+        );
+    }
+
+    #[test]
+    fn subject_claim_reqs() {
+        assert_eq!(
+            serde_json::to_string(&SubjectClaim::custom(vec!["repo", "context"])).unwrap(),
+            r#"{"use_default":false,"include_claim_keys":["repo","context"]}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&SubjectClaim::default_template()).unwrap(),
+            r#"{"use_default":true}"#
+        );
+    }
+
+    #[test]
+    fn runner_group_reqs() {
+        let options = RunnerGroupOptions::builder("deploy")
+            .visibility(Visibility::Selected)
+            .selected_repository_ids(vec![1296269])
+            .restricted_to_workflows(true)
+            .selected_workflows(vec![
+                "octo-org/octo-repo/.github/workflows/deploy.yaml@main",
+            ])
+            .build();
+        assert_eq!(
+            serde_json::to_string(&options).unwrap(),
+            concat!(
+                r#"{"name":"deploy","visibility":"selected","selected_repository_ids":[1296269],"#,
+                r#""restricted_to_workflows":true,"#,
+                r#""selected_workflows":["octo-org/octo-repo/.github/workflows/deploy.yaml@main"]}"#
+            )
+        );
+    }
+}
@@ -0,0 +1,325 @@
+//! Enterprise administration interface
+//!
+//! These endpoints are only available on Github Enterprise Server, to site
+//! administrators. Enable the `enterprise` feature to use them.
+//!
+//! See the [github docs](https://docs.github.com/en/enterprise-server@latest/rest/enterprise-admin)
+//! for more information.
+
+pub mod pre_receive;
+
+use std::collections::HashMap;
+
+use futures::future;
+use hyper::client::connect::Connect;
+use serde_json;
+
+use self::pre_receive::{PreReceiveEnvironments, PreReceiveHookEnforcements, PreReceiveHooks};
+use hooks::HookConfig;
+use users::User;
+use {unfold, Future, Github, Stream};
+
+fn identity<T>(x: T) -> T {
+    x
+}
+
+/// Provides access to the administration of a Github Enterprise Server instance
+pub struct Admin<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+}
+
+impl<C: Clone + Connect + 'static> Admin<C> {
+    #[doc(hidden)]
+    pub fn new(github: Github<C>) -> Self {
+        Admin { github }
+    }
+
+    /// create a user. instances using built-in authentication send the
+    /// user an email to set their password
+    pub fn create_user<L, E>(&self, login: L, email: E) -> Future<User>
+    where
+        L: Into<String>,
+        E: Into<String>,
+    {
+        let mut payload = HashMap::new();
+        payload.insert("login", login.into());
+        payload.insert("email", email.into());
+        self.github.post("/admin/users", json!(payload))
+    }
+
+    /// rename a user
+    pub fn rename_user<U, L>(&self, user: U, login: L) -> Future<()>
+    where
+        U: Into<String>,
+        L: Into<String>,
+    {
+        let mut payload = HashMap::new();
+        payload.insert("login", login.into());
+        self.github
+            .patch_no_response(&format!("/admin/users/{}", user.into()), json!(payload))
+    }
+
+    /// delete a user, along with their repositories, gists and other data.
+    /// consider suspending them instead
+    pub fn delete_user<U>(&self, user: U) -> Future<()>
+    where
+        U: Into<String>,
+    {
+        self.github.delete(&format!("/admin/users/{}", user.into()))
+    }
+
+    /// suspend a user, recording the reason in the audit log. users of
+    /// instances using LDAP, CAS or SAML are suspended by the identity
+    /// provider instead
+    pub fn suspend<U, R>(&self, user: U, reason: Option<R>) -> Future<()>
+    where
+        U: Into<String>,
+        R: Into<String>,
+    {
+        let mut payload = HashMap::new();
+        if let Some(reason) = reason {
+            payload.insert("reason", reason.into());
+        }
+        self.github
+            .put_no_response(&format!("/users/{}/suspended", user.into()), json!(payload))
+    }
+
+    /// unsuspend a user
+    pub fn unsuspend<U>(&self, user: U) -> Future<()>
+    where
+        U: Into<String>,
+    {
+        self.github
+            .delete(&format!("/users/{}/suspended", user.into()))
+    }
+
+    /// promote a user to site administrator
+    pub fn promote<U>(&self, user: U) -> Future<()>
+    where
+        U: Into<String>,
+    {
+        self.github
+            .put_no_response(&format!("/users/{}/site_admin", user.into()), Vec::new())
+    }
+
+    /// demote a site administrator to an ordinary user
+    pub fn demote<U>(&self, user: U) -> Future<()>
+    where
+        U: Into<String>,
+    {
+        self.github
+            .delete(&format!("/users/{}/site_admin", user.into()))
+    }
+
+    /// queue a sync of a user's LDAP mapping
+    pub fn sync_ldap_user<U>(&self, user: U) -> Future<LdapSync>
+    where
+        U: Into<String>,
+    {
+        self.github.post(
+            &format!("/admin/ldap/users/{}/sync", user.into()),
+            Vec::new(),
+        )
+    }
+
+    /// queue a sync of a team's LDAP mapping
+    pub fn sync_ldap_team(&self, team_id: u64) -> Future<LdapSync> {
+        self.github
+            .post(&format!("/admin/ldap/teams/{}/sync", team_id), Vec::new())
+    }
+
+    /// returns a reference to an interface for global webhooks, which
+    /// are notified of user and organization events across the instance
+    pub fn hooks(&self) -> GlobalHooks<C> {
+        GlobalHooks::new(self.github.clone())
+    }
+
+    /// returns a reference to an interface for the environments
+    /// pre-receive hooks run in
+    pub fn pre_receive_environments(&self) -> PreReceiveEnvironments<C> {
+        PreReceiveEnvironments::new(self.github.clone())
+    }
+
+    /// returns a reference to an interface for pre-receive hooks
+    pub fn pre_receive_hooks(&self) -> PreReceiveHooks<C> {
+        PreReceiveHooks::new(self.github.clone())
+    }
+
+    /// returns a reference to an interface for the enforcement of
+    /// pre-receive hooks for an organization
+    pub fn org_pre_receive_hooks<O>(&self, org: O) -> PreReceiveHookEnforcements<C>
+    where
+        O: Into<String>,
+    {
+        PreReceiveHookEnforcements::new(self.github.clone(), format!("/orgs/{}", org.into()))
+    }
+
+    /// returns a reference to an interface for the enforcement of
+    /// pre-receive hooks for a repository
+    pub fn repo_pre_receive_hooks<O, R>(&self, owner: O, repo: R) -> PreReceiveHookEnforcements<C>
+    where
+        O: Into<String>,
+        R: Into<String>,
+    {
+        PreReceiveHookEnforcements::new(
+            self.github.clone(),
+            format!("/repos/{}/{}", owner.into(), repo.into()),
+        )
+    }
+}
+
+/// Provides access to the global webhooks of a Github Enterprise Server instance
+pub struct GlobalHooks<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+}
+
+impl<C: Clone + Connect + 'static> GlobalHooks<C> {
+    #[doc(hidden)]
+    pub fn new(github: Github<C>) -> Self {
+        GlobalHooks { github }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!("/admin/hooks{}", more)
+    }
+
+    /// list global webhooks
+    pub fn list(&self) -> Future<Vec<GlobalHook>> {
+        self.github.get(&self.path(""))
+    }
+
+    /// provides a stream over all pages of global webhooks
+    pub fn iter(&self) -> Stream<GlobalHook> {
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&self.path("")),
+            identity,
+        )
+    }
+
+    /// get a global webhook by id
+    pub fn get(&self, id: u64) -> Future<GlobalHook> {
+        self.github.get(&self.path(&format!("/{}", id)))
+    }
+
+    /// create a global webhook
+    pub fn create(&self, options: &GlobalHookOptions) -> Future<GlobalHook> {
+        self.github.post(&self.path(""), json!(options))
+    }
+
+    /// update a global webhook. the config replaces the existing one
+    pub fn update(&self, id: u64, options: &GlobalHookOptions) -> Future<GlobalHook> {
+        self.github
+            .patch(&self.path(&format!("/{}", id)), json!(options))
+    }
+
+    /// delete a global webhook
+    pub fn delete(&self, id: u64) -> Future<()> {
+        self.github.delete(&self.path(&format!("/{}", id)))
+    }
+
+    /// trigger a ping event to be sent to a global webhook
+    pub fn ping(&self, id: u64) -> Future<()> {
+        self.github
+            .post_no_response(&self.path(&format!("/{}/pings", id)), Vec::new())
+    }
+}
+
+// representations
+
+#[derive(Debug, Deserialize)]
+pub struct LdapSync {
+    /// `queued` once the sync has been queued
+    pub status: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GlobalHook {
+    pub id: u64,
+    pub url: String,
+    pub ping_url: String,
+    pub name: String,
+    /// global webhooks support the `organization` and `user` events
+    pub events: Vec<String>,
+    pub active: bool,
+    pub config: HookConfig,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// options for creating or updating a global webhook
+#[derive(Debug, Serialize)]
+pub struct GlobalHookOptions {
+    /// always `web`
+    pub name: String,
+    pub config: HookConfig,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active: Option<bool>,
+}
+
+impl GlobalHookOptions {
+    /// deliveries are sent to the url of `config`
+    pub fn builder(config: HookConfig) -> GlobalHookOptionsBuilder {
+        GlobalHookOptionsBuilder(GlobalHookOptions {
+            name: "web".into(),
+            config,
+            events: Vec::new(),
+            active: None,
+        })
+    }
+}
+
+pub struct GlobalHookOptionsBuilder(GlobalHookOptions);
+
+impl GlobalHookOptionsBuilder {
+    /// the events deliveries are sent for. defaults to `user` and `organization`
+    pub fn events<E>(&mut self, events: Vec<E>) -> &mut Self
+    where
+        E: Into<String>,
+    {
+        self.0.events = events.into_iter().map(|e| e.into()).collect();
+        self
+    }
+
+    pub fn active(&mut self, active: bool) -> &mut Self {
+        self.0.active = Some(active);
+        self
+    }
+
+    pub fn build(&self) -> GlobalHookOptions {
+        GlobalHookOptions {
+            name: self.0.name.clone(),
+            config: self.0.config.clone(),
+            events: self.0.events.clone(),
+            active: self.0.active,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn global_hook_options_reqs() {
+        let config = HookConfig {
+            url: Some("https://example.com/hook".into()),
+            ..Default::default()
+        };
+        let options = GlobalHookOptions::builder(config)
+            .events(vec!["user"])
+            .build();
+        assert_eq!(
+            serde_json::to_string(&options).unwrap(),
+            r#"{"name":"web","config":{"url":"https://example.com/hook"},"events":["user"]}"#
+        );
+    }
+}
@@ -0,0 +1,434 @@
+//! Pre-receive hooks interface
+//!
+//! Pre-receive hooks run scripts in an environment on the server to accept
+//! or reject pushes. Site administrators define environments and hooks,
+//! and organizations and repositories may override their enforcement when
+//! allowed to.
+//!
+//! See the [github docs](https://docs.github.com/en/enterprise-server@latest/rest/enterprise-admin/pre-receive-hooks)
+//! for more information.
+
+use std::fmt;
+
+use futures::future;
+use hyper::client::connect::Connect;
+use serde_json;
+
+use {unfold, Future, Github, Stream};
+
+fn identity<T>(x: T) -> T {
+    x
+}
+
+/// Whether a pre-receive hook rejects pushes
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Enforcement {
+    Enabled,
+    Disabled,
+    /// run the hook, reporting failures to pushers without rejecting pushes
+    Testing,
+}
+
+impl fmt::Display for Enforcement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Enforcement::Enabled => "enabled",
+            Enforcement::Disabled => "disabled",
+            Enforcement::Testing => "testing",
+        }
+        .fmt(f)
+    }
+}
+
+/// Provides access to the environments pre-receive hooks run in
+pub struct PreReceiveEnvironments<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+}
+
+impl<C: Clone + Connect + 'static> PreReceiveEnvironments<C> {
+    #[doc(hidden)]
+    pub fn new(github: Github<C>) -> Self {
+        PreReceiveEnvironments { github }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!("/admin/pre-receive-environments{}", more)
+    }
+
+    /// list pre-receive environments
+    pub fn list(&self) -> Future<Vec<PreReceiveEnvironment>> {
+        self.github.get(&self.path(""))
+    }
+
+    /// provides a stream over all pages of pre-receive environments
+    pub fn iter(&self) -> Stream<PreReceiveEnvironment> {
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&self.path("")),
+            identity,
+        )
+    }
+
+    /// get a pre-receive environment by id
+    pub fn get(&self, id: u64) -> Future<PreReceiveEnvironment> {
+        self.github.get(&self.path(&format!("/{}", id)))
+    }
+
+    /// create a pre-receive environment from a tarball, which is
+    /// downloaded asynchronously
+    pub fn create(&self, options: &PreReceiveEnvironmentOptions) -> Future<PreReceiveEnvironment> {
+        self.github.post(&self.path(""), json!(options))
+    }
+
+    /// update a pre-receive environment. the default environment can't be updated
+    pub fn update(
+        &self,
+        id: u64,
+        options: &PreReceiveEnvironmentOptions,
+    ) -> Future<PreReceiveEnvironment> {
+        self.github
+            .patch(&self.path(&format!("/{}", id)), json!(options))
+    }
+
+    /// delete a pre-receive environment no hooks use
+    pub fn delete(&self, id: u64) -> Future<()> {
+        self.github.delete(&self.path(&format!("/{}", id)))
+    }
+
+    /// download the tarball of a pre-receive environment again
+    pub fn download(&self, id: u64) -> Future<PreReceiveEnvironmentDownload> {
+        self.github
+            .post(&self.path(&format!("/{}/downloads", id)), Vec::new())
+    }
+
+    /// get the state of the latest download of a pre-receive environment
+    pub fn latest_download(&self, id: u64) -> Future<PreReceiveEnvironmentDownload> {
+        self.github
+            .get(&self.path(&format!("/{}/downloads/latest", id)))
+    }
+}
+
+/// Provides access to the pre-receive hooks of the server
+pub struct PreReceiveHooks<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+}
+
+impl<C: Clone + Connect + 'static> PreReceiveHooks<C> {
+    #[doc(hidden)]
+    pub fn new(github: Github<C>) -> Self {
+        PreReceiveHooks { github }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!("/admin/pre-receive-hooks{}", more)
+    }
+
+    /// list pre-receive hooks
+    pub fn list(&self) -> Future<Vec<PreReceiveHook>> {
+        self.github.get(&self.path(""))
+    }
+
+    /// provides a stream over all pages of pre-receive hooks
+    pub fn iter(&self) -> Stream<PreReceiveHook> {
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&self.path("")),
+            identity,
+        )
+    }
+
+    /// get a pre-receive hook by id
+    pub fn get(&self, id: u64) -> Future<PreReceiveHook> {
+        self.github.get(&self.path(&format!("/{}", id)))
+    }
+
+    /// create a pre-receive hook
+    pub fn create(&self, options: &PreReceiveHookOptions) -> Future<PreReceiveHook> {
+        self.github.post(&self.path(""), json!(options))
+    }
+
+    /// update a pre-receive hook
+    pub fn update(&self, id: u64, options: &PreReceiveHookOptions) -> Future<PreReceiveHook> {
+        self.github
+            .patch(&self.path(&format!("/{}", id)), json!(options))
+    }
+
+    /// delete a pre-receive hook
+    pub fn delete(&self, id: u64) -> Future<()> {
+        self.github.delete(&self.path(&format!("/{}", id)))
+    }
+}
+
+/// Provides access to the enforcement of pre-receive hooks for an
+/// organization or repository
+pub struct PreReceiveHookEnforcements<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    path: String,
+}
+
+impl<C: Clone + Connect + 'static> PreReceiveHookEnforcements<C> {
+    #[doc(hidden)]
+    pub fn new<P>(github: Github<C>, path: P) -> Self
+    where
+        P: Into<String>,
+    {
+        PreReceiveHookEnforcements {
+            github,
+            path: path.into(),
+        }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!("{}/pre-receive-hooks{}", self.path, more)
+    }
+
+    /// list the pre-receive hooks available, with their enforcement
+    pub fn list(&self) -> Future<Vec<PreReceiveHookEnforcement>> {
+        self.github.get(&self.path(""))
+    }
+
+    /// provides a stream over all pages of the pre-receive hooks available
+    pub fn iter(&self) -> Stream<PreReceiveHookEnforcement> {
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&self.path("")),
+            identity,
+        )
+    }
+
+    /// get the enforcement of a pre-receive hook
+    pub fn get(&self, id: u64) -> Future<PreReceiveHookEnforcement> {
+        self.github.get(&self.path(&format!("/{}", id)))
+    }
+
+    /// override the enforcement of a pre-receive hook, when the hook
+    /// allows downstream configuration
+    pub fn update(
+        &self,
+        id: u64,
+        options: &PreReceiveHookEnforcementOptions,
+    ) -> Future<PreReceiveHookEnforcement> {
+        self.github
+            .patch(&self.path(&format!("/{}", id)), json!(options))
+    }
+
+    /// remove an override, inheriting the enforcement of a pre-receive hook again
+    pub fn remove(&self, id: u64) -> Future<PreReceiveHookEnforcement> {
+        self.github
+            .delete_message(&self.path(&format!("/{}", id)), Vec::new())
+    }
+}
+
+// representations
+
+#[derive(Debug, Deserialize)]
+pub struct PreReceiveEnvironment {
+    pub id: u64,
+    pub name: String,
+    /// the url of the tarball the environment was created from
+    pub image_url: String,
+    pub url: String,
+    pub html_url: String,
+    /// whether this is the default environment, which can't be changed
+    pub default_environment: bool,
+    pub created_at: Option<String>,
+    pub hooks_count: u64,
+    pub download: PreReceiveEnvironmentDownload,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PreReceiveEnvironmentDownload {
+    pub url: String,
+    /// one of `not_started`, `in_progress`, `success` or `failed`
+    pub state: String,
+    pub downloaded_at: Option<String>,
+    /// the reason a download failed
+    pub message: Option<String>,
+}
+
+/// options for creating or updating a pre-receive environment
+#[derive(Debug, Serialize)]
+pub struct PreReceiveEnvironmentOptions {
+    pub name: String,
+    pub image_url: String,
+}
+
+impl PreReceiveEnvironmentOptions {
+    pub fn new<N, I>(name: N, image_url: I) -> Self
+    where
+        N: Into<String>,
+        I: Into<String>,
+    {
+        PreReceiveEnvironmentOptions {
+            name: name.into(),
+            image_url: image_url.into(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PreReceiveHook {
+    pub id: u64,
+    pub name: String,
+    pub enforcement: Enforcement,
+    /// the path of the script within its repository
+    pub script: String,
+    pub script_repository: ScriptRepository,
+    pub environment: PreReceiveEnvironment,
+    /// whether organizations and repositories may override the enforcement
+    pub allow_downstream_configuration: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScriptRepository {
+    #[serde(skip_serializing)]
+    pub id: Option<u64>,
+    pub full_name: String,
+    #[serde(skip_serializing)]
+    pub url: Option<String>,
+    #[serde(skip_serializing)]
+    pub html_url: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct EnvironmentRef {
+    id: u64,
+}
+
+/// options for creating or updating a pre-receive hook
+#[derive(Debug, Serialize)]
+pub struct PreReceiveHookOptions {
+    pub name: String,
+    pub script: String,
+    pub script_repository: ScriptRepository,
+    environment: EnvironmentRef,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enforcement: Option<Enforcement>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_downstream_configuration: Option<bool>,
+}
+
+impl PreReceiveHookOptions {
+    /// `script` is the path of the script in the repository named by
+    /// `script_repository`, like `octo-org/hooks`
+    pub fn builder<N, S, R>(
+        name: N,
+        script: S,
+        script_repository: R,
+        environment_id: u64,
+    ) -> PreReceiveHookOptionsBuilder
+    where
+        N: Into<String>,
+        S: Into<String>,
+        R: Into<String>,
+    {
+        PreReceiveHookOptionsBuilder(PreReceiveHookOptions {
+            name: name.into(),
+            script: script.into(),
+            script_repository: ScriptRepository {
+                id: None,
+                full_name: script_repository.into(),
+                url: None,
+                html_url: None,
+            },
+            environment: EnvironmentRef { id: environment_id },
+            enforcement: None,
+            allow_downstream_configuration: None,
+        })
+    }
+}
+
+pub struct PreReceiveHookOptionsBuilder(PreReceiveHookOptions);
+
+impl PreReceiveHookOptionsBuilder {
+    pub fn enforcement(&mut self, enforcement: Enforcement) -> &mut Self {
+        self.0.enforcement = Some(enforcement);
+        self
+    }
+
+    /// allow organizations and repositories to override the enforcement
+    pub fn allow_downstream_configuration(&mut self, allow: bool) -> &mut Self {
+        self.0.allow_downstream_configuration = Some(allow);
+        self
+    }
+
+    pub fn build(&self) -> PreReceiveHookOptions {
+        PreReceiveHookOptions {
+            name: self.0.name.clone(),
+            script: self.0.script.clone(),
+            script_repository: ScriptRepository {
+                id: None,
+                full_name: self.0.script_repository.full_name.clone(),
+                url: None,
+                html_url: None,
+            },
+            environment: EnvironmentRef {
+                id: self.0.environment.id,
+            },
+            enforcement: self.0.enforcement,
+            allow_downstream_configuration: self.0.allow_downstream_configuration,
+        }
+    }
+}
+
+/// A pre-receive hook as configured for an organization or repository
+#[derive(Debug, Deserialize)]
+pub struct PreReceiveHookEnforcement {
+    pub id: u64,
+    pub name: String,
+    pub enforcement: Enforcement,
+    /// the url of the level the enforcement is configured at
+    pub configuration_url: String,
+    /// for organizations, whether their repositories may override the enforcement
+    pub allow_downstream_configuration: Option<bool>,
+}
+
+/// options for overriding the enforcement of a pre-receive hook
+#[derive(Debug, Default, Serialize)]
+pub struct PreReceiveHookEnforcementOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enforcement: Option<Enforcement>,
+    /// only applies to organizations
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_downstream_configuration: Option<bool>,
+}
+
+impl PreReceiveHookEnforcementOptions {
+    pub fn new(enforcement: Enforcement) -> Self {
+        PreReceiveHookEnforcementOptions {
+            enforcement: Some(enforcement),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pre_receive_hook_options_reqs() {
+        let options = PreReceiveHookOptions::builder(
+            "Check commits",
+            "scripts/check.sh",
+            "octo-org/hooks",
+            2,
+        )
+        .enforcement(Enforcement::Testing)
+        .build();
+        assert_eq!(
+            serde_json::to_string(&options).unwrap(),
+            r#"{"name":"Check commits","script":"scripts/check.sh","script_repository":{"full_name":"octo-org/hooks"},"environment":{"id":2},"enforcement":"testing"}"#
+        );
+    }
+}
@@ -3,6 +3,7 @@
 use hyper::client::connect::Connect;
 
 use self::super::{AuthenticationConstraint, Future, Github, MediaType};
+use ids::InstallationId;
 
 pub struct App<C>
 where
@@ -24,9 +25,15 @@ where
         format!("/app{}", more)
     }
 
-    pub fn make_access_token(&self, installation_id: u64) -> Future<AccessToken> {
+    pub fn make_access_token<I>(&self, installation_id: I) -> Future<AccessToken>
+    where
+        I: Into<InstallationId>,
+    {
         self.github.post_media::<AccessToken>(
-            &self.path(&format!("/installations/{}/access_tokens", installation_id)),
+            &self.path(&format!(
+                "/installations/{}/access_tokens",
+                installation_id.into()
+            )),
             Vec::new(),
             MediaType::Preview("machine-man"),
             AuthenticationConstraint::JWT,
@@ -36,7 +43,8 @@ where
     pub fn find_repo_installation<O, R>(&self, owner: O, repo: R) -> Future<Installation>
     where
         O: Into<String>,
-        R: Into<String> {
+        R: Into<String>,
+    {
         self.github.get_media::<Installation>(
             &format!("/repos/{}/{}/installation", owner.into(), repo.into()),
             MediaType::Preview("machine-man"),
@@ -54,7 +62,7 @@ pub struct AccessToken {
 
 #[derive(Debug, Deserialize)]
 pub struct Installation {
-    pub id: u64,
+    pub id: InstallationId,
     // account: Account
     pub access_tokens_url: String,
     pub repositories_url: String,
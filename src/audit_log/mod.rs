@@ -0,0 +1,247 @@
+//! Audit log interface
+//!
+//! See the [github docs](https://docs.github.com/en/rest/orgs/orgs#get-the-audit-log-for-an-organization)
+//! for more information
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use futures::Future as StdFuture;
+use hyper::client::connect::Connect;
+use serde_json;
+use url::{form_urlencoded, Url};
+
+use {next_link, unfold, Future, Github, SortDirection, Stream};
+
+fn identity<T>(x: T) -> T {
+    x
+}
+
+/// The kinds of events included in the audit log
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Include {
+    Web,
+    Git,
+    All,
+}
+
+impl fmt::Display for Include {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Include::Web => "web",
+            Include::Git => "git",
+            Include::All => "all",
+        }
+        .fmt(f)
+    }
+}
+
+/// Provides access to the audit log of an organization
+pub struct AuditLog<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    org: String,
+}
+
+impl<C: Clone + Connect + 'static> AuditLog<C> {
+    #[doc(hidden)]
+    pub fn new<O>(github: Github<C>, org: O) -> Self
+    where
+        O: Into<String>,
+    {
+        AuditLog {
+            github,
+            org: org.into(),
+        }
+    }
+
+    fn path(&self, options: &AuditLogOptions) -> String {
+        let mut uri = vec![format!("/orgs/{}/audit-log", self.org)];
+        if let Some(query) = options.serialize() {
+            uri.push(query);
+        }
+        uri.join("?")
+    }
+
+    /// list audit log events
+    pub fn list(&self, options: &AuditLogOptions) -> Future<Vec<AuditLogEntry>> {
+        self.github.get(&self.path(options))
+    }
+
+    /// get a page of audit log events along with the cursor of the next page.
+    /// Pass the cursor to `AuditLogOptionsBuilder::after` to continue from
+    /// where a previous poll left off
+    pub fn page(&self, options: &AuditLogOptions) -> Future<AuditLogPage> {
+        Box::new(
+            self.github
+                .get_pages::<Vec<AuditLogEntry>>(&self.path(options))
+                .map(|(link, entries)| AuditLogPage {
+                    after: link
+                        .as_ref()
+                        .and_then(next_link)
+                        .and_then(|url| cursor(&url, "after")),
+                    entries,
+                }),
+        )
+    }
+
+    /// provides a stream over all pages of audit log events
+    pub fn iter(&self, options: &AuditLogOptions) -> Stream<AuditLogEntry> {
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&self.path(options)),
+            identity,
+        )
+    }
+}
+
+fn cursor(url: &str, name: &str) -> Option<String> {
+    Url::parse(url).ok().and_then(|url| {
+        url.query_pairs()
+            .find(|&(ref key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    })
+}
+
+// representations
+
+/// A page of audit log events
+#[derive(Debug)]
+pub struct AuditLogPage {
+    pub entries: Vec<AuditLogEntry>,
+    /// the cursor of the next page, if any
+    pub after: Option<String>,
+}
+
+/// An audit log event. The fields common to all actions are typed, the
+/// action specific ones are collected in `fields`
+#[derive(Debug, Deserialize)]
+pub struct AuditLogEntry {
+    /// the event's action, like `repo.create` or `org.add_member`
+    pub action: String,
+    /// milliseconds since the epoch
+    #[serde(rename = "@timestamp")]
+    pub timestamp: Option<u64>,
+    #[serde(rename = "_document_id")]
+    pub document_id: Option<String>,
+    pub actor: Option<String>,
+    pub actor_id: Option<u64>,
+    pub user: Option<String>,
+    pub user_id: Option<u64>,
+    pub org: Option<String>,
+    pub org_id: Option<u64>,
+    pub repo: Option<String>,
+    pub business: Option<String>,
+    pub operation_type: Option<String>,
+    pub created_at: Option<u64>,
+    #[serde(flatten)]
+    pub fields: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Default)]
+pub struct AuditLogOptions {
+    params: HashMap<&'static str, String>,
+}
+
+impl AuditLogOptions {
+    pub fn builder() -> AuditLogOptionsBuilder {
+        AuditLogOptionsBuilder::default()
+    }
+
+    /// serialize options as a string. returns None if no options are defined
+    pub fn serialize(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded)
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct AuditLogOptionsBuilder(AuditLogOptions);
+
+impl AuditLogOptionsBuilder {
+    /// a search phrase, like `action:repo.create actor:octocat`
+    pub fn phrase<P>(&mut self, phrase: P) -> &mut Self
+    where
+        P: Into<String>,
+    {
+        self.0.params.insert("phrase", phrase.into());
+        self
+    }
+
+    pub fn include(&mut self, include: Include) -> &mut Self {
+        self.0.params.insert("include", include.to_string());
+        self
+    }
+
+    /// list events after a cursor
+    pub fn after<A>(&mut self, cursor: A) -> &mut Self
+    where
+        A: Into<String>,
+    {
+        self.0.params.insert("after", cursor.into());
+        self
+    }
+
+    /// list events before a cursor
+    pub fn before<B>(&mut self, cursor: B) -> &mut Self
+    where
+        B: Into<String>,
+    {
+        self.0.params.insert("before", cursor.into());
+        self
+    }
+
+    /// the order of events by timestamp. defaults to `Desc`
+    pub fn order(&mut self, order: SortDirection) -> &mut Self {
+        self.0.params.insert("order", order.to_string());
+        self
+    }
+
+    pub fn per_page(&mut self, n: usize) -> &mut Self {
+        self.0.params.insert("per_page", n.to_string());
+        self
+    }
+
+    pub fn build(&self) -> AuditLogOptions {
+        AuditLogOptions {
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_cursor() {
+        assert_eq!(
+            cursor(
+                "https://api.github.com/organizations/1/audit-log?after=MS42NjQzODM&before=",
+                "after"
+            ),
+            Some("MS42NjQzODM".to_owned())
+        );
+    }
+
+    #[test]
+    fn deserialize_entry() {
+        let entry = serde_json::from_str::<AuditLogEntry>(
+            r#"{"@timestamp":1606929874512,"action":"team.add_member","actor":"octocat","team":"octo-org/octo-team"}"#,
+        )
+        .unwrap();
+        assert_eq!(entry.action, "team.add_member");
+        assert_eq!(
+            entry.fields.get("team"),
+            Some(&serde_json::Value::from("octo-org/octo-team"))
+        );
+    }
+}
@@ -0,0 +1,157 @@
+//! Batch interface
+//!
+//! Runs many requests concurrently, bounded by a parallelism limit. Before
+//! each request is started, the rate limit quota reported by the most
+//! recent response is consulted. When fewer requests than a threshold
+//! remain, requests are held back until the rate limit window resets.
+//!
+//! ```no_run
+//! extern crate futures;
+//! extern crate hubcaps;
+//! extern crate tokio;
+//!
+//! use futures::Stream;
+//! use hubcaps::{Credentials, Github};
+//! use tokio::runtime::Runtime;
+//!
+//! fn main() {
+//!     let mut rt = Runtime::new().unwrap();
+//!     let github = Github::new(
+//!         "my-cool-user-agent/0.1.0",
+//!         Credentials::Token("personal-access-token".into()),
+//!     );
+//!     let repos = vec!["hubcaps", "envy", "dynomite"];
+//!     let requests = repos
+//!         .into_iter()
+//!         .map(|repo| github.repo("softprops", repo).get())
+//!         .collect::<Vec<_>>();
+//!     let batch = github.batch().parallelism(2).min_remaining(50).run(requests);
+//!     for repo in rt.block_on(batch.collect()).unwrap() {
+//!         println!("{}", repo.full_name);
+//!     }
+//! }
+//! ```
+
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use futures::{future, stream, Future as StdFuture, Stream as StdStream};
+use hyper::client::connect::Connect;
+use tokio_timer::Delay;
+
+use {Error, Future, Github, Quota, Stream};
+
+const DEFAULT_PARALLELISM: usize = 8;
+const DEFAULT_MIN_REMAINING: u32 = 100;
+
+/// Runs many requests concurrently without exhausting the rate limit quota
+pub struct Batch<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    parallelism: usize,
+    min_remaining: u32,
+}
+
+impl<C: Clone + Connect + 'static> Batch<C> {
+    #[doc(hidden)]
+    pub fn new(github: Github<C>) -> Self {
+        Batch {
+            github,
+            parallelism: DEFAULT_PARALLELISM,
+            min_remaining: DEFAULT_MIN_REMAINING,
+        }
+    }
+
+    /// the maximum number of requests in flight at once. defaults to 8
+    pub fn parallelism(&mut self, parallelism: usize) -> &mut Self {
+        self.parallelism = parallelism.max(1);
+        self
+    }
+
+    /// hold back requests while fewer than this many requests remain in
+    /// the current rate limit window. defaults to 100
+    pub fn min_remaining(&mut self, min_remaining: u32) -> &mut Self {
+        self.min_remaining = min_remaining;
+        self
+    }
+
+    /// run requests, yielding their results in the order they complete.
+    /// requests are only started once a slot is free and enough quota remains
+    pub fn run<I, T>(&self, requests: I) -> Stream<T>
+    where
+        I: IntoIterator<Item = Future<T>>,
+        I::IntoIter: Send + 'static,
+        T: Send + 'static,
+    {
+        let github = self.github.clone();
+        let min_remaining = self.min_remaining;
+        Box::new(
+            stream::iter_ok::<_, Error>(requests)
+                .map(move |request| {
+                    wait_for_quota(github.quota(), min_remaining).and_then(move |_| request)
+                })
+                .buffer_unordered(self.parallelism),
+        )
+    }
+}
+
+/// resolves once `quota` permits another request
+fn wait_for_quota(quota: Option<Quota>, min_remaining: u32) -> Future<()> {
+    match pause(quota, min_remaining, SystemTime::now()) {
+        Some(duration) => {
+            debug!(
+                "rate limit quota below {}, pausing for {:?}",
+                min_remaining, duration
+            );
+            Box::new(
+                Delay::new(Instant::now() + duration)
+                    .map_err(|err| Error::from(format!("batch timer failed: {}", err))),
+            )
+        }
+        None => Box::new(future::ok(())),
+    }
+}
+
+/// how long to wait for the rate limit window to reset, if at all
+fn pause(quota: Option<Quota>, min_remaining: u32, now: SystemTime) -> Option<Duration> {
+    let quota = quota?;
+    if quota.remaining >= min_remaining {
+        return None;
+    }
+    let now = now.duration_since(UNIX_EPOCH).ok()?.as_secs();
+    let reset = u64::from(quota.reset);
+    if reset > now {
+        Some(Duration::from_secs(reset - now))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pause_below_threshold() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_000);
+        let quota = Quota {
+            remaining: 10,
+            reset: 1_060,
+        };
+        assert_eq!(pause(None, 100, now), None);
+        assert_eq!(pause(Some(quota), 5, now), None);
+        assert_eq!(pause(Some(quota), 100, now), Some(Duration::from_secs(60)));
+        assert_eq!(
+            pause(
+                Some(Quota {
+                    reset: 900,
+                    ..quota
+                }),
+                100,
+                now
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,112 @@
+//! Billing interface
+//!
+//! See the [github docs](https://docs.github.com/en/rest/billing) for more information
+
+use std::collections::BTreeMap;
+
+use hyper::client::connect::Connect;
+
+use {Future, Github};
+
+/// Provides access to the billing usage of an organization or user
+pub struct Billing<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    owner: String,
+}
+
+impl<C: Clone + Connect + 'static> Billing<C> {
+    #[doc(hidden)]
+    pub fn new<O>(github: Github<C>, owner: O) -> Self
+    where
+        O: Into<String>,
+    {
+        Billing {
+            github,
+            owner: owner.into(),
+        }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!("{}/settings/billing{}", self.owner, more)
+    }
+
+    /// get the Actions minutes used in the current billing cycle
+    pub fn actions(&self) -> Future<ActionsBilling> {
+        self.github.get(&self.path("/actions"))
+    }
+
+    /// get the Packages data transfer used in the current billing cycle
+    pub fn packages(&self) -> Future<PackagesBilling> {
+        self.github.get(&self.path("/packages"))
+    }
+
+    /// get the estimated Actions and Packages storage used in the current billing cycle
+    pub fn shared_storage(&self) -> Future<SharedStorageBilling> {
+        self.github.get(&self.path("/shared-storage"))
+    }
+}
+
+// representations
+
+#[derive(Debug, Deserialize)]
+pub struct ActionsBilling {
+    pub total_minutes_used: u64,
+    pub total_paid_minutes_used: f64,
+    pub included_minutes: u64,
+    pub minutes_used_breakdown: MinutesUsedBreakdown,
+}
+
+/// Minutes used per runner SKU. Minutes of larger runners, like
+/// `ubuntu_4_core`, are collected in `other`
+#[derive(Debug, Default, Deserialize)]
+pub struct MinutesUsedBreakdown {
+    #[serde(rename = "UBUNTU")]
+    pub ubuntu: Option<u64>,
+    #[serde(rename = "MACOS")]
+    pub macos: Option<u64>,
+    #[serde(rename = "WINDOWS")]
+    pub windows: Option<u64>,
+    #[serde(flatten)]
+    pub other: BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PackagesBilling {
+    pub total_gigabytes_bandwidth_used: u64,
+    pub total_paid_gigabytes_bandwidth_used: u64,
+    pub included_gigabytes_bandwidth: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SharedStorageBilling {
+    pub days_left_in_billing_cycle: u32,
+    pub estimated_paid_storage_for_month: f64,
+    pub estimated_storage_for_month: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn deserialize_actions_billing() {
+        let billing = serde_json::from_str::<ActionsBilling>(
+            r#"{
+                "total_minutes_used":305,
+                "total_paid_minutes_used":0,
+                "included_minutes":3000,
+                "minutes_used_breakdown":{"UBUNTU":205,"MACOS":10,"WINDOWS":90,"ubuntu_4_core":12}
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(billing.minutes_used_breakdown.ubuntu, Some(205));
+        assert_eq!(
+            billing.minutes_used_breakdown.other.get("ubuntu_4_core"),
+            Some(&12)
+        );
+    }
+}
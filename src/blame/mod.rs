@@ -0,0 +1,118 @@
+//! Blame interface
+//!
+//! Github's REST api has no blame endpoint, so blame is fetched through its
+//! GraphQL api. See `Repository::blame`, and the
+//! [github docs](https://docs.github.com/en/graphql/reference/objects#blame)
+//! for more information.
+
+pub(crate) const BLAME_QUERY: &str = r#"
+query($owner: String!, $repo: String!, $reference: String!, $path: String!) {
+  repository(owner: $owner, name: $repo) {
+    object(expression: $reference) {
+      ... on Commit {
+        blame(path: $path) {
+          ranges {
+            startingLine
+            endingLine
+            age
+            commit {
+              oid
+              abbreviatedOid
+              message
+              url
+              committedDate
+              author { name email date user { login } }
+            }
+          }
+        }
+      }
+    }
+  }
+}"#;
+
+// representations
+
+/// The lines of a file last changed by a commit
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlameRange {
+    /// the first line of the range, counting from 1
+    pub starting_line: u64,
+    /// the last line of the range, inclusive
+    pub ending_line: u64,
+    /// how recently the range changed, from 1 for the newest changes in the
+    /// file to 10 for the oldest
+    pub age: u8,
+    pub commit: BlameCommit,
+}
+
+impl BlameRange {
+    /// whether the range includes a line, counting from 1
+    pub fn contains(&self, line: u64) -> bool {
+        self.starting_line <= line && line <= self.ending_line
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlameCommit {
+    pub oid: String,
+    pub abbreviated_oid: String,
+    pub message: String,
+    pub url: String,
+    pub committed_date: String,
+    pub author: Option<BlameAuthor>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BlameAuthor {
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub date: Option<String>,
+    /// the Github user matching the author's email, if any
+    pub user: Option<BlameUser>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BlameUser {
+    pub login: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct Blame {
+    pub ranges: Vec<BlameRange>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn deserialize_blame_range() {
+        let range: BlameRange = serde_json::from_str(
+            r#"{
+              "startingLine": 3,
+              "endingLine": 7,
+              "age": 10,
+              "commit": {
+                "oid": "6dcb09b5b57875f334f61aebed695e2e4193db5e",
+                "abbreviatedOid": "6dcb09b",
+                "message": "Fix all the bugs",
+                "url": "https://github.com/octocat/Hello-World/commit/6dcb09b5b57875f334f61aebed695e2e4193db5e",
+                "committedDate": "2011-04-14T16:00:49Z",
+                "author": {
+                  "name": "Monalisa Octocat",
+                  "email": "support@github.com",
+                  "date": "2011-04-14T16:00:49Z",
+                  "user": {"login": "octocat"}
+                }
+              }
+            }"#,
+        )
+        .unwrap();
+        assert!(range.contains(7));
+        assert!(!range.contains(8));
+        assert_eq!(range.commit.author.unwrap().user.unwrap().login, "octocat");
+    }
+}
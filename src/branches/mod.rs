@@ -73,6 +73,30 @@ impl<C: Clone + Connect + 'static> Branches<C> {
         ))
     }
 
+    /// rename a branch. Github updates the branch protection rules, open
+    /// pull requests and, for the default branch, the repository's default
+    /// branch to match
+    ///
+    /// https://docs.github.com/en/rest/branches/branches#rename-a-branch
+    pub fn rename<B, N>(&self, branch: B, new_name: N) -> Future<Branch>
+    where
+        B: Into<String>,
+        N: Into<String>,
+    {
+        let payload = BranchRename {
+            new_name: new_name.into(),
+        };
+        self.github.post(
+            &format!(
+                "/repos/{owner}/{repo}/branches/{branch}/rename",
+                owner = self.owner,
+                repo = self.repo,
+                branch = branch.into()
+            ),
+            json!(payload),
+        )
+    }
+
     /// update branch production for a given branch
     ///
     /// https://developer.github.com/v3/repos/branches/#update-branch-protection
@@ -102,6 +126,11 @@ pub struct Branch {
     // todo: commit ref
 }
 
+#[derive(Debug, Serialize)]
+struct BranchRename {
+    new_name: String,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ProtectionState {
     pub required_status_checks: Option<StatusChecks>,
@@ -110,13 +139,13 @@ pub struct ProtectionState {
     //pub restrictions: Option<Restrictions>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct EnforceAdmins {
     pub url: String,
     pub enabled: bool,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Protection {
     pub required_status_checks: Option<StatusChecks>,
     pub enforce_admins: bool,
@@ -124,20 +153,20 @@ pub struct Protection {
     pub restrictions: Option<Restrictions>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Restrictions {
     pub users: Vec<String>,
     pub teams: Vec<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct RequiredPullRequestReviews {
     pub dismissal_restrictions: Restrictions,
     pub dismiss_stale_reviews: bool,
     pub require_code_owner_reviews: bool,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct StatusChecks {
     pub strict: bool,
     pub contexts: Vec<String>,
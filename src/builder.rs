@@ -0,0 +1,320 @@
+//! Client builder
+//!
+//! `GithubBuilder` configures everything about a `Github` client, from the
+//! host, credentials and user agent to retries, caching, proxying and the
+//! underlying connection's timeouts and pooling. Settings left unset fall
+//! back to hyper's defaults, which place no bound on how long connecting to
+//! or hearing back from Github may take.
+//!
+//! ```no_run
+//! extern crate hubcaps;
+//!
+//! use std::time::Duration;
+//!
+//! use hubcaps::{Credentials, Github, RetryPolicy};
+//!
+//! fn main() {
+//!     let github = Github::builder()
+//!         .agent("my-cool-user-agent/0.1.0")
+//!         .credentials(Credentials::Token("personal-access-token".into()))
+//!         .connect_timeout(Duration::from_secs(5))
+//!         .timeout(Duration::from_secs(30))
+//!         .max_idle_per_host(4)
+//!         .retry_policy(RetryPolicy::WaitForReset(Duration::from_secs(60)))
+//!         .build()
+//!         .unwrap();
+//! }
+//! ```
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use hyper::client::connect::Connect;
+use hyper::client::HttpConnector;
+use hyper::Client;
+#[cfg(feature = "proxy")]
+use hyper::Uri;
+#[cfg(feature = "proxy")]
+use hyper_proxy::{Intercept, Proxy, ProxyConnector};
+
+use limits::ResponseLimits;
+use logging::RequestLogging;
+use metrics::Metrics;
+use middleware::Middleware;
+use rate_budget::RateBudget;
+use tls;
+#[cfg(feature = "httpcache")]
+use {BoxedHttpCache, HttpCache};
+use {Credentials, Github, HttpsConnector, Result, RetryPolicy, TransientRetry, DEFAULT_HOST};
+
+/// Builds a `Github` client with custom connection settings
+#[derive(Debug)]
+pub struct GithubBuilder {
+    host: String,
+    agent: String,
+    credentials: Option<Credentials>,
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    keep_alive: bool,
+    keep_alive_timeout: Option<Duration>,
+    max_idle_per_host: Option<usize>,
+    root_certificates: Vec<Vec<u8>>,
+    retry_policy: RetryPolicy,
+    transient_retry: Option<TransientRetry>,
+    middleware: Vec<Arc<Middleware>>,
+    metrics: Option<Arc<Metrics>>,
+    logging: Option<RequestLogging>,
+    budget: Option<RateBudget>,
+    limits: ResponseLimits,
+    #[cfg(feature = "httpcache")]
+    http_cache: BoxedHttpCache,
+    #[cfg(feature = "proxy")]
+    proxy: Option<Uri>,
+}
+
+impl Default for GithubBuilder {
+    fn default() -> Self {
+        GithubBuilder {
+            host: DEFAULT_HOST.into(),
+            agent: concat!("hubcaps/", env!("CARGO_PKG_VERSION")).into(),
+            credentials: None,
+            connect_timeout: None,
+            timeout: None,
+            keep_alive: true,
+            keep_alive_timeout: None,
+            max_idle_per_host: None,
+            root_certificates: Vec::new(),
+            retry_policy: RetryPolicy::Never,
+            transient_retry: None,
+            middleware: Vec::new(),
+            metrics: None,
+            logging: None,
+            budget: None,
+            limits: ResponseLimits::default(),
+            #[cfg(feature = "httpcache")]
+            http_cache: HttpCache::noop(),
+            #[cfg(feature = "proxy")]
+            proxy: None,
+        }
+    }
+}
+
+impl GithubBuilder {
+    #[doc(hidden)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// the api host to send requests to. defaults to https://api.github.com
+    pub fn host<H>(&mut self, host: H) -> &mut Self
+    where
+        H: Into<String>,
+    {
+        self.host = host.into();
+        self
+    }
+
+    /// the user agent sent with each request. defaults to hubcaps/{version}
+    pub fn agent<A>(&mut self, agent: A) -> &mut Self
+    where
+        A: Into<String>,
+    {
+        self.agent = agent.into();
+        self
+    }
+
+    pub fn credentials<C>(&mut self, credentials: C) -> &mut Self
+    where
+        C: Into<Option<Credentials>>,
+    {
+        self.credentials = credentials.into();
+        self
+    }
+
+    /// how requests rejected for exhausting the rate limit are retried.
+    /// defaults to `RetryPolicy::Never`
+    pub fn retry_policy(&mut self, retry_policy: RetryPolicy) -> &mut Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// retry `GET` and `HEAD` requests which fail for transient reasons,
+    /// like a reset connection or a `5xx` response. defaults to not retrying
+    pub fn transient_retry(&mut self, transient_retry: TransientRetry) -> &mut Self {
+        self.transient_retry = Some(transient_retry);
+        self
+    }
+
+    /// register middleware to be invoked around every request the client
+    /// sends. middleware runs in the order it was added
+    pub fn middleware<M>(&mut self, middleware: M) -> &mut Self
+    where
+        M: Middleware + 'static,
+    {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// notify `metrics` as every request the client sends completes
+    pub fn metrics<M>(&mut self, metrics: M) -> &mut Self
+    where
+        M: Metrics + 'static,
+    {
+        self.metrics = Some(Arc::new(metrics));
+        self
+    }
+
+    /// log every request the client sends, with credentials redacted.
+    /// defaults to not logging requests
+    pub fn request_logging(&mut self, logging: RequestLogging) -> &mut Self {
+        self.logging = Some(logging);
+        self
+    }
+
+    /// partition the rate limit quota of each resource the client uses.
+    /// defaults to no partitions
+    pub fn rate_budget(&mut self, budget: RateBudget) -> &mut Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// bound the size and nesting of the responses the client accepts.
+    /// defaults to unbounded responses
+    pub fn response_limits(&mut self, limits: ResponseLimits) -> &mut Self {
+        self.limits = limits;
+        self
+    }
+
+    /// the cache used to make conditional requests. defaults to no cache
+    #[cfg(feature = "httpcache")]
+    pub fn http_cache(&mut self, http_cache: BoxedHttpCache) -> &mut Self {
+        self.http_cache = http_cache;
+        self
+    }
+
+    /// tunnel requests through the HTTP(S) proxy at `proxy`. only applies to
+    /// clients created with `build_proxied`
+    #[cfg(feature = "proxy")]
+    pub fn proxy(&mut self, proxy: Uri) -> &mut Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// the maximum time to wait for a tcp connection to be established
+    pub fn connect_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// the maximum time to wait for a request to complete, from sending it
+    /// to reading the last byte of its response. requests which take longer
+    /// fail with `ErrorKind::Timeout`. streams, downloads and uploads only
+    /// bound the wait for the head of their response, once any upload has
+    /// been sent
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// whether connections are kept open and reused between requests.
+    /// defaults to true
+    pub fn keep_alive(&mut self, keep_alive: bool) -> &mut Self {
+        self.keep_alive = keep_alive;
+        self
+    }
+
+    /// how long an idle connection is kept open before it is closed
+    pub fn keep_alive_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.keep_alive_timeout = Some(timeout);
+        self
+    }
+
+    /// the maximum number of idle connections kept open to the api host
+    pub fn max_idle_per_host(&mut self, max: usize) -> &mut Self {
+        self.max_idle_per_host = Some(max);
+        self
+    }
+
+    /// trust an additional PEM encoded root certificate, such as the private
+    /// certificate authority of a Github Enterprise Server instance
+    pub fn add_root_certificate<P>(&mut self, pem: P) -> &mut Self
+    where
+        P: Into<Vec<u8>>,
+    {
+        self.root_certificates.push(pem.into());
+        self
+    }
+
+    pub fn build(&self) -> Result<Github<HttpsConnector<HttpConnector>>> {
+        let connector = tls::connector(self.http_connector(), &self.root_certificates)?;
+        Ok(self.github(connector))
+    }
+
+    /// build a client which tunnels requests through the proxy set with
+    /// `proxy`, or else the one named by the `HTTPS_PROXY` (or `https_proxy`)
+    /// environment variable. when neither is set requests connect directly
+    #[cfg(feature = "proxy")]
+    pub fn build_proxied(&self) -> Result<Github<ProxyConnector<HttpsConnector<HttpConnector>>>> {
+        let connector = tls::connector(self.http_connector(), &self.root_certificates)?;
+        let proxy = match self.proxy {
+            Some(ref proxy) => Some(proxy.clone()),
+            None => proxy_from_env()?,
+        };
+        let connector = match proxy {
+            Some(proxy) => {
+                ProxyConnector::from_proxy(connector, Proxy::new(Intercept::All, proxy))?
+            }
+            None => ProxyConnector::new(connector)?,
+        };
+        Ok(self.github(connector))
+    }
+
+    fn http_connector(&self) -> HttpConnector {
+        let mut http = HttpConnector::new(4);
+        http.enforce_http(false);
+        http.set_connect_timeout(self.connect_timeout);
+        http
+    }
+
+    fn github<C>(&self, connector: C) -> Github<C>
+    where
+        C: Clone + Connect + 'static,
+    {
+        let mut client = Client::builder();
+        client.keep_alive(self.keep_alive);
+        if let Some(timeout) = self.keep_alive_timeout {
+            client.keep_alive_timeout(timeout);
+        }
+        if let Some(max) = self.max_idle_per_host {
+            client.max_idle_per_host(max);
+        }
+
+        Github {
+            host: self.host.clone(),
+            agent: self.agent.clone(),
+            client: client.build(connector),
+            credentials: self.credentials.clone(),
+            #[cfg(feature = "httpcache")]
+            http_cache: self.http_cache.clone(),
+            quota: Arc::new(Mutex::new(None)),
+            timeout: self.timeout,
+            retry_policy: self.retry_policy,
+            transient_retry: self.transient_retry,
+            retries_spent: Arc::new(Mutex::new(0)),
+            middleware: self.middleware.clone(),
+            metrics: self.metrics.clone(),
+            logging: self.logging,
+            budget: self.budget.clone(),
+            limits: self.limits,
+        }
+    }
+}
+
+/// the proxy uri named by the `HTTPS_PROXY` or `https_proxy` environment variable, if any
+#[cfg(feature = "proxy")]
+fn proxy_from_env() -> Result<Option<Uri>> {
+    match ::std::env::var("HTTPS_PROXY").or_else(|_| ::std::env::var("https_proxy")) {
+        Ok(ref value) if !value.trim().is_empty() => Ok(Some(value.trim().parse()?)),
+        _ => Ok(None),
+    }
+}
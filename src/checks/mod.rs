@@ -3,11 +3,24 @@
 
 extern crate serde_json;
 
-use futures::IntoFuture;
+use futures::{future, stream, Future as StdFuture, IntoFuture, Stream as StdStream};
 use hyper::client::connect::Connect;
 
 use self::super::{AuthenticationConstraint, Future, Github, MediaType};
 
+/// the most annotations Github accepts in a single request
+pub const MAX_ANNOTATIONS: usize = 50;
+
+/// splits annotations into batches Github accepts
+fn batches(annotations: Vec<Annotation>) -> Vec<Vec<Annotation>> {
+    let mut annotations = annotations.into_iter().peekable();
+    let mut batches = Vec::new();
+    while annotations.peek().is_some() {
+        batches.push(annotations.by_ref().take(MAX_ANNOTATIONS).collect());
+    }
+    batches
+}
+
 pub struct CheckRuns<C>
 where
     C: Clone + Connect + 'static,
@@ -56,16 +69,101 @@ where
         check_run_options: &CheckRunUpdateOptions,
     ) -> Future<CheckRun> {
         match serde_json::to_string(check_run_options) {
-            Ok(data) => self.github.post_media::<CheckRun>(
+            Ok(data) => self.github.patch_media::<CheckRun>(
                 &self.path(&format!("/{}", check_run_id)),
                 data.into_bytes(),
                 MediaType::Preview("antiope"),
-                AuthenticationConstraint::Unconstrained,
             ),
             Err(e) => Box::new(Err(e.into()).into_future()),
         }
     }
 
+    /// create a check run with any number of annotations. the annotations of
+    /// the options' output and `annotations` are sent in batches of
+    /// `MAX_ANNOTATIONS`: the first with the check run, and the rest in
+    /// subsequent updates of it
+    pub fn create_annotated(
+        &self,
+        mut check_run_options: CheckRunOptions,
+        annotations: Vec<Annotation>,
+    ) -> Future<CheckRun> {
+        let (title, summary, rest) = match check_run_options.output {
+            Some(ref mut output) => {
+                let mut all = output.annotations.take().unwrap_or_default();
+                all.extend(annotations);
+                let mut rest = batches(all);
+                if !rest.is_empty() {
+                    output.annotations = Some(rest.remove(0));
+                }
+                (output.title.clone(), output.summary.clone(), rest)
+            }
+            None if annotations.is_empty() => return self.create(&check_run_options),
+            None => {
+                return Box::new(future::err(
+                    "check run annotations require an output".into(),
+                ))
+            }
+        };
+        let checks = CheckRuns::new(self.github.clone(), self.owner.as_str(), self.repo.as_str());
+        Box::new(self.create(&check_run_options).and_then(move |run| {
+            checks
+                .annotate_batches(run.id, title, summary, rest)
+                .map(|_| run)
+        }))
+    }
+
+    /// add any number of annotations to a check run, in batches of
+    /// `MAX_ANNOTATIONS`. each update replaces the output's title and summary
+    pub fn annotate<T, S>(
+        &self,
+        check_run_id: u64,
+        title: T,
+        summary: S,
+        annotations: Vec<Annotation>,
+    ) -> Future<()>
+    where
+        T: Into<String>,
+        S: Into<String>,
+    {
+        self.annotate_batches(
+            check_run_id,
+            title.into(),
+            summary.into(),
+            batches(annotations),
+        )
+    }
+
+    fn annotate_batches(
+        &self,
+        check_run_id: u64,
+        title: String,
+        summary: String,
+        batches: Vec<Vec<Annotation>>,
+    ) -> Future<()> {
+        let checks = CheckRuns::new(self.github.clone(), self.owner.as_str(), self.repo.as_str());
+        let id = check_run_id.to_string();
+        Box::new(stream::iter_ok(batches).for_each(move |annotations| {
+            let options = CheckRunUpdateOptions {
+                name: None,
+                details_url: None,
+                external_id: None,
+                status: None,
+                started_at: None,
+                conclusion: None,
+                completed_at: None,
+                output: Some(Output {
+                    title: title.clone(),
+                    summary: summary.clone(),
+                    text: None,
+                    annotations: Some(annotations),
+                    images: None,
+                }),
+                actions: None,
+            };
+            checks.update(&id, &options).map(|_| ())
+        }))
+    }
+
     pub fn list_for_suite(&self, suite_id: &str) -> Future<Vec<CheckRun>> {
         // !!! does this actually work?
         // https://developer.github.com/v3/checks/runs/#list-check-runs-in-a-check-suite
@@ -74,6 +172,18 @@ where
             MediaType::Preview("antiope"),
         )
     }
+
+    /// lists the check runs for a git sha, branch or tag. only the first
+    /// 100 check runs are listed
+    pub fn list_for_ref(&self, reference: &str) -> Future<CheckRunList> {
+        self.github.get_media::<CheckRunList>(
+            &format!(
+                "/repos/{}/{}/commits/{}/check-runs?per_page=100",
+                self.owner, self.repo, reference
+            ),
+            MediaType::Preview("antiope"),
+        )
+    }
 }
 
 // representations
@@ -95,6 +205,8 @@ pub enum Conclusion {
     Cancelled,
     TimedOut,
     ActionRequired,
+    Skipped,
+    Stale,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
@@ -169,7 +281,6 @@ pub struct CheckRunOptions {
     pub actions: Option<Vec<Action>>,
 }
 
-
 #[derive(Debug, Serialize, PartialEq)]
 pub struct CheckRunUpdateOptions {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -194,7 +305,7 @@ pub struct CheckRunUpdateOptions {
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct CheckRun {
-    pub id: i32,
+    pub id: u64,
     pub name: String,
     pub head_sha: String,
     pub url: String,
@@ -226,7 +337,43 @@ pub struct CheckRun {
     pub actions: Option<Vec<Action>>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct CheckRunList {
+    pub total_count: u64,
+    pub check_runs: Vec<CheckRun>,
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct CheckSuite {
-    pub id: u32,
+    pub id: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn annotation(line: u32) -> Annotation {
+        Annotation {
+            path: "src/lib.rs".into(),
+            start_line: line,
+            end_line: line,
+            start_column: None,
+            end_column: None,
+            annotation_level: AnnotationLevel::Warning,
+            message: "unused variable".into(),
+            title: "unused".into(),
+            raw_details: String::new(),
+        }
+    }
+
+    #[test]
+    fn annotation_batches() {
+        assert!(batches(Vec::new()).is_empty());
+        let batched = batches((1..=120).map(annotation).collect());
+        assert_eq!(
+            batched.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![50, 50, 20]
+        );
+        assert_eq!(batched[2][0].start_line, 101);
+    }
 }
@@ -0,0 +1,740 @@
+//! Code scanning interface
+//!
+//! See the [github docs](https://docs.github.com/en/rest/code-scanning) for more information
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Write;
+
+use base64;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use futures::{future, stream, Async, Poll, Stream as StdStream};
+use hyper::client::connect::Connect;
+use hyper::Chunk;
+use serde_json;
+use url::form_urlencoded;
+
+use users::User;
+use {unfold, Error, Future, Github, Stream};
+
+fn identity<T>(x: T) -> T {
+    x
+}
+
+/// The state of a code scanning alert
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertState {
+    Open,
+    Dismissed,
+    Fixed,
+    Closed,
+}
+
+impl fmt::Display for AlertState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AlertState::Open => "open",
+            AlertState::Dismissed => "dismissed",
+            AlertState::Fixed => "fixed",
+            AlertState::Closed => "closed",
+        }
+        .fmt(f)
+    }
+}
+
+/// The reason a code scanning alert was dismissed
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub enum DismissedReason {
+    #[serde(rename = "false positive")]
+    FalsePositive,
+    #[serde(rename = "won't fix")]
+    WontFix,
+    #[serde(rename = "used in tests")]
+    UsedInTests,
+}
+
+/// Provides access to code scanning operations for a repository
+pub struct CodeScanning<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    owner: String,
+    repo: String,
+}
+
+impl<C: Clone + Connect + 'static> CodeScanning<C> {
+    #[doc(hidden)]
+    pub fn new<O, R>(github: Github<C>, owner: O, repo: R) -> Self
+    where
+        O: Into<String>,
+        R: Into<String>,
+    {
+        CodeScanning {
+            github,
+            owner: owner.into(),
+            repo: repo.into(),
+        }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!("/repos/{}/{}/code-scanning{}", self.owner, self.repo, more)
+    }
+
+    /// list code scanning alerts
+    pub fn list(&self, options: &AlertListOptions) -> Future<Vec<Alert>> {
+        let mut uri = vec![self.path("/alerts")];
+        if let Some(query) = options.serialize() {
+            uri.push(query);
+        }
+        self.github.get(&uri.join("?"))
+    }
+
+    /// provides a stream over all pages of code scanning alerts
+    pub fn iter(&self, options: &AlertListOptions) -> Stream<Alert> {
+        let mut uri = vec![self.path("/alerts")];
+        if let Some(query) = options.serialize() {
+            uri.push(query);
+        }
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&uri.join("?")),
+            identity,
+        )
+    }
+
+    /// get a code scanning alert by number
+    pub fn get(&self, number: u64) -> Future<Alert> {
+        self.github.get(&self.path(&format!("/alerts/{}", number)))
+    }
+
+    /// update the state of a code scanning alert, typically to dismiss it
+    ///
+    /// ```no_run
+    /// # use hubcaps::code_scanning::{AlertState, AlertUpdateOptions, DismissedReason};
+    /// # let github = hubcaps::Github::new("agent", None);
+    /// github.repo("owner", "repo").code_scanning().update(
+    ///     42,
+    ///     &AlertUpdateOptions::builder(AlertState::Dismissed)
+    ///         .dismissed_reason(DismissedReason::UsedInTests)
+    ///         .build(),
+    /// );
+    /// ```
+    pub fn update(&self, number: u64, options: &AlertUpdateOptions) -> Future<Alert> {
+        self.github
+            .patch(&self.path(&format!("/alerts/{}", number)), json!(options))
+    }
+
+    /// list the instances of a code scanning alert across branches
+    pub fn instances(&self, number: u64) -> Future<Vec<AlertInstance>> {
+        self.github
+            .get(&self.path(&format!("/alerts/{}/instances", number)))
+    }
+
+    /// list code scanning analyses
+    pub fn analyses(&self, options: &AnalysisListOptions) -> Future<Vec<Analysis>> {
+        let mut uri = vec![self.path("/analyses")];
+        if let Some(query) = options.serialize() {
+            uri.push(query);
+        }
+        self.github.get(&uri.join("?"))
+    }
+
+    /// get a code scanning analysis by id
+    pub fn analysis(&self, id: u64) -> Future<Analysis> {
+        self.github.get(&self.path(&format!("/analyses/{}", id)))
+    }
+
+    /// delete a code scanning analysis by id. Deleting the last analysis
+    /// of a set requires `confirm_delete`
+    pub fn delete_analysis(&self, id: u64, confirm_delete: bool) -> Future<()> {
+        let path = self.path(&format!("/analyses/{}", id));
+        if confirm_delete {
+            self.github.delete(&format!("{}?confirm_delete=true", path))
+        } else {
+            self.github.delete(&path)
+        }
+    }
+
+    /// upload a SARIF file with the results of a code scanning analysis.
+    /// The file is gzip compressed and base64 encoded before uploading
+    pub fn upload_sarif(&self, options: &SarifUploadOptions) -> Future<SarifUpload> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        let compressed = match encoder
+            .write_all(&options.sarif)
+            .and_then(|_| encoder.finish())
+        {
+            Ok(compressed) => compressed,
+            Err(err) => return Box::new(future::err(err.into())),
+        };
+        let request = SarifUploadRequest::new(options, base64::encode(&compressed));
+        self.github.post(&self.path("/sarifs"), json!(request))
+    }
+
+    /// upload a SARIF file with the results of a code scanning analysis as
+    /// its contents are produced by `sarif`, compressing and encoding them
+    /// as they arrive rather than holding the whole file in memory. the
+    /// `sarif` of the options is ignored. streams can't be replayed, so
+    /// these uploads are never retried
+    pub fn upload_sarif_stream<S>(
+        &self,
+        options: &SarifUploadOptions,
+        sarif: S,
+    ) -> Future<SarifUpload>
+    where
+        S: StdStream<Item = Chunk, Error = Error> + Send + 'static,
+    {
+        // the encoded file is spliced into the request's json in place of an
+        // empty `sarif`, which is serialized last
+        let request = match serde_json::to_string(&SarifUploadRequest::new(options, String::new()))
+        {
+            Ok(request) => request,
+            Err(err) => return Box::new(future::err(err.into())),
+        };
+        let (head, tail) = request.split_at(request.len() - 2);
+        let body = stream::once(Ok(Chunk::from(head.to_owned())))
+            .chain(Encoded::new(sarif))
+            .chain(stream::once(Ok(Chunk::from(tail.to_owned()))));
+        self.github.post_json_stream(&self.path("/sarifs"), body)
+    }
+
+    /// get the processing status of an uploaded SARIF file
+    pub fn sarif(&self, id: &str) -> Future<SarifStatus> {
+        self.github.get(&self.path(&format!("/sarifs/{}", id)))
+    }
+}
+
+/// Provides access to code scanning operations for an organization
+pub struct OrgCodeScanning<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    org: String,
+}
+
+impl<C: Clone + Connect + 'static> OrgCodeScanning<C> {
+    #[doc(hidden)]
+    pub fn new<O>(github: Github<C>, org: O) -> Self
+    where
+        O: Into<String>,
+    {
+        OrgCodeScanning {
+            github,
+            org: org.into(),
+        }
+    }
+
+    fn path(&self, options: &AlertListOptions) -> String {
+        let mut uri = vec![format!("/orgs/{}/code-scanning/alerts", self.org)];
+        if let Some(query) = options.serialize() {
+            uri.push(query);
+        }
+        uri.join("?")
+    }
+
+    /// list code scanning alerts across an organization's repositories
+    pub fn list(&self, options: &AlertListOptions) -> Future<Vec<Alert>> {
+        self.github.get(&self.path(options))
+    }
+
+    /// provides a stream over all pages of an organization's code scanning alerts
+    pub fn iter(&self, options: &AlertListOptions) -> Stream<Alert> {
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&self.path(options)),
+            identity,
+        )
+    }
+}
+
+// representations
+
+#[derive(Debug, Deserialize)]
+pub struct Alert {
+    pub number: u64,
+    pub url: String,
+    pub html_url: String,
+    pub instances_url: String,
+    pub state: String,
+    pub created_at: String,
+    pub updated_at: Option<String>,
+    pub fixed_at: Option<String>,
+    pub dismissed_by: Option<User>,
+    pub dismissed_at: Option<String>,
+    pub dismissed_reason: Option<String>,
+    pub dismissed_comment: Option<String>,
+    pub rule: Rule,
+    pub tool: Tool,
+    pub most_recent_instance: AlertInstance,
+    /// only included when listing an organization's alerts
+    pub repository: Option<AlertRepository>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AlertRepository {
+    pub id: u64,
+    pub name: String,
+    pub full_name: String,
+    pub html_url: String,
+    pub private: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Rule {
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub severity: Option<String>,
+    pub security_severity_level: Option<String>,
+    pub description: Option<String>,
+    pub tags: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Tool {
+    pub name: String,
+    pub guid: Option<String>,
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AlertInstance {
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    pub analysis_key: String,
+    pub environment: Option<String>,
+    pub category: Option<String>,
+    pub state: String,
+    pub commit_sha: Option<String>,
+    pub message: Option<Message>,
+    pub location: Option<Location>,
+    pub classifications: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Message {
+    pub text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Location {
+    pub path: String,
+    pub start_line: Option<u32>,
+    pub end_line: Option<u32>,
+    pub start_column: Option<u32>,
+    pub end_column: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Analysis {
+    pub id: u64,
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    pub commit_sha: String,
+    pub analysis_key: String,
+    pub environment: String,
+    pub category: Option<String>,
+    pub error: String,
+    pub warning: String,
+    pub created_at: String,
+    pub results_count: u64,
+    pub rules_count: u64,
+    pub url: String,
+    pub sarif_id: String,
+    pub tool: Tool,
+    pub deletable: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SarifUpload {
+    pub id: String,
+    pub url: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SarifStatus {
+    /// one of `pending`, `complete` or `failed`
+    pub processing_status: String,
+    pub analyses_url: Option<String>,
+    pub errors: Option<Vec<String>>,
+}
+
+#[derive(Default)]
+pub struct AlertListOptions {
+    params: HashMap<&'static str, String>,
+}
+
+impl AlertListOptions {
+    pub fn builder() -> AlertListOptionsBuilder {
+        AlertListOptionsBuilder::default()
+    }
+
+    /// serialize options as a string. returns None if no options are defined
+    pub fn serialize(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded)
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct AlertListOptionsBuilder(AlertListOptions);
+
+impl AlertListOptionsBuilder {
+    pub fn state(&mut self, state: AlertState) -> &mut Self {
+        self.0.params.insert("state", state.to_string());
+        self
+    }
+
+    pub fn tool_name<T>(&mut self, name: T) -> &mut Self
+    where
+        T: Into<String>,
+    {
+        self.0.params.insert("tool_name", name.into());
+        self
+    }
+
+    /// the git reference alerts are listed for, like `refs/heads/main`.
+    /// only applicable to repository alerts
+    pub fn git_ref<R>(&mut self, git_ref: R) -> &mut Self
+    where
+        R: Into<String>,
+    {
+        self.0.params.insert("ref", git_ref.into());
+        self
+    }
+
+    /// one of `critical`, `high`, `medium`, `low`, `warning`, `note` or `error`
+    pub fn severity<S>(&mut self, severity: S) -> &mut Self
+    where
+        S: Into<String>,
+    {
+        self.0.params.insert("severity", severity.into());
+        self
+    }
+
+    pub fn per_page(&mut self, n: usize) -> &mut Self {
+        self.0.params.insert("per_page", n.to_string());
+        self
+    }
+
+    pub fn build(&self) -> AlertListOptions {
+        AlertListOptions {
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct AnalysisListOptions {
+    params: HashMap<&'static str, String>,
+}
+
+impl AnalysisListOptions {
+    pub fn builder() -> AnalysisListOptionsBuilder {
+        AnalysisListOptionsBuilder::default()
+    }
+
+    /// serialize options as a string. returns None if no options are defined
+    pub fn serialize(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded)
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct AnalysisListOptionsBuilder(AnalysisListOptions);
+
+impl AnalysisListOptionsBuilder {
+    pub fn tool_name<T>(&mut self, name: T) -> &mut Self
+    where
+        T: Into<String>,
+    {
+        self.0.params.insert("tool_name", name.into());
+        self
+    }
+
+    pub fn git_ref<R>(&mut self, git_ref: R) -> &mut Self
+    where
+        R: Into<String>,
+    {
+        self.0.params.insert("ref", git_ref.into());
+        self
+    }
+
+    pub fn sarif_id<S>(&mut self, id: S) -> &mut Self
+    where
+        S: Into<String>,
+    {
+        self.0.params.insert("sarif_id", id.into());
+        self
+    }
+
+    pub fn per_page(&mut self, n: usize) -> &mut Self {
+        self.0.params.insert("per_page", n.to_string());
+        self
+    }
+
+    pub fn build(&self) -> AnalysisListOptions {
+        AnalysisListOptions {
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+/// options for updating a code scanning alert
+#[derive(Debug, Serialize)]
+pub struct AlertUpdateOptions {
+    pub state: AlertState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dismissed_reason: Option<DismissedReason>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dismissed_comment: Option<String>,
+}
+
+impl AlertUpdateOptions {
+    /// alerts may only be updated to the `Open` or `Dismissed` states
+    pub fn builder(state: AlertState) -> AlertUpdateOptionsBuilder {
+        AlertUpdateOptionsBuilder(AlertUpdateOptions {
+            state,
+            dismissed_reason: None,
+            dismissed_comment: None,
+        })
+    }
+}
+
+pub struct AlertUpdateOptionsBuilder(AlertUpdateOptions);
+
+impl AlertUpdateOptionsBuilder {
+    /// required when dismissing an alert
+    pub fn dismissed_reason(&mut self, reason: DismissedReason) -> &mut Self {
+        self.0.dismissed_reason = Some(reason);
+        self
+    }
+
+    pub fn dismissed_comment<C>(&mut self, comment: C) -> &mut Self
+    where
+        C: Into<String>,
+    {
+        self.0.dismissed_comment = Some(comment.into());
+        self
+    }
+
+    pub fn build(&self) -> AlertUpdateOptions {
+        AlertUpdateOptions {
+            state: self.0.state,
+            dismissed_reason: self.0.dismissed_reason,
+            dismissed_comment: self.0.dismissed_comment.clone(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct SarifUploadRequest<'a> {
+    commit_sha: &'a str,
+    #[serde(rename = "ref")]
+    git_ref: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    checkout_uri: Option<&'a String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    started_at: Option<&'a String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_name: Option<&'a String>,
+    /// serialized last, so streamed uploads can splice the file in
+    sarif: String,
+}
+
+impl<'a> SarifUploadRequest<'a> {
+    fn new(options: &'a SarifUploadOptions, sarif: String) -> Self {
+        SarifUploadRequest {
+            commit_sha: &options.commit_sha,
+            git_ref: &options.git_ref,
+            checkout_uri: options.checkout_uri.as_ref(),
+            started_at: options.started_at.as_ref(),
+            tool_name: options.tool_name.as_ref(),
+            sarif,
+        }
+    }
+}
+
+/// gzip compresses and base64 encodes a stream of content as it's produced
+struct Encoded<S> {
+    content: S,
+    encoder: Option<GzEncoder<Vec<u8>>>,
+    /// compressed bytes yet to be encoded, as base64 encodes three at a time
+    pending: Vec<u8>,
+}
+
+impl<S> Encoded<S> {
+    fn new(content: S) -> Self {
+        Encoded {
+            content,
+            encoder: Some(GzEncoder::new(Vec::new(), Compression::default())),
+            pending: Vec::new(),
+        }
+    }
+}
+
+impl<S> StdStream for Encoded<S>
+where
+    S: StdStream<Item = Chunk, Error = Error>,
+{
+    type Item = Chunk;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<Chunk>, Error> {
+        loop {
+            let chunk = match self.encoder {
+                Some(_) => match self.content.poll()? {
+                    Async::Ready(chunk) => chunk,
+                    Async::NotReady => return Ok(Async::NotReady),
+                },
+                None => return Ok(Async::Ready(None)),
+            };
+            match chunk {
+                Some(chunk) => {
+                    let encoder = self.encoder.as_mut().unwrap();
+                    encoder.write_all(&chunk)?;
+                    self.pending.append(encoder.get_mut());
+                }
+                None => {
+                    let rest = self.encoder.take().unwrap().finish()?;
+                    self.pending.extend(rest);
+                    let encoded = base64::encode(&self.pending);
+                    self.pending.clear();
+                    return Ok(Async::Ready(Some(Chunk::from(encoded))));
+                }
+            }
+            let whole = self.pending.len() / 3 * 3;
+            if whole > 0 {
+                let encoded = base64::encode(&self.pending[..whole]);
+                self.pending.drain(..whole);
+                return Ok(Async::Ready(Some(Chunk::from(encoded))));
+            }
+        }
+    }
+}
+
+/// options for uploading a SARIF file
+#[derive(Debug, Default)]
+pub struct SarifUploadOptions {
+    pub commit_sha: String,
+    pub git_ref: String,
+    /// the uncompressed contents of the SARIF file
+    pub sarif: Vec<u8>,
+    pub checkout_uri: Option<String>,
+    pub started_at: Option<String>,
+    pub tool_name: Option<String>,
+}
+
+impl SarifUploadOptions {
+    /// `git_ref` is the full reference the analysis ran against, like `refs/heads/main`
+    pub fn builder<S, R, B>(commit_sha: S, git_ref: R, sarif: B) -> SarifUploadOptionsBuilder
+    where
+        S: Into<String>,
+        R: Into<String>,
+        B: Into<Vec<u8>>,
+    {
+        SarifUploadOptionsBuilder(SarifUploadOptions {
+            commit_sha: commit_sha.into(),
+            git_ref: git_ref.into(),
+            sarif: sarif.into(),
+            ..Default::default()
+        })
+    }
+}
+
+pub struct SarifUploadOptionsBuilder(SarifUploadOptions);
+
+impl SarifUploadOptionsBuilder {
+    /// the base uri of the checkout the analysis ran against. used to
+    /// convert absolute paths in the SARIF file to relative ones
+    pub fn checkout_uri<U>(&mut self, uri: U) -> &mut Self
+    where
+        U: Into<String>,
+    {
+        self.0.checkout_uri = Some(uri.into());
+        self
+    }
+
+    /// an ISO 8601 timestamp of when the analysis began
+    pub fn started_at<S>(&mut self, started_at: S) -> &mut Self
+    where
+        S: Into<String>,
+    {
+        self.0.started_at = Some(started_at.into());
+        self
+    }
+
+    pub fn tool_name<T>(&mut self, name: T) -> &mut Self
+    where
+        T: Into<String>,
+    {
+        self.0.tool_name = Some(name.into());
+        self
+    }
+
+    pub fn build(&self) -> SarifUploadOptions {
+        SarifUploadOptions {
+            commit_sha: self.0.commit_sha.clone(),
+            git_ref: self.0.git_ref.clone(),
+            sarif: self.0.sarif.clone(),
+            checkout_uri: self.0.checkout_uri.clone(),
+            started_at: self.0.started_at.clone(),
+            tool_name: self.0.tool_name.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alert_update_reqs() {
+        for (options, expect) in vec![
+            (
+                AlertUpdateOptions::builder(AlertState::Open).build(),
+                r#"{"state":"open"}"#,
+            ),
+            (
+                AlertUpdateOptions::builder(AlertState::Dismissed)
+                    .dismissed_reason(DismissedReason::WontFix)
+                    .build(),
+                r#"{"state":"dismissed","dismissed_reason":"won't fix"}"#,
+            ),
+        ] {
+            assert_eq!(serde_json::to_string(&options).unwrap(), expect)
+        }
+    }
+
+    #[test]
+    fn encoded() {
+        use flate2::read::GzDecoder;
+        use futures::Future as StdFuture;
+        use std::io::Read;
+
+        let sarif = br#"{"version":"2.1.0","runs":[]}"#.repeat(100);
+        let content = stream::iter_ok::<_, Error>(sarif.chunks(7).map(|c| Chunk::from(c.to_vec())));
+        let encoded = Encoded::new(content).concat2().wait().unwrap();
+        let compressed = base64::decode(&encoded[..]).unwrap();
+        let mut decoded = Vec::new();
+        GzDecoder::new(&compressed[..])
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, sarif);
+    }
+}
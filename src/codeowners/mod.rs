@@ -0,0 +1,288 @@
+//! Code owners interface
+//!
+//! Parses a repository's `CODEOWNERS` file and resolves the users and teams
+//! which own paths, so bots can request reviews from them. See
+//! `Repository::codeowners` and `PullRequest::request_code_owner_reviews`, and
+//! the [github docs](https://docs.github.com/en/repositories/managing-your-repositorys-settings-and-features/customizing-your-repository/about-code-owners)
+//! for more information.
+
+use std::fmt;
+
+use futures::{future, Future as StdFuture};
+use hyper::client::connect::Connect;
+use hyper::StatusCode;
+
+use content::File;
+use {Error, ErrorKind, Future, Github};
+
+/// the locations Github looks for a `CODEOWNERS` file in, in order
+pub const LOCATIONS: &[&str] = &[".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+/// fetches the first `CODEOWNERS` file of a repository found in `LOCATIONS`,
+/// at `reference` or the default branch. repositories without one have no
+/// code owners
+pub(crate) fn fetch<C>(
+    github: Github<C>,
+    owner: String,
+    repo: String,
+    reference: Option<String>,
+) -> Future<CodeOwners>
+where
+    C: Clone + Connect + 'static,
+{
+    let found: Future<Option<File>> = Box::new(future::ok(None));
+    let found = LOCATIONS
+        .iter()
+        .fold(found, |found, location| -> Future<Option<File>> {
+            let github = github.clone();
+            let mut uri = format!("/repos/{}/{}/contents/{}", owner, repo, location);
+            if let Some(ref reference) = reference {
+                uri.push_str(&format!("?ref={}", reference));
+            }
+            Box::new(found.and_then(move |found| -> Future<Option<File>> {
+                if found.is_some() {
+                    return Box::new(future::ok(found));
+                }
+                Box::new(github.get::<File>(&uri).map(Some).or_else(|err| match err {
+                    Error(
+                        ErrorKind::Fault {
+                            code: StatusCode::NOT_FOUND,
+                            ..
+                        },
+                        _,
+                    ) => Ok(None),
+                    otherwise => Err(otherwise),
+                }))
+            }))
+        });
+    Box::new(found.map(|file| match file {
+        Some(file) => CodeOwners::parse(&String::from_utf8_lossy(&file.content)),
+        None => CodeOwners::default(),
+    }))
+}
+
+/// An owner of paths in a repository
+#[derive(Clone, Debug, PartialEq)]
+pub enum Owner {
+    /// a user, by login
+    User(String),
+    /// a team, by its organization and slug
+    Team { org: String, slug: String },
+    /// a user, by the email of their Github account
+    Email(String),
+}
+
+impl Owner {
+    fn parse(owner: &str) -> Owner {
+        if owner.starts_with('@') {
+            let name = &owner[1..];
+            match name.find('/') {
+                Some(slash) => Owner::Team {
+                    org: name[..slash].to_owned(),
+                    slug: name[slash + 1..].to_owned(),
+                },
+                None => Owner::User(name.to_owned()),
+            }
+        } else {
+            Owner::Email(owner.to_owned())
+        }
+    }
+}
+
+impl fmt::Display for Owner {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Owner::User(ref login) => write!(f, "@{}", login),
+            Owner::Team { ref org, ref slug } => write!(f, "@{}/{}", org, slug),
+            Owner::Email(ref email) => email.fmt(f),
+        }
+    }
+}
+
+/// A rule of a `CODEOWNERS` file
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rule {
+    pub pattern: String,
+    /// no one owns paths matching rules without owners
+    pub owners: Vec<Owner>,
+}
+
+impl Rule {
+    /// whether the rule's pattern matches a path, relative to the
+    /// repository's root
+    pub fn matches(&self, path: &str) -> bool {
+        let path = path.trim_start_matches('/');
+        let directory = self.pattern.ends_with('/');
+        let pattern = self.pattern.trim_end_matches('/');
+        // patterns are relative to the root when they contain a slash,
+        // and otherwise match at any depth
+        let anchored = pattern.contains('/');
+        let pattern = pattern.trim_start_matches('/');
+        let pattern = if anchored {
+            pattern.to_owned()
+        } else {
+            format!("**/{}", pattern)
+        };
+        // patterns naming a directory match everything in it, except that
+        // trailing wildcards like `docs/*` only match the directory's files
+        let last = pattern.rsplit('/').next().unwrap_or_default();
+        let contents =
+            !last.contains('*') && glob(format!("{}/**", pattern).as_bytes(), path.as_bytes());
+        contents || (!directory && glob(pattern.as_bytes(), path.as_bytes()))
+    }
+}
+
+/// matches `path` against a gitignore style pattern, where `*` and `?`
+/// don't match slashes and `**` does
+pub(crate) fn glob(pattern: &[u8], path: &[u8]) -> bool {
+    if pattern.starts_with(b"**/") {
+        let rest = &pattern[3..];
+        glob(rest, path)
+            || path
+                .iter()
+                .enumerate()
+                .any(|(i, &c)| c == b'/' && glob(rest, &path[i + 1..]))
+    } else if pattern.starts_with(b"**") {
+        (0..=path.len()).any(|i| glob(&pattern[2..], &path[i..]))
+    } else if pattern.starts_with(b"*") {
+        for i in 0..=path.len() {
+            if glob(&pattern[1..], &path[i..]) {
+                return true;
+            }
+            if i < path.len() && path[i] == b'/' {
+                return false;
+            }
+        }
+        false
+    } else {
+        match (pattern.first(), path.first()) {
+            (None, None) => true,
+            (Some(&b'?'), Some(&c)) if c != b'/' => glob(&pattern[1..], &path[1..]),
+            (Some(&p), Some(&c)) if p == c => glob(&pattern[1..], &path[1..]),
+            _ => false,
+        }
+    }
+}
+
+/// The rules of a `CODEOWNERS` file
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CodeOwners {
+    pub rules: Vec<Rule>,
+}
+
+impl CodeOwners {
+    /// parse the contents of a `CODEOWNERS` file
+    pub fn parse(contents: &str) -> CodeOwners {
+        let rules = contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let mut fields = line.split_whitespace();
+                fields.next().map(|pattern| Rule {
+                    pattern: pattern.to_owned(),
+                    owners: fields
+                        .take_while(|field| !field.starts_with('#'))
+                        .map(Owner::parse)
+                        .collect(),
+                })
+            })
+            .collect();
+        CodeOwners { rules }
+    }
+
+    /// the owners of a path. the last rule matching the path takes
+    /// precedence
+    pub fn owners_of(&self, path: &str) -> &[Owner] {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.matches(path))
+            .map(|rule| rule.owners.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// the distinct owners of any of `paths`, like the files a pull
+    /// request changes
+    pub fn owners_for<P>(&self, paths: &[P]) -> Vec<Owner>
+    where
+        P: AsRef<str>,
+    {
+        let mut owners: Vec<Owner> = Vec::new();
+        for path in paths {
+            for owner in self.owners_of(path.as_ref()) {
+                if !owners.contains(owner) {
+                    owners.push(owner.clone());
+                }
+            }
+        }
+        owners
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CODEOWNERS: &str = r#"
+# default owners
+*       @global-owner1 @global-owner2
+*.js    @js-owner # javascript
+/build/logs/ @doctocat
+docs/*  docs@example.com
+apps/   @octocat
+/scripts/ @doctocat @octo-org/ops
+/apps/github
+"#;
+
+    #[test]
+    fn parse_owners() {
+        let owners = CodeOwners::parse(CODEOWNERS);
+        assert_eq!(owners.rules.len(), 7);
+        assert_eq!(
+            owners.rules[5].owners,
+            vec![
+                Owner::User("doctocat".into()),
+                Owner::Team {
+                    org: "octo-org".into(),
+                    slug: "ops".into()
+                }
+            ]
+        );
+        assert_eq!(owners.rules[1].owners, vec![Owner::User("js-owner".into())]);
+        assert!(owners.rules[6].owners.is_empty());
+    }
+
+    #[test]
+    fn owners_of_paths() {
+        let owners = CodeOwners::parse(CODEOWNERS);
+        let owner = |path| {
+            owners
+                .owners_of(path)
+                .iter()
+                .map(|o| o.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(owner("README.md"), vec!["@global-owner1", "@global-owner2"]);
+        assert_eq!(owner("src/app.js"), vec!["@js-owner"]);
+        assert_eq!(owner("build/logs/out.txt"), vec!["@doctocat"]);
+        assert_eq!(owner("docs/getting-started.md"), vec!["docs@example.com"]);
+        assert_eq!(
+            owner("docs/build-app/troubleshooting.md"),
+            vec!["@global-owner1", "@global-owner2"]
+        );
+        assert_eq!(owner("web/apps/index.html"), vec!["@octocat"]);
+        assert!(owner("apps/github/main.rb").is_empty());
+        assert_eq!(
+            owners.owners_for(&["scripts/deploy.sh", "src/app.js", "lib/util.js"]),
+            vec![
+                Owner::User("doctocat".into()),
+                Owner::Team {
+                    org: "octo-org".into(),
+                    slug: "ops".into()
+                },
+                Owner::User("js-owner".into()),
+            ]
+        );
+    }
+}
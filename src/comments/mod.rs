@@ -5,9 +5,10 @@ extern crate serde_json;
 
 use futures::future;
 use hyper::client::connect::Connect;
+use ids::IssueNumber;
 use std::collections::HashMap;
 use url::form_urlencoded;
-use users::User;
+use users::{AuthorAssociation, User};
 use {Future, Github};
 
 /// A structure for interfacing with a issue comments
@@ -18,21 +19,22 @@ where
     github: Github<C>,
     owner: String,
     repo: String,
-    number: u64,
+    number: IssueNumber,
 }
 
 impl<C: Clone + Connect + 'static> Comments<C> {
     #[doc(hidden)]
-    pub fn new<O, R>(github: Github<C>, owner: O, repo: R, number: u64) -> Self
+    pub fn new<O, R, N>(github: Github<C>, owner: O, repo: R, number: N) -> Self
     where
         O: Into<String>,
         R: Into<String>,
+        N: Into<IssueNumber>,
     {
         Comments {
             github,
             owner: owner.into(),
             repo: repo.into(),
-            number,
+            number: number.into(),
         }
     }
 
@@ -67,6 +69,7 @@ pub struct Comment {
     pub html_url: String,
     pub body: String,
     pub user: User,
+    pub author_association: AuthorAssociation,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -0,0 +1,261 @@
+//! Comparisons interface
+//!
+//! Compares two commits, branches or tags of a repository, or of repositories
+//! in the same fork network. Github lists at most 250 commits and 300 changed
+//! files in a comparison. Page through the commits of larger comparisons with
+//! `Compare::iter_commits`.
+//!
+//! See the [github docs](https://docs.github.com/en/rest/commits/commits#compare-two-commits)
+//! for more information.
+
+use std::collections::HashMap;
+
+use hyper::client::connect::Connect;
+use url::form_urlencoded;
+
+use pull_commits::{CommitDetails, CommitRef};
+use pulls::FileDiff;
+use users::User;
+use {unfold, Future, Github, Stream};
+
+/// the most changed files Github lists in a comparison
+pub const MAX_FILES: usize = 300;
+
+fn commits(comparison: Comparison) -> Vec<CompareCommit> {
+    comparison.commits
+}
+
+/// Provides access to the comparison of two commits
+pub struct Compare<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    owner: String,
+    repo: String,
+    basehead: String,
+}
+
+impl<C: Clone + Connect + 'static> Compare<C> {
+    #[doc(hidden)]
+    pub fn new<O, R, B, H>(github: Github<C>, owner: O, repo: R, base: B, head: H) -> Self
+    where
+        O: Into<String>,
+        R: Into<String>,
+        B: Into<String>,
+        H: Into<String>,
+    {
+        Compare {
+            github,
+            owner: owner.into(),
+            repo: repo.into(),
+            basehead: format!("{}...{}", base.into(), head.into()),
+        }
+    }
+
+    fn path(&self) -> String {
+        format!(
+            "/repos/{}/{}/compare/{}",
+            self.owner, self.repo, self.basehead
+        )
+    }
+
+    /// get the comparison, listing up to 250 of its commits and
+    /// `MAX_FILES` of its changed files
+    pub fn get(&self) -> Future<Comparison> {
+        self.github.get(&self.path())
+    }
+
+    /// get a page of the comparison's commits. changed files are only
+    /// listed on the first page
+    pub fn page(&self, options: &CompareOptions) -> Future<Comparison> {
+        let mut uri = vec![self.path()];
+        if let Some(query) = options.serialize() {
+            uri.push(query);
+        }
+        self.github.get(&uri.join("?"))
+    }
+
+    /// provides a stream over all of the comparison's commits, oldest first
+    pub fn iter_commits(&self) -> Stream<CompareCommit> {
+        unfold(
+            self.github.clone(),
+            self.github
+                .get_pages(&format!("{}?per_page=100", self.path())),
+            commits,
+        )
+    }
+}
+
+// representations
+
+string_enum! {
+    /// How the head of a comparison relates to its base
+    pub enum CompareStatus {
+        /// head has commits base doesn't
+        Ahead => "ahead",
+        /// base has commits head doesn't
+        Behind => "behind",
+        /// both have commits the other doesn't
+        Diverged => "diverged",
+        Identical => "identical",
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Comparison {
+    pub url: String,
+    pub html_url: String,
+    pub permalink_url: String,
+    pub diff_url: String,
+    pub patch_url: String,
+    pub base_commit: CompareCommit,
+    pub merge_base_commit: CompareCommit,
+    pub status: CompareStatus,
+    pub ahead_by: u64,
+    pub behind_by: u64,
+    pub total_commits: u64,
+    pub commits: Vec<CompareCommit>,
+    /// absent from pages of a comparison after the first
+    #[serde(default)]
+    pub files: Vec<FileDiff>,
+}
+
+impl Comparison {
+    /// whether Github may have omitted some of the changed files. Github
+    /// lists at most `MAX_FILES` of them, so a comparison listing exactly
+    /// that many can't be told apart from a truncated one. clone the
+    /// repository to diff them all
+    pub fn files_may_be_truncated(&self) -> bool {
+        self.files.len() >= MAX_FILES
+    }
+
+    /// whether Github omitted some of the commits. see `Compare::iter_commits`
+    pub fn commits_truncated(&self) -> bool {
+        (self.commits.len() as u64) < self.total_commits
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CompareCommit {
+    pub url: String,
+    pub sha: String,
+    pub html_url: String,
+    pub comments_url: String,
+    pub commit: CommitDetails,
+    /// `None` when the author's email isn't associated with a Github user
+    pub author: Option<User>,
+    /// `None` when the committer's email isn't associated with a Github user
+    pub committer: Option<User>,
+    pub parents: Vec<CommitRef>,
+}
+
+#[derive(Default)]
+pub struct CompareOptions {
+    params: HashMap<&'static str, String>,
+}
+
+impl CompareOptions {
+    pub fn builder() -> CompareOptionsBuilder {
+        CompareOptionsBuilder::default()
+    }
+
+    /// serialize options as a string. returns None if no options are defined
+    pub fn serialize(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded)
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct CompareOptionsBuilder(CompareOptions);
+
+impl CompareOptionsBuilder {
+    /// commits per page, up to 100
+    pub fn per_page(&mut self, n: usize) -> &mut Self {
+        self.0.params.insert("per_page", n.to_string());
+        self
+    }
+
+    pub fn page(&mut self, n: usize) -> &mut Self {
+        self.0.params.insert("page", n.to_string());
+        self
+    }
+
+    pub fn build(&self) -> CompareOptions {
+        CompareOptions {
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn truncation() {
+        let commit = r#"{
+          "url": "https://api.github.com/repos/octocat/Hello-World/commits/6dcb09b",
+          "sha": "6dcb09b",
+          "html_url": "https://github.com/octocat/Hello-World/commit/6dcb09b",
+          "comments_url": "https://api.github.com/repos/octocat/Hello-World/commits/6dcb09b/comments",
+          "commit": {
+            "url": "https://api.github.com/repos/octocat/Hello-World/git/commits/6dcb09b",
+            "author": {"name": "Monalisa Octocat", "email": "support@github.com", "date": "2011-04-14T16:00:49Z"},
+            "committer": null,
+            "message": "Fix all the bugs",
+            "tree": {"url": "https://api.github.com/repos/octocat/Hello-World/tree/6dcb09b", "sha": "6dcb09b"},
+            "comment_count": 0
+          },
+          "author": null,
+          "committer": null,
+          "parents": []
+        }"#;
+        let mut comparison: Comparison = serde_json::from_str(&format!(
+            r#"{{
+              "url": "https://api.github.com/repos/octocat/Hello-World/compare/master...topic",
+              "html_url": "https://github.com/octocat/Hello-World/compare/master...topic",
+              "permalink_url": "https://github.com/octocat/Hello-World/compare/octocat:bbcd538...octocat:0328041",
+              "diff_url": "https://github.com/octocat/Hello-World/compare/master...topic.diff",
+              "patch_url": "https://github.com/octocat/Hello-World/compare/master...topic.patch",
+              "base_commit": {0},
+              "merge_base_commit": {0},
+              "status": "ahead",
+              "ahead_by": 2,
+              "behind_by": 0,
+              "total_commits": 2,
+              "commits": [{0}]
+            }}"#,
+            commit
+        ))
+        .unwrap();
+        assert_eq!(comparison.status, CompareStatus::Ahead);
+        assert!(comparison.commits_truncated());
+        assert!(!comparison.files_may_be_truncated());
+
+        let file = |n: usize| FileDiff {
+            sha: None,
+            filename: format!("{}.rs", n),
+            status: "added".into(),
+            additions: 1,
+            deletions: 0,
+            changes: 1,
+            blob_url: String::new(),
+            raw_url: String::new(),
+            contents_url: String::new(),
+            patch: None,
+        };
+        comparison.files = (1..MAX_FILES).map(file).collect();
+        assert!(!comparison.files_may_be_truncated());
+        comparison.files.push(file(MAX_FILES));
+        assert!(comparison.files_may_be_truncated());
+    }
+}
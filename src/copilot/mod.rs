@@ -0,0 +1,232 @@
+//! Copilot interface
+//!
+//! See the [github docs](https://docs.github.com/en/rest/copilot/copilot-user-management) for more information
+
+use std::collections::{BTreeMap, HashMap};
+
+use futures::{future, Future as StdFuture};
+use hyper::client::connect::Connect;
+use serde_json;
+use url::form_urlencoded;
+
+use {Future, Github};
+
+/// Provides access to the Copilot seats of an organization
+pub struct Copilot<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    org: String,
+}
+
+impl<C: Clone + Connect + 'static> Copilot<C> {
+    #[doc(hidden)]
+    pub fn new<O>(github: Github<C>, org: O) -> Self
+    where
+        O: Into<String>,
+    {
+        Copilot {
+            github,
+            org: org.into(),
+        }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!("/orgs/{}/copilot/billing{}", self.org, more)
+    }
+
+    /// get the organization's Copilot subscription settings and seat breakdown
+    pub fn billing(&self) -> Future<CopilotBilling> {
+        self.github.get(&self.path(""))
+    }
+
+    /// list the organization's assigned Copilot seats
+    pub fn seats(&self, options: &SeatListOptions) -> Future<SeatList> {
+        let mut uri = vec![self.path("/seats")];
+        if let Some(query) = options.serialize() {
+            uri.push(query);
+        }
+        self.github.get(&uri.join("?"))
+    }
+
+    /// assign Copilot seats to users. returns the number of seats created
+    pub fn add_users(&self, usernames: Vec<&str>) -> Future<u64> {
+        let mut payload = BTreeMap::new();
+        payload.insert("selected_usernames", usernames);
+        Box::new(
+            self.github
+                .post::<SeatsCreated>(&self.path("/selected_users"), json!(payload))
+                .map(|created| created.seats_created),
+        )
+    }
+
+    /// cancel the Copilot seats of users at the end of the billing cycle.
+    /// returns the number of seats cancelled
+    pub fn remove_users(&self, usernames: Vec<&str>) -> Future<u64> {
+        let mut payload = BTreeMap::new();
+        payload.insert("selected_usernames", usernames);
+        Box::new(
+            self.github
+                .delete_message::<SeatsCancelled>(&self.path("/selected_users"), json!(payload))
+                .map(|cancelled| cancelled.seats_cancelled),
+        )
+    }
+
+    /// assign Copilot seats to every member of teams. returns the number of seats created
+    pub fn add_teams(&self, team_slugs: Vec<&str>) -> Future<u64> {
+        let mut payload = BTreeMap::new();
+        payload.insert("selected_teams", team_slugs);
+        Box::new(
+            self.github
+                .post::<SeatsCreated>(&self.path("/selected_teams"), json!(payload))
+                .map(|created| created.seats_created),
+        )
+    }
+
+    /// cancel the Copilot seats assigned through teams at the end of the
+    /// billing cycle. returns the number of seats cancelled
+    pub fn remove_teams(&self, team_slugs: Vec<&str>) -> Future<u64> {
+        let mut payload = BTreeMap::new();
+        payload.insert("selected_teams", team_slugs);
+        Box::new(
+            self.github
+                .delete_message::<SeatsCancelled>(&self.path("/selected_teams"), json!(payload))
+                .map(|cancelled| cancelled.seats_cancelled),
+        )
+    }
+}
+
+// representations
+
+#[derive(Debug, Deserialize)]
+pub struct CopilotBilling {
+    pub seat_breakdown: SeatBreakdown,
+    /// one of `assign_all`, `assign_selected`, `disabled` or `unconfigured`
+    pub seat_management_setting: String,
+    pub ide_chat: Option<String>,
+    pub platform_chat: Option<String>,
+    pub cli: Option<String>,
+    pub public_code_suggestions: String,
+    /// either `business` or `enterprise`
+    pub plan_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SeatBreakdown {
+    pub total: u64,
+    pub added_this_cycle: u64,
+    pub pending_cancellation: u64,
+    pub pending_invitation: u64,
+    pub active_this_cycle: u64,
+    pub inactive_this_cycle: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SeatList {
+    pub total_seats: u64,
+    pub seats: Vec<Seat>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Seat {
+    pub assignee: Assignee,
+    /// the team through which the seat was assigned, if any
+    pub assigning_team: Option<Assignee>,
+    pub created_at: String,
+    pub updated_at: Option<String>,
+    pub pending_cancellation_date: Option<String>,
+    pub last_activity_at: Option<String>,
+    pub last_activity_editor: Option<String>,
+    pub plan_type: Option<String>,
+}
+
+/// The user or team a seat is assigned to
+#[derive(Debug, Deserialize)]
+pub struct Assignee {
+    pub id: u64,
+    /// only present for users
+    pub login: Option<String>,
+    /// only present for teams
+    pub slug: Option<String>,
+    pub name: Option<String>,
+    pub html_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SeatsCreated {
+    seats_created: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct SeatsCancelled {
+    seats_cancelled: u64,
+}
+
+#[derive(Default)]
+pub struct SeatListOptions {
+    params: HashMap<&'static str, String>,
+}
+
+impl SeatListOptions {
+    pub fn builder() -> SeatListOptionsBuilder {
+        SeatListOptionsBuilder::default()
+    }
+
+    /// serialize options as a string. returns None if no options are defined
+    pub fn serialize(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded)
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct SeatListOptionsBuilder(SeatListOptions);
+
+impl SeatListOptionsBuilder {
+    pub fn page(&mut self, page: u32) -> &mut Self {
+        self.0.params.insert("page", page.to_string());
+        self
+    }
+
+    pub fn per_page(&mut self, n: usize) -> &mut Self {
+        self.0.params.insert("per_page", n.to_string());
+        self
+    }
+
+    pub fn build(&self) -> SeatListOptions {
+        SeatListOptions {
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_seats() {
+        let seats = serde_json::from_str::<SeatList>(
+            r#"{"total_seats":2,"seats":[
+                {"assignee":{"id":1,"login":"octocat"},"created_at":"2021-08-03T18:00:00-06:00"},
+                {"assignee":{"id":2,"login":"octokitten"},"assigning_team":{"id":3,"slug":"justice-league","name":"Justice League"},"created_at":"2021-09-23T18:00:00-06:00"}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(seats.total_seats, 2);
+        assert_eq!(
+            seats.seats[1]
+                .assigning_team
+                .as_ref()
+                .and_then(|t| t.slug.clone()),
+            Some("justice-league".to_owned())
+        );
+    }
+}
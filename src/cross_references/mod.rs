@@ -0,0 +1,156 @@
+//! Cross references interface
+//!
+//! Issues and pull requests which mention an issue, or will close it once
+//! merged, appear as cross-referenced events on its timeline. Github only
+//! reports whether a reference will close its target through its GraphQL api,
+//! so cross references are fetched through it. See `IssueRef::cross_references`,
+//! and the [github docs](https://docs.github.com/en/graphql/reference/objects#crossreferencedevent)
+//! for more information.
+
+use ids::{IssueNumber, PullNumber};
+
+pub(crate) const CROSS_REFERENCES_QUERY: &str = r#"
+query($owner: String!, $repo: String!, $number: Int!, $cursor: String) {
+  repository(owner: $owner, name: $repo) {
+    issue(number: $number) {
+      timelineItems(first: 100, after: $cursor, itemTypes: [CROSS_REFERENCED_EVENT]) {
+        nodes {
+          ... on CrossReferencedEvent {
+            referencedAt
+            willCloseTarget
+            isCrossRepository
+            actor { login }
+            source {
+              __typename
+              ... on PullRequest {
+                number title url state mergedAt
+                repository { nameWithOwner }
+              }
+              ... on Issue {
+                number title url state
+                repository { nameWithOwner }
+              }
+            }
+          }
+        }
+        pageInfo { hasNextPage endCursor }
+      }
+    }
+  }
+}"#;
+
+// representations
+
+string_enum! {
+    /// The state of a referencing pull request
+    pub enum PullState {
+        Open => "OPEN",
+        Closed => "CLOSED",
+        Merged => "MERGED",
+    }
+}
+
+/// A mention of an issue by another issue or pull request
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrossReference {
+    pub referenced_at: String,
+    /// whether merging the referencing pull request will close the issue,
+    /// because it says it fixes or closes it
+    pub will_close_target: bool,
+    /// whether the reference was made from another repository
+    pub is_cross_repository: bool,
+    /// `None` for references of deleted users
+    pub actor: Option<Login>,
+    pub source: ReferenceSource,
+}
+
+impl CrossReference {
+    /// the referencing pull request, if the reference was made by one
+    pub fn pull(&self) -> Option<&ReferencingPull> {
+        match self.source {
+            ReferenceSource::PullRequest(ref pull) => Some(pull),
+            ReferenceSource::Issue(_) => None,
+        }
+    }
+
+    /// whether the reference was made by a pull request which will close the
+    /// issue once merged, or which closed it when it was
+    pub fn closes(&self) -> bool {
+        self.will_close_target && self.pull().is_some()
+    }
+}
+
+/// The issue or pull request which made a reference
+#[derive(Debug, Deserialize)]
+#[serde(tag = "__typename")]
+pub enum ReferenceSource {
+    PullRequest(ReferencingPull),
+    Issue(ReferencingIssue),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReferencingPull {
+    pub number: PullNumber,
+    pub title: String,
+    pub url: String,
+    pub state: PullState,
+    pub merged_at: Option<String>,
+    pub repository: RepositoryName,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReferencingIssue {
+    pub number: IssueNumber,
+    pub title: String,
+    pub url: String,
+    /// `OPEN` or `CLOSED`
+    pub state: String,
+    pub repository: RepositoryName,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepositoryName {
+    /// the repository's full name, like `softprops/hubcaps`
+    pub name_with_owner: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Login {
+    pub login: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn deserialize_cross_reference() {
+        let reference: CrossReference = serde_json::from_str(
+            r#"{
+              "referencedAt": "2019-05-01T10:00:00Z",
+              "willCloseTarget": true,
+              "isCrossRepository": false,
+              "actor": {"login": "octocat"},
+              "source": {
+                "__typename": "PullRequest",
+                "number": 42,
+                "title": "Fix the flux capacitor",
+                "url": "https://github.com/softprops/hubcaps/pull/42",
+                "state": "MERGED",
+                "mergedAt": "2019-05-02T10:00:00Z",
+                "repository": {"nameWithOwner": "softprops/hubcaps"}
+              }
+            }"#,
+        )
+        .unwrap();
+        assert!(reference.closes());
+        let pull = reference.pull().unwrap();
+        assert_eq!(pull.number, PullNumber(42));
+        assert_eq!(pull.state, PullState::Merged);
+    }
+}
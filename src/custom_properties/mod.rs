@@ -0,0 +1,252 @@
+//! Custom properties interface
+//!
+//! Organizations define a schema of custom properties which its
+//! repositories are then annotated with. See the [github docs](https://docs.github.com/en/rest/orgs/custom-properties)
+//! for more information
+
+use std::collections::BTreeMap;
+
+use futures::future;
+use hyper::client::connect::Connect;
+use serde_json;
+use url::form_urlencoded;
+
+use {unfold, Future, Github, Stream};
+
+fn identity<T>(x: T) -> T {
+    x
+}
+
+/// Provides access to the custom property schema of an organization and
+/// the property values of its repositories
+pub struct OrgCustomProperties<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    org: String,
+}
+
+impl<C: Clone + Connect + 'static> OrgCustomProperties<C> {
+    #[doc(hidden)]
+    pub fn new<O>(github: Github<C>, org: O) -> Self
+    where
+        O: Into<String>,
+    {
+        OrgCustomProperties {
+            github,
+            org: org.into(),
+        }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!("/orgs/{}/properties{}", self.org, more)
+    }
+
+    /// list the custom properties defined for this organization
+    pub fn schema(&self) -> Future<Vec<CustomProperty>> {
+        self.github.get(&self.path("/schema"))
+    }
+
+    /// create or update custom properties in a single request
+    pub fn create_or_update(&self, properties: Vec<CustomProperty>) -> Future<Vec<CustomProperty>> {
+        let mut payload = BTreeMap::new();
+        payload.insert("properties", properties);
+        self.github.patch(&self.path("/schema"), json!(payload))
+    }
+
+    /// get a custom property by name
+    pub fn get(&self, name: &str) -> Future<CustomProperty> {
+        self.github.get(&self.path(&format!("/schema/{}", name)))
+    }
+
+    /// delete a custom property by name. values of the property are removed
+    /// from all repositories
+    pub fn delete(&self, name: &str) -> Future<()> {
+        self.github.delete(&self.path(&format!("/schema/{}", name)))
+    }
+
+    /// list the property values of the organization's repositories. the
+    /// repositories may be narrowed down with a search query, like `topic:rust`
+    pub fn values(&self, repository_query: Option<&str>) -> Future<Vec<RepositoryValues>> {
+        self.github.get(&self.values_path(repository_query))
+    }
+
+    /// provides a stream over all pages of the property values of the
+    /// organization's repositories
+    pub fn iter_values(&self, repository_query: Option<&str>) -> Stream<RepositoryValues> {
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&self.values_path(repository_query)),
+            identity,
+        )
+    }
+
+    fn values_path(&self, repository_query: Option<&str>) -> String {
+        let mut uri = vec![self.path("/values")];
+        if let Some(query) = repository_query {
+            uri.push(
+                form_urlencoded::Serializer::new(String::new())
+                    .append_pair("repository_query", query)
+                    .finish(),
+            );
+        }
+        uri.join("?")
+    }
+
+    /// set property values for up to 30 repositories at a time. a
+    /// `None` value removes a property from the repositories
+    pub fn set_values(
+        &self,
+        repository_names: Vec<&str>,
+        values: Vec<PropertyValue>,
+    ) -> Future<()> {
+        let payload = SetValues {
+            repository_names: Some(repository_names),
+            properties: values,
+        };
+        self.github
+            .patch_no_response(&self.path("/values"), json!(payload))
+    }
+}
+
+/// Provides access to the custom property values of a repository
+pub struct CustomProperties<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    owner: String,
+    repo: String,
+}
+
+impl<C: Clone + Connect + 'static> CustomProperties<C> {
+    #[doc(hidden)]
+    pub fn new<O, R>(github: Github<C>, owner: O, repo: R) -> Self
+    where
+        O: Into<String>,
+        R: Into<String>,
+    {
+        CustomProperties {
+            github,
+            owner: owner.into(),
+            repo: repo.into(),
+        }
+    }
+
+    fn path(&self) -> String {
+        format!("/repos/{}/{}/properties/values", self.owner, self.repo)
+    }
+
+    /// list the property values of this repository
+    pub fn values(&self) -> Future<Vec<PropertyValue>> {
+        self.github.get(&self.path())
+    }
+
+    /// set property values of this repository. a `None` value removes a
+    /// property from the repository
+    pub fn set_values(&self, values: Vec<PropertyValue>) -> Future<()> {
+        let payload = SetValues {
+            repository_names: None,
+            properties: values,
+        };
+        self.github.patch_no_response(&self.path(), json!(payload))
+    }
+}
+
+// representations
+
+/// The value of a custom property. Multi select properties have multiple values
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Value {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+/// A custom property definition
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CustomProperty {
+    pub property_name: String,
+    /// one of `string`, `single_select`, `multi_select` or `true_false`
+    pub value_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_value: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// the values select properties may take
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_values: Option<Vec<String>>,
+    /// either `org_actors` or `org_and_repo_actors`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub values_editable_by: Option<String>,
+    /// either `organization` or `enterprise`
+    #[serde(skip_serializing)]
+    pub source_type: Option<String>,
+    #[serde(skip_serializing)]
+    pub url: Option<String>,
+}
+
+impl CustomProperty {
+    pub fn new<N, T>(property_name: N, value_type: T) -> Self
+    where
+        N: Into<String>,
+        T: Into<String>,
+    {
+        CustomProperty {
+            property_name: property_name.into(),
+            value_type: value_type.into(),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PropertyValue {
+    pub property_name: String,
+    pub value: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RepositoryValues {
+    pub repository_id: u64,
+    pub repository_name: String,
+    pub repository_full_name: String,
+    pub properties: Vec<PropertyValue>,
+}
+
+#[derive(Serialize)]
+struct SetValues<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    repository_names: Option<Vec<&'a str>>,
+    properties: Vec<PropertyValue>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn property_values() {
+        let values = serde_json::from_str::<Vec<PropertyValue>>(
+            r#"[
+                {"property_name":"environment","value":"production"},
+                {"property_name":"teams","value":["platform","security"]},
+                {"property_name":"service","value":null}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(values[0].value, Some(Value::Single("production".into())));
+        assert_eq!(
+            values[1].value,
+            Some(Value::Multiple(vec!["platform".into(), "security".into()]))
+        );
+        assert_eq!(values[2].value, None);
+        assert_eq!(
+            serde_json::to_string(&values[2]).unwrap(),
+            r#"{"property_name":"service","value":null}"#
+        );
+    }
+}
@@ -0,0 +1,435 @@
+//! Dependabot interface
+//!
+//! See the [github docs](https://docs.github.com/en/rest/dependabot) for more information
+
+use std::collections::HashMap;
+use std::fmt;
+
+use futures::future;
+use hyper::client::connect::Connect;
+use serde_json;
+use url::form_urlencoded;
+
+use code_scanning::AlertRepository;
+use secrets::Secrets;
+use users::User;
+use {unfold, Future, Github, SortDirection, Stream};
+
+fn identity<T>(x: T) -> T {
+    x
+}
+
+/// The state of a Dependabot alert
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertState {
+    AutoDismissed,
+    Dismissed,
+    Fixed,
+    Open,
+}
+
+impl fmt::Display for AlertState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AlertState::AutoDismissed => "auto_dismissed",
+            AlertState::Dismissed => "dismissed",
+            AlertState::Fixed => "fixed",
+            AlertState::Open => "open",
+        }
+        .fmt(f)
+    }
+}
+
+/// The reason a Dependabot alert was dismissed
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DismissedReason {
+    FixStarted,
+    Inaccurate,
+    NoBandwidth,
+    NotUsed,
+    TolerableRisk,
+}
+
+/// Sort keys for Dependabot alerts
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AlertSort {
+    Created,
+    Updated,
+    EpssPercentage,
+}
+
+impl fmt::Display for AlertSort {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AlertSort::Created => "created",
+            AlertSort::Updated => "updated",
+            AlertSort::EpssPercentage => "epss_percentage",
+        }
+        .fmt(f)
+    }
+}
+
+/// Provides access to Dependabot operations for a repository
+pub struct Dependabot<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    owner: String,
+    repo: String,
+}
+
+impl<C: Clone + Connect + 'static> Dependabot<C> {
+    #[doc(hidden)]
+    pub fn new<O, R>(github: Github<C>, owner: O, repo: R) -> Self
+    where
+        O: Into<String>,
+        R: Into<String>,
+    {
+        Dependabot {
+            github,
+            owner: owner.into(),
+            repo: repo.into(),
+        }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!("/repos/{}/{}/dependabot{}", self.owner, self.repo, more)
+    }
+
+    /// list Dependabot alerts
+    pub fn alerts(&self, options: &AlertListOptions) -> Future<Vec<Alert>> {
+        let mut uri = vec![self.path("/alerts")];
+        if let Some(query) = options.serialize() {
+            uri.push(query);
+        }
+        self.github.get(&uri.join("?"))
+    }
+
+    /// provides a stream over all pages of Dependabot alerts
+    pub fn iter_alerts(&self, options: &AlertListOptions) -> Stream<Alert> {
+        let mut uri = vec![self.path("/alerts")];
+        if let Some(query) = options.serialize() {
+            uri.push(query);
+        }
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&uri.join("?")),
+            identity,
+        )
+    }
+
+    /// get a Dependabot alert by number
+    pub fn alert(&self, number: u64) -> Future<Alert> {
+        self.github.get(&self.path(&format!("/alerts/{}", number)))
+    }
+
+    /// update the state of a Dependabot alert, typically to dismiss it
+    pub fn update_alert(&self, number: u64, options: &AlertUpdateOptions) -> Future<Alert> {
+        self.github
+            .patch(&self.path(&format!("/alerts/{}", number)), json!(options))
+    }
+
+    /// get a reference to the Dependabot secrets of this repository
+    pub fn secrets(&self) -> Secrets<C> {
+        Secrets::new(self.github.clone(), self.path("/secrets"))
+    }
+}
+
+/// Provides access to Dependabot operations for an organization
+pub struct OrgDependabot<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    org: String,
+}
+
+impl<C: Clone + Connect + 'static> OrgDependabot<C> {
+    #[doc(hidden)]
+    pub fn new<O>(github: Github<C>, org: O) -> Self
+    where
+        O: Into<String>,
+    {
+        OrgDependabot {
+            github,
+            org: org.into(),
+        }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!("/orgs/{}/dependabot{}", self.org, more)
+    }
+
+    /// list Dependabot alerts across the organization's repositories
+    pub fn alerts(&self, options: &AlertListOptions) -> Future<Vec<Alert>> {
+        let mut uri = vec![self.path("/alerts")];
+        if let Some(query) = options.serialize() {
+            uri.push(query);
+        }
+        self.github.get(&uri.join("?"))
+    }
+
+    /// provides a stream over all pages of the organization's Dependabot alerts
+    pub fn iter_alerts(&self, options: &AlertListOptions) -> Stream<Alert> {
+        let mut uri = vec![self.path("/alerts")];
+        if let Some(query) = options.serialize() {
+            uri.push(query);
+        }
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&uri.join("?")),
+            identity,
+        )
+    }
+
+    /// get a reference to the Dependabot secrets of this organization
+    pub fn secrets(&self) -> Secrets<C> {
+        Secrets::new(self.github.clone(), self.path("/secrets"))
+    }
+}
+
+// representations
+
+#[derive(Debug, Deserialize)]
+pub struct Alert {
+    pub number: u64,
+    pub state: String,
+    pub dependency: Dependency,
+    pub security_advisory: Advisory,
+    pub security_vulnerability: Vulnerability,
+    pub url: String,
+    pub html_url: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub dismissed_at: Option<String>,
+    pub dismissed_by: Option<User>,
+    pub dismissed_reason: Option<String>,
+    pub dismissed_comment: Option<String>,
+    pub fixed_at: Option<String>,
+    pub auto_dismissed_at: Option<String>,
+    /// only included when listing an organization's alerts
+    pub repository: Option<AlertRepository>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Dependency {
+    pub package: Package,
+    pub manifest_path: String,
+    /// either `development` or `runtime`
+    pub scope: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Package {
+    pub ecosystem: String,
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Advisory {
+    pub ghsa_id: String,
+    pub cve_id: Option<String>,
+    pub summary: String,
+    pub description: String,
+    pub severity: String,
+    pub published_at: String,
+    pub updated_at: String,
+    pub withdrawn_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Vulnerability {
+    pub package: Package,
+    pub severity: String,
+    pub vulnerable_version_range: String,
+    pub first_patched_version: Option<PatchedVersion>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PatchedVersion {
+    pub identifier: String,
+}
+
+#[derive(Default)]
+pub struct AlertListOptions {
+    params: HashMap<&'static str, String>,
+}
+
+impl AlertListOptions {
+    pub fn builder() -> AlertListOptionsBuilder {
+        AlertListOptionsBuilder::default()
+    }
+
+    /// serialize options as a string. returns None if no options are defined
+    pub fn serialize(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded)
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct AlertListOptionsBuilder(AlertListOptions);
+
+impl AlertListOptionsBuilder {
+    /// filter by one or more states
+    pub fn state(&mut self, states: Vec<AlertState>) -> &mut Self {
+        self.0.params.insert(
+            "state",
+            states
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+        self
+    }
+
+    /// a comma separated list of `low`, `medium`, `high` or `critical`
+    pub fn severity<S>(&mut self, severity: S) -> &mut Self
+    where
+        S: Into<String>,
+    {
+        self.0.params.insert("severity", severity.into());
+        self
+    }
+
+    /// a comma separated list of ecosystems, like `npm,pip,rust`
+    pub fn ecosystem<E>(&mut self, ecosystem: E) -> &mut Self
+    where
+        E: Into<String>,
+    {
+        self.0.params.insert("ecosystem", ecosystem.into());
+        self
+    }
+
+    /// a comma separated list of package names
+    pub fn package<P>(&mut self, package: P) -> &mut Self
+    where
+        P: Into<String>,
+    {
+        self.0.params.insert("package", package.into());
+        self
+    }
+
+    /// a comma separated list of manifest paths
+    pub fn manifest<M>(&mut self, manifest: M) -> &mut Self
+    where
+        M: Into<String>,
+    {
+        self.0.params.insert("manifest", manifest.into());
+        self
+    }
+
+    /// either `development` or `runtime`
+    pub fn scope<S>(&mut self, scope: S) -> &mut Self
+    where
+        S: Into<String>,
+    {
+        self.0.params.insert("scope", scope.into());
+        self
+    }
+
+    pub fn sort(&mut self, sort: AlertSort) -> &mut Self {
+        self.0.params.insert("sort", sort.to_string());
+        self
+    }
+
+    pub fn direction(&mut self, direction: SortDirection) -> &mut Self {
+        self.0.params.insert("direction", direction.to_string());
+        self
+    }
+
+    pub fn per_page(&mut self, n: usize) -> &mut Self {
+        self.0.params.insert("per_page", n.to_string());
+        self
+    }
+
+    pub fn build(&self) -> AlertListOptions {
+        AlertListOptions {
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+/// options for updating a Dependabot alert
+#[derive(Debug, Serialize)]
+pub struct AlertUpdateOptions {
+    pub state: AlertState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dismissed_reason: Option<DismissedReason>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dismissed_comment: Option<String>,
+}
+
+impl AlertUpdateOptions {
+    /// alerts may only be updated to the `Open` or `Dismissed` states
+    pub fn builder(state: AlertState) -> AlertUpdateOptionsBuilder {
+        AlertUpdateOptionsBuilder(AlertUpdateOptions {
+            state,
+            dismissed_reason: None,
+            dismissed_comment: None,
+        })
+    }
+}
+
+pub struct AlertUpdateOptionsBuilder(AlertUpdateOptions);
+
+impl AlertUpdateOptionsBuilder {
+    /// required when dismissing an alert
+    pub fn dismissed_reason(&mut self, reason: DismissedReason) -> &mut Self {
+        self.0.dismissed_reason = Some(reason);
+        self
+    }
+
+    pub fn dismissed_comment<C>(&mut self, comment: C) -> &mut Self
+    where
+        C: Into<String>,
+    {
+        self.0.dismissed_comment = Some(comment.into());
+        self
+    }
+
+    pub fn build(&self) -> AlertUpdateOptions {
+        AlertUpdateOptions {
+            state: self.0.state,
+            dismissed_reason: self.0.dismissed_reason,
+            dismissed_comment: self.0.dismissed_comment.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alert_list_reqs() {
+        let options = AlertListOptions::builder()
+            .state(vec![AlertState::Open, AlertState::AutoDismissed])
+            .build();
+        assert_eq!(
+            options.serialize(),
+            Some("state=open%2Cauto_dismissed".to_owned())
+        )
+    }
+
+    #[test]
+    fn alert_update_reqs() {
+        let options = AlertUpdateOptions::builder(AlertState::Dismissed)
+            .dismissed_reason(DismissedReason::TolerableRisk)
+            .build();
+        assert_eq!(
+            serde_json::to_string(&options).unwrap(),
+            r#"{"state":"dismissed","dismissed_reason":"tolerable_risk"}"#
+        )
+    }
+}
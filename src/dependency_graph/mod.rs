@@ -0,0 +1,292 @@
+//! Dependency graph interface
+//!
+//! See the [github docs](https://docs.github.com/en/rest/dependency-graph) for more information
+
+use std::collections::BTreeMap;
+
+use futures::{future, Future as StdFuture};
+use hyper::client::connect::Connect;
+use serde_json;
+use url::form_urlencoded;
+
+use {Future, Github};
+
+/// Provides access to the dependency graph of a repository
+pub struct DependencyGraph<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    owner: String,
+    repo: String,
+}
+
+impl<C: Clone + Connect + 'static> DependencyGraph<C> {
+    #[doc(hidden)]
+    pub fn new<O, R>(github: Github<C>, owner: O, repo: R) -> Self
+    where
+        O: Into<String>,
+        R: Into<String>,
+    {
+        DependencyGraph {
+            github,
+            owner: owner.into(),
+            repo: repo.into(),
+        }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!(
+            "/repos/{}/{}/dependency-graph{}",
+            self.owner, self.repo, more
+        )
+    }
+
+    /// export the software bill of materials of this repository as an SPDX document
+    pub fn sbom(&self) -> Future<Sbom> {
+        Box::new(
+            self.github
+                .get::<SbomResponse>(&self.path("/sbom"))
+                .map(|response| response.sbom),
+        )
+    }
+
+    /// list the dependency changes between two commits, including the
+    /// vulnerabilities of added dependencies. `basehead` takes the form `base...head`.
+    /// Changes may optionally be limited to those of a single manifest file
+    pub fn compare<B>(&self, basehead: B, manifest: Option<&str>) -> Future<Vec<DependencyChange>>
+    where
+        B: Into<String>,
+    {
+        let mut uri = vec![self.path(&format!("/compare/{}", basehead.into()))];
+        if let Some(name) = manifest {
+            uri.push(
+                form_urlencoded::Serializer::new(String::new())
+                    .append_pair("name", name)
+                    .finish(),
+            );
+        }
+        self.github.get(&uri.join("?"))
+    }
+
+    /// submit a snapshot of the dependencies resolved at build time
+    pub fn submit(&self, snapshot: &Snapshot) -> Future<SnapshotResult> {
+        self.github.post(&self.path("/snapshots"), json!(snapshot))
+    }
+}
+
+// representations
+
+#[derive(Debug, Deserialize)]
+struct SbomResponse {
+    sbom: Sbom,
+}
+
+/// An [SPDX](https://spdx.dev/) 2.3 document
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Sbom {
+    #[serde(rename = "SPDXID")]
+    pub spdx_id: String,
+    pub spdx_version: String,
+    pub creation_info: CreationInfo,
+    pub name: String,
+    pub data_license: String,
+    pub document_namespace: String,
+    #[serde(default)]
+    pub document_describes: Vec<String>,
+    pub packages: Vec<SbomPackage>,
+    #[serde(default)]
+    pub relationships: Vec<SbomRelationship>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreationInfo {
+    pub created: String,
+    pub creators: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SbomPackage {
+    #[serde(rename = "SPDXID")]
+    pub spdx_id: String,
+    pub name: String,
+    pub version_info: Option<String>,
+    pub download_location: Option<String>,
+    pub files_analyzed: Option<bool>,
+    pub license_concluded: Option<String>,
+    pub license_declared: Option<String>,
+    pub supplier: Option<String>,
+    pub copyright_text: Option<String>,
+    #[serde(default)]
+    pub external_refs: Vec<ExternalRef>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalRef {
+    pub reference_category: String,
+    pub reference_locator: String,
+    pub reference_type: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SbomRelationship {
+    pub relationship_type: String,
+    pub spdx_element_id: String,
+    pub related_spdx_element: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DependencyChange {
+    /// either `added` or `removed`
+    pub change_type: String,
+    pub manifest: String,
+    pub ecosystem: String,
+    pub name: String,
+    pub version: String,
+    pub package_url: Option<String>,
+    pub license: Option<String>,
+    pub source_repository_url: Option<String>,
+    pub scope: Option<String>,
+    pub vulnerabilities: Vec<ChangeVulnerability>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChangeVulnerability {
+    pub severity: String,
+    pub advisory_ghsa_id: String,
+    pub advisory_summary: String,
+    pub advisory_url: String,
+}
+
+/// A snapshot of the dependencies of a repository at a given commit, as
+/// resolved by a build. See the [github docs](https://docs.github.com/en/rest/dependency-graph/dependency-submission)
+/// for more information
+#[derive(Debug, Serialize, PartialEq)]
+pub struct Snapshot {
+    /// the version of the snapshot format. currently `0`
+    pub version: u32,
+    pub job: Job,
+    pub sha: String,
+    /// the fully qualified git ref, like `refs/heads/main`
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    pub detector: Detector,
+    /// an ISO 8601 timestamp of when the snapshot was taken
+    pub scanned: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<BTreeMap<String, serde_json::Value>>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub manifests: BTreeMap<String, Manifest>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct Job {
+    /// identifies the job across runs, like a workflow name and job id.
+    /// submissions with the same correlator replace each other
+    pub correlator: String,
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub html_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct Detector {
+    pub name: String,
+    pub version: String,
+    pub url: String,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct Manifest {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<ManifestFile>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<BTreeMap<String, serde_json::Value>>,
+    /// resolved dependencies keyed by a name unique within the manifest
+    pub resolved: BTreeMap<String, ResolvedDependency>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ManifestFile {
+    /// the path of the manifest relative to the repository root
+    pub source_location: String,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Relationship {
+    Direct,
+    Indirect,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Scope {
+    Runtime,
+    Development,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ResolvedDependency {
+    /// a [package url](https://github.com/package-url/purl-spec), like `pkg:cargo/serde@1.0.0`
+    pub package_url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<BTreeMap<String, serde_json::Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relationship: Option<Relationship>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<Scope>,
+    /// the keys of the resolved dependencies this dependency depends on
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SnapshotResult {
+    pub id: u64,
+    pub created_at: String,
+    pub message: String,
+    /// one of `SUCCESS`, `ACCEPTED` or `INVALID`
+    pub result: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_sbom() {
+        let sbom = serde_json::from_str::<SbomResponse>(
+            r#"{"sbom":{
+                "SPDXID":"SPDXRef-DOCUMENT",
+                "spdxVersion":"SPDX-2.3",
+                "creationInfo":{"created":"2019-01-01T00:00:00Z","creators":["Tool: GitHub.com-Dependency-Graph"]},
+                "name":"com.github.softprops/hubcaps",
+                "dataLicense":"CC0-1.0",
+                "documentNamespace":"https://github.com/softprops/hubcaps/dependency_graph/sbom-123",
+                "packages":[{
+                    "SPDXID":"SPDXRef-cargo-serde-1.0.0",
+                    "name":"cargo:serde",
+                    "versionInfo":"1.0.0",
+                    "externalRefs":[{
+                        "referenceCategory":"PACKAGE-MANAGER",
+                        "referenceLocator":"pkg:cargo/serde@1.0.0",
+                        "referenceType":"purl"
+                    }]
+                }]
+            }}"#,
+        )
+        .unwrap()
+        .sbom;
+        assert_eq!(sbom.spdx_version, "SPDX-2.3");
+        assert_eq!(
+            sbom.packages[0].external_refs[0].reference_locator,
+            "pkg:cargo/serde@1.0.0"
+        );
+    }
+}
@@ -4,15 +4,21 @@ extern crate futures;
 extern crate serde_json;
 
 use std::collections::HashMap;
+use std::fmt;
 
 use futures::future;
+use futures::Stream as StdStream;
 use hyper::client::connect::Connect;
 use serde;
 use statuses::State;
 use url::form_urlencoded;
 use users::User;
 
-use {Future, Github};
+use {unfold, Future, Github, Stream};
+
+fn identity<T>(x: T) -> T {
+    x
+}
 
 /// Interface for repository deployments
 pub struct Deployments<C>
@@ -96,6 +102,19 @@ impl<C: Clone + Connect + 'static> Deployments<C> {
         self.github.get(&uri.join("?"))
     }
 
+    /// provides a stream over all pages of deployments for a repository
+    pub fn iter(&self, opts: &DeploymentListOptions) -> Stream<Deployment> {
+        let mut uri = vec![self.path("")];
+        if let Some(query) = opts.serialize() {
+            uri.push(query);
+        }
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&uri.join("?")),
+            identity,
+        )
+    }
+
     /// creates a new deployment for this repository
     pub fn create(&self, dep: &DeploymentOptions) -> Future<Deployment> {
         self.github.post(&self.path(""), json!(dep))
@@ -110,6 +129,42 @@ impl<C: Clone + Connect + 'static> Deployments<C> {
             id,
         )
     }
+
+    /// marks every deployment to `environment` other than `current` inactive,
+    /// returning the statuses created. Deployments created with a status
+    /// marked `auto_inactive` do this for themselves
+    pub fn deactivate_previous<E>(
+        &self,
+        environment: E,
+        current: u64,
+    ) -> Future<Vec<DeploymentStatus>>
+    where
+        E: Into<String>,
+    {
+        let github = self.github.clone();
+        let owner = self.owner.clone();
+        let repo = self.repo.clone();
+        let inactive = DeploymentStatusOptions::builder(DeploymentState::Inactive).build();
+        let deployments = self.iter(
+            &DeploymentListOptions::builder()
+                .environment(environment)
+                .build(),
+        );
+        Box::new(
+            deployments
+                .filter(move |deployment| deployment.id != current)
+                .and_then(move |deployment| {
+                    DeploymentStatuses::new(
+                        github.clone(),
+                        owner.as_str(),
+                        repo.as_str(),
+                        deployment.id,
+                    )
+                    .create(&inactive)
+                })
+                .collect(),
+        )
+    }
 }
 
 // representations
@@ -131,6 +186,10 @@ pub struct Deployment {
     pub updated_at: String,
     pub statuses_url: String,
     pub repository_url: String,
+    #[serde(default)]
+    pub transient_environment: bool,
+    #[serde(default)]
+    pub production_environment: bool,
 }
 
 #[derive(Debug, Default, Serialize)]
@@ -150,6 +209,10 @@ pub struct DeploymentOptions {
     pub environment: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transient_environment: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub production_environment: Option<bool>,
 }
 
 impl DeploymentOptions {
@@ -217,6 +280,20 @@ impl DeploymentOptionsBuilder {
         self
     }
 
+    /// mark the environment as one that will no longer exist at some point
+    /// in the future, such as a review app
+    pub fn transient_environment(&mut self, transient: bool) -> &mut Self {
+        self.0.transient_environment = Some(transient);
+        self
+    }
+
+    /// mark the environment as one end users interact with. defaults to
+    /// true when the environment is `production`
+    pub fn production_environment(&mut self, production: bool) -> &mut Self {
+        self.0.production_environment = Some(production);
+        self
+    }
+
     pub fn build(&self) -> DeploymentOptions {
         DeploymentOptions {
             commit_ref: self.0.commit_ref.clone(),
@@ -226,6 +303,53 @@ impl DeploymentOptionsBuilder {
             payload: self.0.payload.clone(),
             environment: self.0.environment.clone(),
             description: self.0.description.clone(),
+            transient_environment: self.0.transient_environment,
+            production_environment: self.0.production_environment,
+        }
+    }
+}
+
+/// The state of a deployment
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeploymentState {
+    Pending,
+    Queued,
+    InProgress,
+    Success,
+    Failure,
+    Error,
+    Inactive,
+}
+
+impl Default for DeploymentState {
+    fn default() -> DeploymentState {
+        DeploymentState::Pending
+    }
+}
+
+impl fmt::Display for DeploymentState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DeploymentState::Pending => "pending",
+            DeploymentState::Queued => "queued",
+            DeploymentState::InProgress => "in_progress",
+            DeploymentState::Success => "success",
+            DeploymentState::Failure => "failure",
+            DeploymentState::Error => "error",
+            DeploymentState::Inactive => "inactive",
+        }
+        .fmt(f)
+    }
+}
+
+impl From<State> for DeploymentState {
+    fn from(state: State) -> DeploymentState {
+        match state {
+            State::Pending => DeploymentState::Pending,
+            State::Success => DeploymentState::Success,
+            State::Error => DeploymentState::Error,
+            State::Failure => DeploymentState::Failure,
         }
     }
 }
@@ -235,7 +359,7 @@ pub struct DeploymentStatus {
     pub url: String,
     pub created_at: String,
     pub updated_at: String,
-    pub state: State,
+    pub state: DeploymentState,
     pub target_url: Option<String>,
     pub description: Option<String>,
     pub id: u64,
@@ -247,7 +371,7 @@ pub struct DeploymentStatus {
 pub struct DeploymentStatusOptionsBuilder(DeploymentStatusOptions);
 
 impl DeploymentStatusOptionsBuilder {
-    pub(crate) fn new(state: State) -> DeploymentStatusOptionsBuilder {
+    pub(crate) fn new(state: DeploymentState) -> DeploymentStatusOptionsBuilder {
         DeploymentStatusOptionsBuilder(DeploymentStatusOptions {
             state,
             ..Default::default()
@@ -270,27 +394,77 @@ impl DeploymentStatusOptionsBuilder {
         self
     }
 
+    /// move the deployment to another environment
+    pub fn environment<E>(&mut self, env: E) -> &mut DeploymentStatusOptionsBuilder
+    where
+        E: Into<String>,
+    {
+        self.0.environment = Some(env.into());
+        self
+    }
+
+    /// set the url for accessing the deployed environment
+    pub fn environment_url<U>(&mut self, url: U) -> &mut DeploymentStatusOptionsBuilder
+    where
+        U: Into<String>,
+    {
+        self.0.environment_url = Some(url.into());
+        self
+    }
+
+    /// set the url of the deployment's output. supersedes `target_url`
+    pub fn log_url<U>(&mut self, url: U) -> &mut DeploymentStatusOptionsBuilder
+    where
+        U: Into<String>,
+    {
+        self.0.log_url = Some(url.into());
+        self
+    }
+
+    /// when set on a successful status, Github marks all previous
+    /// non-transient, non-production deployments to the same environment
+    /// inactive
+    pub fn auto_inactive(&mut self, auto_inactive: bool) -> &mut DeploymentStatusOptionsBuilder {
+        self.0.auto_inactive = Some(auto_inactive);
+        self
+    }
+
     pub fn build(&self) -> DeploymentStatusOptions {
         DeploymentStatusOptions {
             state: self.0.state.clone(),
             target_url: self.0.target_url.clone(),
             description: self.0.description.clone(),
+            environment: self.0.environment.clone(),
+            environment_url: self.0.environment_url.clone(),
+            log_url: self.0.log_url.clone(),
+            auto_inactive: self.0.auto_inactive,
         }
     }
 }
 
 #[derive(Debug, Default, Serialize)]
 pub struct DeploymentStatusOptions {
-    state: State,
+    state: DeploymentState,
     #[serde(skip_serializing_if = "Option::is_none")]
     target_url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    environment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    environment_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    log_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    auto_inactive: Option<bool>,
 }
 
 impl DeploymentStatusOptions {
-    pub fn builder(state: State) -> DeploymentStatusOptionsBuilder {
-        DeploymentStatusOptionsBuilder::new(state)
+    pub fn builder<S>(state: S) -> DeploymentStatusOptionsBuilder
+    where
+        S: Into<DeploymentState>,
+    {
+        DeploymentStatusOptionsBuilder::new(state.into())
     }
 }
 
@@ -363,7 +537,7 @@ impl DeploymentListOptionsBuilder {
 
 #[cfg(test)]
 mod tests {
-    use super::{DeploymentOptions, DeploymentStatusOptions};
+    use super::{DeploymentOptions, DeploymentState, DeploymentStatusOptions};
     use serde::ser::Serialize;
     use serde_json;
     use statuses::State;
@@ -404,6 +578,14 @@ mod tests {
                     "}"
                 ),
             ),
+            (
+                DeploymentOptions::builder("test")
+                    .environment("review-1")
+                    .transient_environment(true)
+                    .production_environment(false)
+                    .build(),
+                r#"{"ref":"test","environment":"review-1","transient_environment":true,"production_environment":false}"#,
+            ),
         ];
         test_encoding(tests)
     }
@@ -428,6 +610,13 @@ mod tests {
                     .build(),
                 r#"{"state":"pending","target_url":"http://host.com","description":"desc"}"#,
             ),
+            (
+                DeploymentStatusOptions::builder(DeploymentState::InProgress)
+                    .log_url("http://host.com/logs")
+                    .auto_inactive(true)
+                    .build(),
+                r#"{"state":"in_progress","log_url":"http://host.com/logs","auto_inactive":true}"#,
+            ),
         ];
         test_encoding(tests)
     }
@@ -0,0 +1,110 @@
+//! Endpoint deprecation notices
+//!
+//! Github announces the retirement of an endpoint with the `Deprecation`
+//! and `Sunset` response headers, and may link to documentation with a
+//! `Link` header of relation type `deprecation`. Each response carrying
+//! these headers is logged as a warning. To act on them, for instance to
+//! fail a test suite, parse them from a `Middleware`'s `after` hook with
+//! `Deprecation::from_headers`.
+
+use std::fmt;
+
+use hyper::header::LINK;
+use hyper::HeaderMap;
+use hyperx::header::{Link, RelationType};
+
+/// The deprecation notice carried by a response
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Deprecation {
+    /// the value of the `Deprecation` header: `true`, or the date the
+    /// endpoint was deprecated
+    pub deprecation: Option<String>,
+    /// the value of the `Sunset` header: the date the endpoint will stop
+    /// responding
+    pub sunset: Option<String>,
+    /// a link to documentation about the deprecation
+    pub link: Option<String>,
+}
+
+impl Deprecation {
+    /// parse the deprecation notice carried by `headers`, if any
+    pub fn from_headers(headers: &HeaderMap) -> Option<Deprecation> {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.to_owned())
+        };
+        let deprecation = Deprecation {
+            deprecation: header("deprecation"),
+            sunset: header("sunset"),
+            link: headers
+                .get(LINK)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse::<Link>().ok())
+                .and_then(|link| deprecation_link(&link)),
+        };
+        if deprecation == Deprecation::default() {
+            None
+        } else {
+            Some(deprecation)
+        }
+    }
+}
+
+impl fmt::Display for Deprecation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "deprecated")?;
+        if let Some(ref deprecation) = self.deprecation {
+            if deprecation != "true" {
+                write!(f, " since {}", deprecation)?;
+            }
+        }
+        if let Some(ref sunset) = self.sunset {
+            write!(f, ", removed after {}", sunset)?;
+        }
+        if let Some(ref link) = self.link {
+            write!(f, " (see {})", link)?;
+        }
+        Ok(())
+    }
+}
+
+fn deprecation_link(link: &Link) -> Option<String> {
+    let relation = RelationType::ExtRelType("deprecation".into());
+    link.values()
+        .iter()
+        .find(|v| v.rel().unwrap_or(&[]).contains(&relation))
+        .map(|v| v.link().to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_headers() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(Deprecation::from_headers(&headers), None);
+        headers.insert("deprecation", "true".parse().unwrap());
+        headers.insert("sunset", "Sat, 1 Nov 2025 00:00:00 GMT".parse().unwrap());
+        headers.insert(
+            LINK,
+            r#"<https://api.github.com/repositories/1/issues?page=2>; rel="next", <https://developer.github.com/changes/2020-02-10-deprecating-auth-through-query-param/>; rel="deprecation""#
+                .parse()
+                .unwrap(),
+        );
+        let deprecation = Deprecation::from_headers(&headers).unwrap();
+        assert_eq!(
+            deprecation.link,
+            Some(
+                "https://developer.github.com/changes/2020-02-10-deprecating-auth-through-query-param/"
+                    .into()
+            )
+        );
+        assert_eq!(
+            deprecation.to_string(),
+            "deprecated, removed after Sat, 1 Nov 2025 00:00:00 GMT (see https://developer.github.com/changes/2020-02-10-deprecating-auth-through-query-param/)"
+        );
+    }
+}
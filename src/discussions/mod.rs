@@ -0,0 +1,372 @@
+//! Discussions interface
+//!
+//! Discussions are only exposed through Github's GraphQL api. Discussions,
+//! their categories and comments are addressed by their GraphQL node ids
+//!
+//! See the [github docs](https://docs.github.com/en/graphql/guides/using-the-graphql-api-for-discussions)
+//! for more information.
+
+use futures::Future as StdFuture;
+use hyper::client::connect::Connect;
+use serde_json::Value;
+
+use graphql::{extract, json_vars, paginate, GraphQL};
+use {Future, Github};
+
+const DISCUSSION_FIELDS: &str = "id number title body url createdAt \
+                                 author { login } category { id name slug isAnswerable } \
+                                 answer { id }";
+
+const COMMENT_FIELDS: &str = "id body url createdAt isAnswer author { login } replyTo { id }";
+
+const CATEGORIES_QUERY: &str = r#"
+query($owner: String!, $repo: String!, $cursor: String) {
+  repository(owner: $owner, name: $repo) {
+    discussionCategories(first: 100, after: $cursor) {
+      nodes { id name slug description emoji isAnswerable }
+      pageInfo { hasNextPage endCursor }
+    }
+  }
+}"#;
+
+const REPOSITORY_ID_QUERY: &str = r#"
+query($owner: String!, $repo: String!) {
+  repository(owner: $owner, name: $repo) { id }
+}"#;
+
+const MARK_ANSWER_MUTATION: &str = r#"
+mutation($comment: ID!) {
+  markDiscussionCommentAsAnswer(input: {id: $comment}) {
+    discussion { id }
+  }
+}"#;
+
+const UNMARK_ANSWER_MUTATION: &str = r#"
+mutation($comment: ID!) {
+  unmarkDiscussionCommentAsAnswer(input: {id: $comment}) {
+    discussion { id }
+  }
+}"#;
+
+/// Provides access to the discussions of a repository
+pub struct Discussions<C>
+where
+    C: Clone + Connect + 'static,
+{
+    graphql: GraphQL<C>,
+    owner: String,
+    repo: String,
+}
+
+impl<C: Clone + Connect + 'static> Discussions<C> {
+    #[doc(hidden)]
+    pub fn new<O, R>(github: Github<C>, owner: O, repo: R) -> Self
+    where
+        O: Into<String>,
+        R: Into<String>,
+    {
+        Discussions {
+            graphql: GraphQL::new(github),
+            owner: owner.into(),
+            repo: repo.into(),
+        }
+    }
+
+    fn repository_vars(&self) -> Value {
+        json_vars(&[
+            ("owner", Value::String(self.owner.clone())),
+            ("repo", Value::String(self.repo.clone())),
+        ])
+    }
+
+    /// list the repository's discussion categories
+    pub fn categories(&self) -> Future<Vec<DiscussionCategory>> {
+        paginate(
+            self.graphql.clone(),
+            CATEGORIES_QUERY.to_owned(),
+            self.repository_vars(),
+            "/repository/discussionCategories".to_owned(),
+        )
+    }
+
+    /// list all of the repository's discussions, most recently updated first
+    pub fn list(&self) -> Future<Vec<Discussion>> {
+        let query = format!(
+            r#"query($owner: String!, $repo: String!, $cursor: String) {{
+  repository(owner: $owner, name: $repo) {{
+    discussions(first: 100, after: $cursor, orderBy: {{field: UPDATED_AT, direction: DESC}}) {{
+      nodes {{ {} }}
+      pageInfo {{ hasNextPage endCursor }}
+    }}
+  }}
+}}"#,
+            DISCUSSION_FIELDS
+        );
+        paginate(
+            self.graphql.clone(),
+            query,
+            self.repository_vars(),
+            "/repository/discussions".to_owned(),
+        )
+    }
+
+    /// list all top level comments of a discussion
+    pub fn comments<D>(&self, discussion_id: D) -> Future<Vec<DiscussionComment>>
+    where
+        D: Into<String>,
+    {
+        let query = format!(
+            r#"query($discussion: ID!, $cursor: String) {{
+  node(id: $discussion) {{
+    ... on Discussion {{
+      comments(first: 100, after: $cursor) {{
+        nodes {{ {} }}
+        pageInfo {{ hasNextPage endCursor }}
+      }}
+    }}
+  }}
+}}"#,
+            COMMENT_FIELDS
+        );
+        paginate(
+            self.graphql.clone(),
+            query,
+            json_vars(&[("discussion", Value::String(discussion_id.into()))]),
+            "/node/comments".to_owned(),
+        )
+    }
+
+    /// start a discussion in one of the repository's categories
+    pub fn create(&self, options: &DiscussionOptions) -> Future<Discussion> {
+        let graphql = self.graphql.clone();
+        let options = options.clone();
+        let mutation = format!(
+            r#"mutation($repository: ID!, $category: ID!, $title: String!, $body: String!) {{
+  createDiscussion(
+    input: {{repositoryId: $repository, categoryId: $category, title: $title, body: $body}}
+  ) {{
+    discussion {{ {} }}
+  }}
+}}"#,
+            DISCUSSION_FIELDS
+        );
+        Box::new(
+            self.graphql
+                .query::<_, Value>(REPOSITORY_ID_QUERY, &self.repository_vars())
+                .and_then(|data| extract::<String>(&data, "/repository/id"))
+                .and_then(move |repository| {
+                    let variables = json_vars(&[
+                        ("repository", Value::String(repository)),
+                        ("category", Value::String(options.category_id)),
+                        ("title", Value::String(options.title)),
+                        ("body", Value::String(options.body)),
+                    ]);
+                    graphql
+                        .query::<_, Value>(&mutation, &variables)
+                        .and_then(|data| extract(&data, "/createDiscussion/discussion"))
+                }),
+        )
+    }
+
+    /// comment on a discussion
+    pub fn comment<D, B>(&self, discussion_id: D, body: B) -> Future<DiscussionComment>
+    where
+        D: Into<String>,
+        B: Into<String>,
+    {
+        self.add_comment(discussion_id.into(), None, body.into())
+    }
+
+    /// reply to a top level comment of a discussion
+    pub fn reply<D, I, B>(
+        &self,
+        discussion_id: D,
+        comment_id: I,
+        body: B,
+    ) -> Future<DiscussionComment>
+    where
+        D: Into<String>,
+        I: Into<String>,
+        B: Into<String>,
+    {
+        self.add_comment(discussion_id.into(), Some(comment_id.into()), body.into())
+    }
+
+    fn add_comment(
+        &self,
+        discussion_id: String,
+        reply_to: Option<String>,
+        body: String,
+    ) -> Future<DiscussionComment> {
+        let mutation = format!(
+            r#"mutation($discussion: ID!, $replyTo: ID, $body: String!) {{
+  addDiscussionComment(input: {{discussionId: $discussion, replyToId: $replyTo, body: $body}}) {{
+    comment {{ {} }}
+  }}
+}}"#,
+            COMMENT_FIELDS
+        );
+        let variables = json_vars(&[
+            ("discussion", Value::String(discussion_id)),
+            (
+                "replyTo",
+                reply_to.map(Value::String).unwrap_or(Value::Null),
+            ),
+            ("body", Value::String(body)),
+        ]);
+        Box::new(
+            self.graphql
+                .query::<_, Value>(&mutation, &variables)
+                .and_then(|data| extract(&data, "/addDiscussionComment/comment")),
+        )
+    }
+
+    /// mark a comment as the answer to its discussion. only discussions in
+    /// answerable categories, like Q&A, have answers
+    pub fn mark_answer<I>(&self, comment_id: I) -> Future<()>
+    where
+        I: Into<String>,
+    {
+        self.set_answer(
+            MARK_ANSWER_MUTATION,
+            "/markDiscussionCommentAsAnswer",
+            comment_id.into(),
+        )
+    }
+
+    /// unmark a comment as the answer to its discussion
+    pub fn unmark_answer<I>(&self, comment_id: I) -> Future<()>
+    where
+        I: Into<String>,
+    {
+        self.set_answer(
+            UNMARK_ANSWER_MUTATION,
+            "/unmarkDiscussionCommentAsAnswer",
+            comment_id.into(),
+        )
+    }
+
+    fn set_answer(&self, mutation: &str, pointer: &'static str, comment_id: String) -> Future<()> {
+        let variables = json_vars(&[("comment", Value::String(comment_id))]);
+        Box::new(
+            self.graphql
+                .query::<_, Value>(mutation, &variables)
+                .and_then(move |data| extract::<Value>(&data, pointer).map(|_| ())),
+        )
+    }
+}
+
+// representations
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscussionCategory {
+    /// the category's node id
+    pub id: String,
+    pub name: String,
+    pub slug: String,
+    pub description: Option<String>,
+    pub emoji: String,
+    /// whether discussions in the category can have an answer
+    pub is_answerable: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Discussion {
+    /// the discussion's node id
+    pub id: String,
+    pub number: u64,
+    pub title: String,
+    pub body: String,
+    pub url: String,
+    pub created_at: String,
+    /// `None` for discussions of deleted users
+    pub author: Option<Login>,
+    pub category: DiscussionCategorySummary,
+    pub answer: Option<NodeId>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscussionCategorySummary {
+    pub id: String,
+    pub name: String,
+    pub slug: String,
+    pub is_answerable: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscussionComment {
+    /// the comment's node id
+    pub id: String,
+    pub body: String,
+    pub url: String,
+    pub created_at: String,
+    pub is_answer: bool,
+    /// `None` for comments of deleted users
+    pub author: Option<Login>,
+    /// the comment replied to, for replies
+    pub reply_to: Option<NodeId>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Login {
+    pub login: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NodeId {
+    pub id: String,
+}
+
+/// options for starting a discussion
+#[derive(Clone, Debug)]
+pub struct DiscussionOptions {
+    /// the node id of the discussion's category
+    pub category_id: String,
+    pub title: String,
+    pub body: String,
+}
+
+impl DiscussionOptions {
+    pub fn new<C, T, B>(category_id: C, title: T, body: B) -> Self
+    where
+        C: Into<String>,
+        T: Into<String>,
+        B: Into<String>,
+    {
+        DiscussionOptions {
+            category_id: category_id.into(),
+            title: title.into(),
+            body: body.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn deserialize_discussion() {
+        let discussion: Discussion = serde_json::from_str(
+            r#"{
+              "id": "D_1",
+              "number": 7,
+              "title": "How do I paginate?",
+              "body": "...",
+              "url": "https://github.com/softprops/hubcaps/discussions/7",
+              "createdAt": "2021-05-11T19:19:47Z",
+              "author": {"login": "octocat"},
+              "category": {"id": "DIC_1", "name": "Q&A", "slug": "q-a", "isAnswerable": true},
+              "answer": {"id": "DC_1"}
+            }"#,
+        )
+        .unwrap();
+        assert!(discussion.category.is_answerable);
+        assert_eq!(discussion.answer.unwrap().id, "DC_1");
+    }
+}
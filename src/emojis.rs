@@ -0,0 +1,26 @@
+//! Emojis interface
+//!
+//! See the [github docs](https://docs.github.com/en/rest/emojis) for more information
+
+use std::collections::BTreeMap;
+
+use hyper::client::connect::Connect;
+
+use {Future, Github};
+
+/// Provides access to the emojis available on Github
+pub struct Emojis<C: Clone + Connect + 'static> {
+    github: Github<C>,
+}
+
+impl<C: Clone + Connect + 'static> Emojis<C> {
+    #[doc(hidden)]
+    pub fn new(github: Github<C>) -> Self {
+        Self { github }
+    }
+
+    /// list all emojis as a map of names, like `+1`, to image urls
+    pub fn list(&self) -> Future<BTreeMap<String, String>> {
+        self.github.get("/emojis")
+    }
+}
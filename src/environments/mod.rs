@@ -0,0 +1,157 @@
+//! Deployment environments interface
+//!
+//! Environments may gate deployments behind custom protection rules, which
+//! GitHub Apps enforce by approving or rejecting each deployment once their
+//! own checks pass. See `Actions::review_custom_gate`, and the
+//! [github docs](https://docs.github.com/en/rest/deployments/protection-rules)
+//! for more information.
+
+use futures::{future, Future as StdFuture};
+use hyper::client::connect::Connect;
+use percent_encoding::{utf8_percent_encode, PATH_SEGMENT_ENCODE_SET};
+use serde_json;
+
+use {Future, Github};
+
+/// Provides access to a deployment environment of a repository
+pub struct Environment<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    owner: String,
+    repo: String,
+    name: String,
+}
+
+impl<C: Clone + Connect + 'static> Environment<C> {
+    #[doc(hidden)]
+    pub fn new<O, R, N>(github: Github<C>, owner: O, repo: R, name: N) -> Self
+    where
+        O: Into<String>,
+        R: Into<String>,
+        N: Into<String>,
+    {
+        Environment {
+            github,
+            owner: owner.into(),
+            repo: repo.into(),
+            name: name.into(),
+        }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!(
+            "/repos/{}/{}/environments/{}{}",
+            self.owner,
+            self.repo,
+            utf8_percent_encode(&self.name, PATH_SEGMENT_ENCODE_SET),
+            more
+        )
+    }
+
+    /// list the custom protection rules enabled on the environment
+    pub fn protection_rules(&self) -> Future<Vec<ProtectionRule>> {
+        Box::new(
+            self.github
+                .get::<ProtectionRuleList>(&self.path("/deployment_protection_rules"))
+                .map(|list| list.custom_deployment_protection_rules),
+        )
+    }
+
+    /// get a custom protection rule enabled on the environment
+    pub fn protection_rule(&self, id: u64) -> Future<ProtectionRule> {
+        self.github
+            .get(&self.path(&format!("/deployment_protection_rules/{}", id)))
+    }
+
+    /// list the GitHub Apps installed on the repository which can enforce
+    /// custom protection rules on the environment
+    pub fn protection_rule_apps(&self) -> Future<Vec<ProtectionRuleApp>> {
+        Box::new(
+            self.github
+                .get::<ProtectionRuleAppList>(&self.path("/deployment_protection_rules/apps"))
+                .map(|list| list.available_custom_deployment_protection_rule_integrations),
+        )
+    }
+
+    /// enable the custom protection rule of the GitHub App with
+    /// `integration_id` on the environment
+    pub fn enable_protection_rule(&self, integration_id: u64) -> Future<ProtectionRule> {
+        let rule = ProtectionRuleOptions { integration_id };
+        self.github
+            .post(&self.path("/deployment_protection_rules"), json!(rule))
+    }
+
+    /// disable a custom protection rule on the environment
+    pub fn disable_protection_rule(&self, id: u64) -> Future<()> {
+        self.github
+            .delete(&self.path(&format!("/deployment_protection_rules/{}", id)))
+    }
+}
+
+// representations
+
+#[derive(Debug, Deserialize)]
+struct ProtectionRuleList {
+    custom_deployment_protection_rules: Vec<ProtectionRule>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProtectionRuleAppList {
+    available_custom_deployment_protection_rule_integrations: Vec<ProtectionRuleApp>,
+}
+
+#[derive(Debug, Serialize)]
+struct ProtectionRuleOptions {
+    integration_id: u64,
+}
+
+/// A custom protection rule, enforced by a GitHub App
+#[derive(Debug, Deserialize)]
+pub struct ProtectionRule {
+    pub id: u64,
+    pub node_id: String,
+    pub enabled: bool,
+    pub app: ProtectionRuleApp,
+}
+
+/// A GitHub App which enforces custom protection rules
+#[derive(Debug, Deserialize)]
+pub struct ProtectionRuleApp {
+    pub id: u64,
+    pub node_id: String,
+    pub slug: String,
+    pub integration_url: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_protection_rules() {
+        let list: ProtectionRuleList = serde_json::from_str(
+            r#"{
+              "total_count": 1,
+              "custom_deployment_protection_rules": [
+                {
+                  "id": 3,
+                  "node_id": "IEH37kRlcGxveW1lbnRTdGF0ddiv",
+                  "enabled": true,
+                  "app": {
+                    "id": 1,
+                    "node_id": "GHT58kRlcGxveW1lbnRTdTY!bbcy",
+                    "slug": "a-custom-app",
+                    "integration_url": "https://api.github.com/apps/a-custom-app"
+                  }
+                }
+              ]
+            }"#,
+        )
+        .unwrap();
+        let rule = &list.custom_deployment_protection_rules[0];
+        assert!(rule.enabled);
+        assert_eq!(rule.app.slug, "a-custom-app");
+    }
+}
@@ -10,6 +10,8 @@ use hyper::StatusCode;
 use jwt::errors::Error as JWTError;
 use serde_json::error::Error as SerdeError;
 
+use scopes::Scope;
+
 error_chain! {
     errors {
         #[doc = "Client side error returned for faulty requests"]
@@ -26,6 +28,54 @@ error_chain! {
         } {
             display("Rate limit exhausted. Will reset in {} seconds", reset.as_secs())
         }
+        #[doc = "Error kind returned when a request would overdraw the client's `RateBudget` partition of a rate limit resource"]
+        BudgetExhausted {
+            resource: String,
+            reset: Duration
+        } {
+            display("Rate budget for {} exhausted. Will reset in {} seconds", resource, reset.as_secs())
+        }
+        #[doc = "Error kind returned when a token must be authorized for an organization enforcing SAML single sign-on. Direct users to the authorization url, when provided, to authorize it"]
+        SsoRequired(url: Option<String>) {
+            display(
+                "Token must be authorized for SAML single sign-on{}",
+                url.as_ref().map(|url| format!(" at {}", url)).unwrap_or_default()
+            )
+        }
+        #[doc = "Error kind returned when attempting to write to an archived repository, which is read-only until unarchived"]
+        ArchivedRepository(message: String) {
+            display("{}", message)
+        }
+        #[doc = "Error kind returned when a token was not granted the OAuth scopes an application requires"]
+        MissingScopes(scopes: Vec<Scope>) {
+            display(
+                "Token is missing required OAuth scopes: {}",
+                scopes.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(", ")
+            )
+        }
+        #[doc = "Error kind returned when a request does not complete within the client's configured timeout"]
+        Timeout(duration: Duration) {
+            display("Request timed out after {:?}", duration)
+        }
+        #[doc = "Error kind returned when a GraphQL query or mutation responds with errors"]
+        GraphQL(errors: Vec<GraphQLError>) {
+            display(
+                "GraphQL errors: {}",
+                errors.iter().map(|e| e.message.as_str()).collect::<Vec<_>>().join(", ")
+            )
+        }
+        #[doc = "Error kind returned when a response body is larger than the client's `ResponseLimits` allow"]
+        ResponseTooLarge(max_body_size: usize) {
+            display("Response body exceeds {} bytes", max_body_size)
+        }
+        #[doc = "Error kind returned when a response's json is nested deeper than the client's `ResponseLimits` allow"]
+        ResponseTooDeep(max_depth: usize) {
+            display("Response json is nested deeper than {} levels", max_depth)
+        }
+        #[doc = "Error kind returned when a webhook payload's signature does not match its body"]
+        InvalidSignature {
+            display("Webhook signature does not match payload")
+        }
     }
     foreign_links {
         Codec(SerdeError);
@@ -54,6 +104,14 @@ pub struct ClientError {
     pub errors: Option<Vec<FieldErr>>,
 }
 
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct GraphQLError {
+    pub message: String,
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+    pub path: Option<Vec<::serde_json::Value>>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::{ClientError, FieldErr};
@@ -0,0 +1,154 @@
+//! Events interface
+//!
+//! Events are the activity of public repositories, organizations and users.
+//! See the [github docs](https://docs.github.com/en/rest/activity/events)
+//! for more information
+
+use std::collections::{HashSet, VecDeque};
+
+use futures::{stream, Stream as StdStream};
+use hyper::client::connect::Connect;
+use serde_json;
+
+use ids::RepoId;
+use poll;
+use {unfold, Future, Github, Stream};
+
+/// the number of event ids remembered to deduplicate polls. the events api
+/// never returns more than 300 events
+const SEEN_CAPACITY: usize = 1000;
+
+fn identity<T>(x: T) -> T {
+    x
+}
+
+/// Provides access to the events of a repository, organization, user or
+/// all of Github
+pub struct Events<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    path: String,
+}
+
+impl<C: Clone + Connect + 'static> Events<C> {
+    #[doc(hidden)]
+    pub fn new<P>(github: Github<C>, path: P) -> Self
+    where
+        P: Into<String>,
+    {
+        Events {
+            github,
+            path: path.into(),
+        }
+    }
+
+    /// list the most recent events
+    pub fn list(&self) -> Future<Vec<Event>> {
+        self.github.get(&self.path)
+    }
+
+    /// provides a stream over all pages of events
+    pub fn iter(&self) -> Stream<Event> {
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&self.path),
+            identity,
+        )
+    }
+
+    /// poll for events, yielding each event once as it happens.
+    ///
+    /// Polls are conditional requests, which Github answers without counting
+    /// them against the rate limit when nothing changed, and are spaced out by
+    /// the interval Github asks for in its `X-Poll-Interval` header. Events
+    /// already yielded are skipped when they appear in later polls
+    pub fn poll(&self) -> Stream<Event> {
+        let mut seen = Seen::default();
+        Box::new(
+            poll::pages(self.github.clone(), self.path.clone())
+                .map(|mut events: Vec<Event>| {
+                    // events are listed newest first
+                    events.reverse();
+                    stream::iter_ok(events)
+                })
+                .flatten()
+                .filter(move |event| seen.insert(&event.id)),
+        )
+    }
+}
+
+/// a bounded set of the most recently seen event ids
+#[derive(Default)]
+struct Seen {
+    ids: HashSet<String>,
+    order: VecDeque<String>,
+}
+
+impl Seen {
+    /// returns true if `id` was not seen before
+    fn insert(&mut self, id: &str) -> bool {
+        if self.ids.contains(id) {
+            return false;
+        }
+        if self.order.len() == SEEN_CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.ids.remove(&oldest);
+            }
+        }
+        self.ids.insert(id.to_owned());
+        self.order.push_back(id.to_owned());
+        true
+    }
+}
+
+// representations
+
+#[derive(Debug, Deserialize)]
+pub struct Event {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub actor: Actor,
+    pub repo: EventRepo,
+    pub org: Option<Actor>,
+    pub payload: serde_json::Value,
+    pub public: bool,
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Actor {
+    pub id: u64,
+    pub login: String,
+    pub display_login: Option<String>,
+    pub gravatar_id: Option<String>,
+    pub url: String,
+    pub avatar_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EventRepo {
+    pub id: RepoId,
+    pub name: String,
+    pub url: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seen_is_bounded() {
+        let mut seen = Seen::default();
+        assert!(seen.insert("0"));
+        assert!(!seen.insert("0"));
+        for id in 1..SEEN_CAPACITY {
+            assert!(seen.insert(&id.to_string()));
+        }
+        assert!(seen.insert("overflow"));
+        assert!(seen.insert("0"));
+        assert_eq!(seen.ids.len(), SEEN_CAPACITY);
+    }
+}
@@ -0,0 +1,44 @@
+//! Gitignore templates interface
+//!
+//! See the [github docs](https://docs.github.com/en/rest/gitignore) for more information
+
+use futures::Future as StdFuture;
+use hyper::client::connect::Connect;
+
+use {Future, Github};
+
+/// Provides access to the `.gitignore` templates used when creating repositories
+pub struct GitignoreTemplates<C: Clone + Connect + 'static> {
+    github: Github<C>,
+}
+
+impl<C: Clone + Connect + 'static> GitignoreTemplates<C> {
+    #[doc(hidden)]
+    pub fn new(github: Github<C>) -> Self {
+        Self { github }
+    }
+
+    /// list the names of all available templates
+    pub fn list(&self) -> Future<Vec<String>> {
+        self.github.get("/gitignore/templates")
+    }
+
+    /// get a template by name, like `Rust`
+    pub fn get(&self, name: &str) -> Future<GitignoreTemplate> {
+        self.github.get(&format!("/gitignore/templates/{}", name))
+    }
+
+    /// get the raw contents of a template by name
+    pub fn raw(&self, name: &str) -> Future<String> {
+        Box::new(self.get(name).map(|template| template.source))
+    }
+}
+
+// representations
+
+#[derive(Debug, Deserialize)]
+pub struct GitignoreTemplate {
+    pub name: String,
+    /// the contents of the template
+    pub source: String,
+}
@@ -0,0 +1,143 @@
+//! GraphQL interface
+//!
+//! Some of Github's functionality, like projects (beta), is only exposed through
+//! its [GraphQL api](https://docs.github.com/en/graphql)
+
+use futures::{future, Future as StdFuture};
+use hyper::client::connect::Connect;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{self, Value};
+
+use errors::GraphQLError;
+use {Error, ErrorKind, Future, Github};
+
+#[derive(Serialize)]
+struct Request<'a, V: 'a> {
+    query: &'a str,
+    variables: &'a V,
+}
+
+#[derive(Deserialize)]
+struct Response<D> {
+    data: Option<D>,
+    errors: Option<Vec<GraphQLError>>,
+}
+
+/// Provides access to Github's GraphQL api
+#[derive(Clone)]
+pub struct GraphQL<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+}
+
+impl<C: Clone + Connect + 'static> GraphQL<C> {
+    #[doc(hidden)]
+    pub fn new(github: Github<C>) -> Self {
+        Self { github }
+    }
+
+    /// executes a query or mutation, deserializing its `data` into a type of your choosing.
+    /// Responses which include `errors` will result in an `ErrorKind::GraphQL` error
+    ///
+    /// ```no_run
+    /// # extern crate hubcaps;
+    /// # extern crate serde_json;
+    /// # fn main() {
+    /// # let github = hubcaps::Github::new("agent", None);
+    /// let viewer = github.graphql().query::<_, serde_json::Value>(
+    ///     "query { viewer { login } }",
+    ///     &serde_json::json!({}),
+    /// );
+    /// # }
+    /// ```
+    pub fn query<V, D>(&self, query: &str, variables: &V) -> Future<D>
+    where
+        V: Serialize,
+        D: DeserializeOwned + 'static + Send,
+    {
+        let request = Request { query, variables };
+        Box::new(
+            self.github
+                .post_graphql::<Response<D>>(json!(request))
+                .and_then(|response| {
+                    let errors = response.errors.unwrap_or_default();
+                    if !errors.is_empty() {
+                        return Err(ErrorKind::GraphQL(errors).into());
+                    }
+                    response
+                        .data
+                        .ok_or_else(|| ErrorKind::GraphQL(errors).into())
+                }),
+        )
+    }
+}
+
+pub(crate) fn json_vars(vars: &[(&str, Value)]) -> Value {
+    Value::Object(
+        vars.iter()
+            .map(|&(ref k, ref v)| (k.to_string(), v.clone()))
+            .collect(),
+    )
+}
+
+pub(crate) fn extract<D>(data: &Value, pointer: &str) -> Result<D, Error>
+where
+    D: DeserializeOwned,
+{
+    let value = data
+        .pointer(pointer)
+        .cloned()
+        .ok_or_else(|| Error::from(format!("graphql response missing {}", pointer)))?;
+    Ok(serde_json::from_value(value)?)
+}
+
+/// fetches every page of a connection found at `pointer` in a query's response.
+/// queries are expected to accept a `$cursor` variable
+pub(crate) fn paginate<C, D>(
+    graphql: GraphQL<C>,
+    query: String,
+    variables: Value,
+    pointer: String,
+) -> Future<Vec<D>>
+where
+    C: Clone + Connect + 'static,
+    D: DeserializeOwned + 'static + Send,
+{
+    Box::new(future::loop_fn(
+        (None, Vec::new()),
+        move |(cursor, mut items): (Option<String>, Vec<D>)| {
+            let mut variables = variables.clone();
+            variables["cursor"] = cursor.map(Value::String).unwrap_or(Value::Null);
+            let pointer = pointer.clone();
+            graphql
+                .query::<_, Value>(&query, &variables)
+                .and_then(move |data| {
+                    let page = extract::<Connection<D>>(&data, &pointer)?;
+                    items.extend(page.nodes);
+                    Ok(match page.page_info.end_cursor {
+                        Some(cursor) if page.page_info.has_next_page => {
+                            future::Loop::Continue((Some(cursor), items))
+                        }
+                        _ => future::Loop::Break(items),
+                    })
+                })
+        },
+    ))
+}
+
+#[derive(Debug, Deserialize)]
+struct Connection<D> {
+    nodes: Vec<D>,
+    #[serde(rename = "pageInfo")]
+    page_info: PageInfo,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
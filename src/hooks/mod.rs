@@ -3,14 +3,172 @@
 //! See the [github docs](https://developer.github.com/v3/repos/hooks/) for more information
 
 use futures::future;
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
 use serde_json;
 
-use {Future, Github};
+use {unfold, Future, Github, Stream};
 
 use hyper::client::connect::Connect;
 use std::collections::BTreeMap;
 use std::fmt;
 
+macro_rules! webhook_events {
+    ($($(#[$attr:meta])* $variant:ident => $name:tt,)*) => {
+        /// An event a hook can be subscribed to. Events this crate does not
+        /// yet know about are represented by `Other`
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum WebhookEvent {
+            $($(#[$attr])* $variant,)*
+            Other(String),
+        }
+
+        impl WebhookEvent {
+            /// the name of this event as it appears in the `X-GitHub-Event` header
+            pub fn name(&self) -> &str {
+                match *self {
+                    $(WebhookEvent::$variant => $name,)*
+                    WebhookEvent::Other(ref name) => name,
+                }
+            }
+        }
+
+        impl<'a> From<&'a str> for WebhookEvent {
+            fn from(name: &'a str) -> WebhookEvent {
+                match name {
+                    $($name => WebhookEvent::$variant,)*
+                    other => WebhookEvent::Other(other.to_owned()),
+                }
+            }
+        }
+    };
+}
+
+webhook_events! {
+    /// every event
+    All => "*",
+    BranchProtectionRule => "branch_protection_rule",
+    CheckRun => "check_run",
+    CheckSuite => "check_suite",
+    CodeScanningAlert => "code_scanning_alert",
+    CommitComment => "commit_comment",
+    Create => "create",
+    Delete => "delete",
+    Deployment => "deployment",
+    DeploymentStatus => "deployment_status",
+    Discussion => "discussion",
+    DiscussionComment => "discussion_comment",
+    Fork => "fork",
+    Gollum => "gollum",
+    IssueComment => "issue_comment",
+    Issues => "issues",
+    Label => "label",
+    Member => "member",
+    Meta => "meta",
+    Milestone => "milestone",
+    Package => "package",
+    PageBuild => "page_build",
+    Ping => "ping",
+    Public => "public",
+    PullRequest => "pull_request",
+    PullRequestReview => "pull_request_review",
+    PullRequestReviewComment => "pull_request_review_comment",
+    PullRequestReviewThread => "pull_request_review_thread",
+    Push => "push",
+    Release => "release",
+    Repository => "repository",
+    RepositoryDispatch => "repository_dispatch",
+    SecretScanningAlert => "secret_scanning_alert",
+    /// a repository was starred
+    Star => "star",
+    Status => "status",
+    /// a repository was starred. despite its name, not related to watching
+    Watch => "watch",
+    WorkflowDispatch => "workflow_dispatch",
+    WorkflowJob => "workflow_job",
+    WorkflowRun => "workflow_run",
+}
+
+impl From<String> for WebhookEvent {
+    fn from(name: String) -> WebhookEvent {
+        WebhookEvent::from(name.as_str())
+    }
+}
+
+impl fmt::Display for WebhookEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.name().fmt(f)
+    }
+}
+
+impl Serialize for WebhookEvent {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for WebhookEvent {
+    fn deserialize<D>(deserializer: D) -> Result<WebhookEvent, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer).map(WebhookEvent::from)
+    }
+}
+
+/// The configuration of a hook
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HookConfig {
+    /// the url deliveries are sent to. required for web hooks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<WebHookContentType>,
+    /// the secret deliveries are signed with. Github masks it in responses
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    /// when true, the certificate of the url is not verified. not recommended
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_insecure_ssl",
+        deserialize_with = "deserialize_insecure_ssl"
+    )]
+    pub insecure_ssl: Option<bool>,
+    /// the settings of service hooks
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+/// Github represents `insecure_ssl` as a "0" or "1" string
+fn serialize_insecure_ssl<S>(insecure_ssl: &Option<bool>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match *insecure_ssl {
+        Some(true) => serializer.serialize_str("1"),
+        _ => serializer.serialize_str("0"),
+    }
+}
+
+fn deserialize_insecure_ssl<'de, D>(deserializer: D) -> Result<Option<bool>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<serde_json::Value>::deserialize(deserializer)? {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(serde_json::Value::Bool(insecure)) => Ok(Some(insecure)),
+        Some(serde_json::Value::Number(ref n)) if n.as_u64() == Some(0) => Ok(Some(false)),
+        Some(serde_json::Value::Number(ref n)) if n.as_u64() == Some(1) => Ok(Some(true)),
+        Some(serde_json::Value::String(ref s)) if s == "0" => Ok(Some(false)),
+        Some(serde_json::Value::String(ref s)) if s == "1" => Ok(Some(true)),
+        Some(other) => Err(de::Error::custom(format!("invalid insecure_ssl {}", other))),
+    }
+}
+
 /// Content-Type web hooks will receive
 /// deliveries in
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -94,6 +252,79 @@ impl<C: Clone + Connect + 'static> Hooks<C> {
         self.github
             .delete(&format!("/repos/{}/{}/hooks/{}", self.owner, self.repo, id))
     }
+
+    /// triggers a ping event to be sent to a hook
+    ///
+    /// See the [github docs](https://docs.github.com/en/rest/webhooks/repos#ping-a-repository-webhook)
+    /// for more information.
+    pub fn ping(&self, id: u64) -> Future<()> {
+        self.github.post_no_response(
+            &format!("/repos/{}/{}/hooks/{}/pings", self.owner, self.repo, id),
+            Vec::new(),
+        )
+    }
+
+    /// triggers a push event for the latest push to the repository to be
+    /// sent to a hook. hooks not subscribed to push events will not receive a delivery
+    ///
+    /// See the [github docs](https://docs.github.com/en/rest/webhooks/repos#test-the-push-repository-webhook)
+    /// for more information.
+    pub fn test(&self, id: u64) -> Future<()> {
+        self.github.post_no_response(
+            &format!("/repos/{}/{}/hooks/{}/tests", self.owner, self.repo, id),
+            Vec::new(),
+        )
+    }
+
+    /// lists the most recent deliveries of a hook
+    ///
+    /// See the [github docs](https://docs.github.com/en/rest/webhooks/repo-deliveries#list-deliveries-for-a-repository-webhook)
+    /// for more information.
+    pub fn deliveries(&self, id: u64) -> Future<Vec<Delivery>> {
+        self.github.get(&format!(
+            "/repos/{}/{}/hooks/{}/deliveries",
+            self.owner, self.repo, id
+        ))
+    }
+
+    /// provides a stream over all pages of a hook's deliveries
+    pub fn iter_deliveries(&self, id: u64) -> Stream<Delivery> {
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&format!(
+                "/repos/{}/{}/hooks/{}/deliveries",
+                self.owner, self.repo, id
+            )),
+            identity,
+        )
+    }
+
+    /// gets a single delivery of a hook, including the request
+    /// and response of the delivery attempt
+    pub fn delivery(&self, id: u64, delivery_id: u64) -> Future<DeliveryDetail> {
+        self.github.get(&format!(
+            "/repos/{}/{}/hooks/{}/deliveries/{}",
+            self.owner, self.repo, id, delivery_id
+        ))
+    }
+
+    /// requests a new attempt of a delivery of a hook
+    ///
+    /// See the [github docs](https://docs.github.com/en/rest/webhooks/repo-deliveries#redeliver-a-delivery-for-a-repository-webhook)
+    /// for more information.
+    pub fn redeliver(&self, id: u64, delivery_id: u64) -> Future<()> {
+        self.github.post_no_response(
+            &format!(
+                "/repos/{}/{}/hooks/{}/deliveries/{}/attempts",
+                self.owner, self.repo, id, delivery_id
+            ),
+            Vec::new(),
+        )
+    }
+}
+
+fn identity<T>(x: T) -> T {
+    x
 }
 
 // representations
@@ -104,8 +335,8 @@ impl<C: Clone + Connect + 'static> Hooks<C> {
 #[derive(Debug, Default, Serialize)]
 pub struct HookCreateOptions {
     name: String,
-    config: BTreeMap<String, ::serde_json::Value>,
-    events: Vec<String>,
+    config: HookConfig,
+    events: Vec<WebhookEvent>,
     active: bool,
 }
 
@@ -151,24 +382,31 @@ impl HookCreateOptionsBuilder {
     /// the [Github api docs](https://developer.github.com/webhooks/#events)
     pub fn events<E>(&mut self, events: Vec<E>) -> &mut Self
     where
-        E: Into<String>,
+        E: Into<WebhookEvent>,
     {
         self.0.events = events.into_iter().map(|e| e.into()).collect::<Vec<_>>();
         self
     }
 
+    /// replaces the hook's configuration
+    pub fn config(&mut self, config: HookConfig) -> &mut Self {
+        self.0.config = config;
+        self
+    }
+
     /// web hooks must have an associated url
     pub fn url<U>(&mut self, url: U) -> &mut Self
     where
         U: Into<String>,
     {
-        self.config_entry("url".to_owned(), ::serde_json::Value::String(url.into()))
+        self.0.config.url = Some(url.into());
+        self
     }
 
     /// web hooks can optionally specify a content_type of "form" or "json"
     /// which indicates the type of payload they will expect to receive
     pub fn content_type(&mut self, content_type: WebHookContentType) -> &mut Self {
-        self.config_str_entry("content_type", content_type.to_string());
+        self.0.config.content_type = Some(content_type);
         self
     }
 
@@ -178,10 +416,17 @@ impl HookCreateOptionsBuilder {
     where
         S: Into<String>,
     {
-        self.config_str_entry("secret", sec);
+        self.0.config.secret = Some(sec.into());
         self
     }
 
+    /// skip verification of the url's certificate. not recommended
+    pub fn insecure_ssl(&mut self, insecure_ssl: bool) -> &mut Self {
+        self.0.config.insecure_ssl = Some(insecure_ssl);
+        self
+    }
+
+    /// sets a configuration entry of a service hook
     pub fn config_str_entry<K, V>(&mut self, k: K, v: V) -> &mut Self
     where
         K: Into<String>,
@@ -191,11 +436,12 @@ impl HookCreateOptionsBuilder {
         self
     }
 
+    /// sets a configuration entry of a service hook
     pub fn config_entry<N>(&mut self, name: N, value: ::serde_json::Value) -> &mut Self
     where
         N: Into<String>,
     {
-        self.0.config.insert(name.into(), value);
+        self.0.config.other.insert(name.into(), value);
         self
     }
 
@@ -214,10 +460,13 @@ impl HookCreateOptionsBuilder {
 /// for githubs official documentation
 #[derive(Debug, Default, Serialize)]
 pub struct HookEditOptions {
-    config: BTreeMap<String, ::serde_json::Value>,
-    events: Vec<String>,
-    add_events: Vec<String>,
-    remove_events: Vec<String>,
+    config: HookConfig,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    events: Vec<WebhookEvent>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    add_events: Vec<WebhookEvent>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    remove_events: Vec<WebhookEvent>,
     active: bool,
 }
 
@@ -242,24 +491,49 @@ impl HookEditOptionsBuilder {
     /// the [Github api docs](https://developer.github.com/webhooks/#events)
     pub fn events<E>(&mut self, events: Vec<E>) -> &mut Self
     where
-        E: Into<String>,
+        E: Into<WebhookEvent>,
     {
         self.0.events = events.into_iter().map(|e| e.into()).collect::<Vec<_>>();
         self
     }
 
+    /// subscribes the hook to events in addition to those it already receives
+    pub fn add_events<E>(&mut self, events: Vec<E>) -> &mut Self
+    where
+        E: Into<WebhookEvent>,
+    {
+        self.0.add_events = events.into_iter().map(|e| e.into()).collect::<Vec<_>>();
+        self
+    }
+
+    /// unsubscribes the hook from events
+    pub fn remove_events<E>(&mut self, events: Vec<E>) -> &mut Self
+    where
+        E: Into<WebhookEvent>,
+    {
+        self.0.remove_events = events.into_iter().map(|e| e.into()).collect::<Vec<_>>();
+        self
+    }
+
+    /// replaces the hook's configuration
+    pub fn config(&mut self, config: HookConfig) -> &mut Self {
+        self.0.config = config;
+        self
+    }
+
     /// web hooks must have an associated url
     pub fn url<U>(&mut self, url: U) -> &mut Self
     where
         U: Into<String>,
     {
-        self.config_entry("url".to_owned(), ::serde_json::Value::String(url.into()))
+        self.0.config.url = Some(url.into());
+        self
     }
 
     /// web hooks can optionally specify a content_type of "form" or "json"
     /// which indicates the type of payload they will expect to receive
     pub fn content_type(&mut self, content_type: WebHookContentType) -> &mut Self {
-        self.config_str_entry("content_type", content_type.to_string());
+        self.0.config.content_type = Some(content_type);
         self
     }
 
@@ -269,10 +543,17 @@ impl HookEditOptionsBuilder {
     where
         S: Into<String>,
     {
-        self.config_str_entry("secret", sec);
+        self.0.config.secret = Some(sec.into());
         self
     }
 
+    /// skip verification of the url's certificate. not recommended
+    pub fn insecure_ssl(&mut self, insecure_ssl: bool) -> &mut Self {
+        self.0.config.insecure_ssl = Some(insecure_ssl);
+        self
+    }
+
+    /// sets a configuration entry of a service hook
     pub fn config_str_entry<K, V>(&mut self, k: K, v: V) -> &mut Self
     where
         K: Into<String>,
@@ -282,11 +563,12 @@ impl HookEditOptionsBuilder {
         self
     }
 
+    /// sets a configuration entry of a service hook
     pub fn config_entry<N>(&mut self, name: N, value: ::serde_json::Value) -> &mut Self
     where
         N: Into<String>,
     {
-        self.0.config.insert(name.into(), value);
+        self.0.config.other.insert(name.into(), value);
         self
     }
 
@@ -308,37 +590,116 @@ pub struct Hook {
     pub test_url: String,
     pub ping_url: String,
     pub name: String,
-    pub events: Vec<String>,
-    pub config: ::serde_json::Value,
+    pub events: Vec<WebhookEvent>,
+    pub config: HookConfig,
     pub created_at: String,
     pub updated_at: String,
     pub active: bool,
 }
 
 impl Hook {
-    pub fn config_value(&self, name: &str) -> Option<&::serde_json::Value> {
-        self.config.pointer(&format!("/{}", name))
+    pub fn url(&self) -> Option<String> {
+        self.config.url.clone()
     }
 
-    pub fn config_string(&self, name: &str) -> Option<String> {
-        self.config_value(name).and_then(|value| match *value {
-            ::serde_json::Value::String(ref val) => Some(val.clone()),
-            _ => None,
-        })
+    pub fn content_type(&self) -> Option<WebHookContentType> {
+        self.config.content_type
     }
+}
 
-    pub fn url(&self) -> Option<String> {
-        self.config_string("url")
-    }
+/// a summary of a single attempt to deliver an event to a hook
+#[derive(Debug, Deserialize)]
+pub struct Delivery {
+    pub id: u64,
+    pub guid: String,
+    pub delivered_at: String,
+    pub redelivery: bool,
+    /// time spent delivering, in seconds
+    pub duration: f64,
+    pub status: String,
+    pub status_code: u16,
+    pub event: String,
+    pub action: Option<String>,
+    pub installation_id: Option<u64>,
+    pub repository_id: Option<u64>,
+}
 
-    pub fn content_type(&self) -> Option<String> {
-        self.config_string("content_type")
-    }
+/// a single delivery attempt including the request sent to the hook
+/// and the response it returned
+#[derive(Debug, Deserialize)]
+pub struct DeliveryDetail {
+    pub id: u64,
+    pub guid: String,
+    pub delivered_at: String,
+    pub redelivery: bool,
+    pub duration: f64,
+    pub status: String,
+    pub status_code: u16,
+    pub event: String,
+    pub action: Option<String>,
+    pub installation_id: Option<u64>,
+    pub repository_id: Option<u64>,
+    pub url: Option<String>,
+    pub request: DeliveryRequest,
+    pub response: DeliveryResponse,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeliveryRequest {
+    pub headers: Option<BTreeMap<String, String>>,
+    pub payload: Option<::serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeliveryResponse {
+    pub headers: Option<BTreeMap<String, String>>,
+    pub payload: Option<String>,
 }
 
 #[cfg(test)]
 mod tests {
-    use super::WebHookContentType;
+    use super::*;
+
+    #[test]
+    fn webhook_event_names() {
+        for name in &["push", "pull_request", "*", "merge_group"] {
+            assert_eq!(WebhookEvent::from(*name).name(), *name)
+        }
+        assert_eq!(WebhookEvent::from("push"), WebhookEvent::Push);
+        assert_eq!(
+            WebhookEvent::from("merge_group"),
+            WebhookEvent::Other("merge_group".into())
+        );
+    }
+
+    #[test]
+    fn hook_create_reqs() {
+        let options = HookCreateOptions::web()
+            .url("https://example.com/hook")
+            .content_type(WebHookContentType::Json)
+            .insecure_ssl(false)
+            .events(vec![WebhookEvent::Push, WebhookEvent::PullRequest])
+            .build();
+        assert_eq!(
+            serde_json::to_string(&options).unwrap(),
+            concat!(
+                r#"{"name":"web","config":{"url":"https://example.com/hook","content_type":"json","insecure_ssl":"0"},"#,
+                r#""events":["push","pull_request"],"active":true}"#
+            )
+        );
+    }
+
+    #[test]
+    fn hook_config_insecure_ssl() {
+        for (json, expect) in &[
+            (r#"{"insecure_ssl":"1"}"#, Some(true)),
+            (r#"{"insecure_ssl":0}"#, Some(false)),
+            (r#"{}"#, None),
+        ] {
+            let config: HookConfig = serde_json::from_str(json).unwrap();
+            assert_eq!(config.insecure_ssl, *expect)
+        }
+    }
 
     #[test]
     fn webhook_content_type_display() {
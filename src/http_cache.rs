@@ -6,6 +6,8 @@ use std::fmt::Debug;
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
 
 use dirs;
 use hyper::Uri;
@@ -28,7 +30,7 @@ impl HttpCache {
     pub fn in_home_dir() -> BoxedHttpCache {
         let mut dir = dirs::home_dir().expect("Expected a home dir");
         dir.push(".hubcaps/cache");
-        Box::new(FileBasedCache { root: dir })
+        Box::new(FileBasedCache::new(dir))
     }
 }
 
@@ -53,9 +55,114 @@ impl HttpCache for NoCache {
     }
 }
 
+/// Caches responses in a directory, so they outlive the process. Entries may
+/// expire after a time to live, and the least recently cached ones are
+/// evicted once the cache grows beyond a size limit
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # use hubcaps::{BoxedHttpCache, FileBasedCache};
+/// let cache: BoxedHttpCache = Box::new(
+///     FileBasedCache::new("/tmp/hubcaps")
+///         .ttl(Duration::from_secs(24 * 60 * 60))
+///         .max_size(50 * 1024 * 1024),
+/// );
+/// ```
 #[derive(Clone, Debug)]
 pub struct FileBasedCache {
     root: PathBuf,
+    ttl: Option<Duration>,
+    max_size: Option<u64>,
+    /// the size of the cached bodies and etags, counted as entries are
+    /// written since it was last measured. shared by clones of the cache
+    size: Arc<Mutex<Option<u64>>>,
+}
+
+impl FileBasedCache {
+    /// cache responses in `root`, without expiring or evicting them
+    pub fn new<P>(root: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        FileBasedCache {
+            root: root.into(),
+            ttl: None,
+            max_size: None,
+            size: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// expire entries cached longer than `ttl` ago
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// evict the least recently cached entries once the cached bodies and
+    /// etags exceed `bytes`, until they fit within 90% of it
+    pub fn max_size(mut self, bytes: u64) -> Self {
+        self.max_size = Some(bytes);
+        self
+    }
+
+    /// the path of the entry's body, or an error when it's not cached or
+    /// has expired. expired entries are removed
+    fn fresh(&self, uri: &str) -> Result<PathBuf> {
+        let path = cache_path(&self.root, uri, "json");
+        if let Some(ttl) = self.ttl {
+            let age = fs::metadata(&path)?
+                .modified()?
+                .elapsed()
+                .unwrap_or_default();
+            if age > ttl {
+                remove_entry(&path);
+                return Err(Error::from(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "Cached entry expired",
+                )));
+            }
+        }
+        Ok(path)
+    }
+
+    /// count a newly written entry towards the size of the cache. the cache
+    /// is only walked when it's first written to and once it may have
+    /// outgrown its size limit
+    fn grow(&self, written: u64, replaced: u64) -> Result<()> {
+        let max_size = match self.max_size {
+            Some(max_size) => max_size,
+            None => return Ok(()),
+        };
+        let mut size = self.size.lock().unwrap();
+        *size = match size.map(|size| (size + written).saturating_sub(replaced)) {
+            Some(grown) if grown <= max_size => Some(grown),
+            _ => Some(self.evict(max_size)?),
+        };
+        Ok(())
+    }
+
+    /// measure the cache and, if it exceeds `max_size`, remove the least
+    /// recently cached entries until it fits within 90% of it, so that
+    /// evictions aren't repeated on every write. returns the size of the
+    /// cache
+    fn evict(&self, max_size: u64) -> Result<u64> {
+        let mut entries = Vec::new();
+        collect_entries(&self.root, &mut entries)?;
+        let mut size: u64 = entries.iter().map(|entry| entry.size).sum();
+        if size <= max_size {
+            return Ok(size);
+        }
+        let target = max_size - max_size / 10;
+        entries.sort_by_key(|entry| entry.modified);
+        for entry in entries {
+            if size <= target {
+                break;
+            }
+            remove_entry(&entry.path);
+            size -= entry.size;
+        }
+        Ok(size)
+    }
 }
 
 impl HttpCache for FileBasedCache {
@@ -64,19 +171,63 @@ impl HttpCache for FileBasedCache {
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)?;
         }
+        let replaced = entry_size(&path);
         fs::write(&path, body)?;
         path.set_extension("etag");
         fs::write(&path, etag)?;
-        Ok(())
+        self.grow((body.len() + etag.len()) as u64, replaced)
     }
 
     fn lookup_etag(&self, uri: &str) -> Result<String> {
-        read_to_string(cache_path(&self.root, uri, "etag"))
+        // an etag is only useful while its body is still cached
+        let mut path = self.fresh(uri)?;
+        path.set_extension("etag");
+        read_to_string(path)
     }
 
     fn lookup_body(&self, uri: &str) -> Result<String> {
-        read_to_string(cache_path(&self.root, uri, "json"))
+        read_to_string(self.fresh(uri)?)
+    }
+}
+
+/// A cached body, along with the size of it and its etag
+struct Entry {
+    path: PathBuf,
+    size: u64,
+    modified: SystemTime,
+}
+
+fn collect_entries(dir: &Path, entries: &mut Vec<Entry>) -> Result<()> {
+    for dir_entry in fs::read_dir(dir)? {
+        let path = dir_entry?.path();
+        if path.is_dir() {
+            collect_entries(&path, entries)?;
+        } else if path.extension() == Some(OsStr::new("json")) {
+            entries.push(Entry {
+                size: entry_size(&path),
+                modified: fs::metadata(&path)?.modified()?,
+                path,
+            });
+        }
     }
+    Ok(())
+}
+
+/// the size of a cached body along with its etag, if any
+fn entry_size(body: &Path) -> u64 {
+    let size = |path: PathBuf| {
+        fs::metadata(path)
+            .map(|file| file.len())
+            .unwrap_or_default()
+    };
+    size(body.to_path_buf()) + size(body.with_extension("etag"))
+}
+
+/// removes a cached body along with its etag. failing to is harmless, the
+/// entry will be overwritten or removed again later
+fn remove_entry(body: &Path) {
+    let _ = fs::remove_file(body.with_extension("etag"));
+    let _ = fs::remove_file(body);
 }
 
 ///       cache_path("https://api.github.com/users/dwijnand/repos", "json") ==>
@@ -124,3 +275,47 @@ where
         Box::new(self.clone())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn cache_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("hubcaps-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn expires_entries() {
+        let dir = cache_dir("ttl");
+        let uri = "https://api.github.com/users/octocat";
+        let cache = FileBasedCache::new(&dir);
+        cache.cache_body_and_etag(uri, b"{}", b"\"abc\"").unwrap();
+        assert_eq!(cache.lookup_etag(uri).unwrap(), "\"abc\"");
+        let expired = cache.ttl(Duration::from_secs(0));
+        std::thread::sleep(Duration::from_millis(10));
+        assert!(expired.lookup_etag(uri).is_err());
+        assert!(!cache_path(&dir, uri, "json").exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn evicts_least_recently_cached_entries() {
+        let dir = cache_dir("size");
+        let cache = FileBasedCache::new(&dir).max_size(20);
+        let first = "https://api.github.com/users/octocat";
+        let second = "https://api.github.com/users/hubot";
+        cache
+            .cache_body_and_etag(first, b"0123456789", b"a")
+            .unwrap();
+        std::thread::sleep(Duration::from_millis(10));
+        cache
+            .cache_body_and_etag(second, b"0123456789", b"b")
+            .unwrap();
+        assert!(cache.lookup_body(first).is_err());
+        assert_eq!(cache.lookup_body(second).unwrap(), "0123456789");
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
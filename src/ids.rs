@@ -0,0 +1,83 @@
+//! Strongly typed identifiers
+//!
+//! Github identifies most resources both by a globally unique id and, within
+//! a repository, by a number. Both are plain integers on the wire, which
+//! makes it easy to pass one where the other was expected. The types in this
+//! module tell them apart. Each converts from a `u64`, so literals can still
+//! be passed wherever an identifier is expected, but an `IssueId` can not be
+//! passed where an `IssueNumber` is.
+
+use std::fmt;
+
+macro_rules! id {
+    ($(#[$attr:meta])* $name:ident) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+        #[serde(transparent)]
+        pub struct $name(pub u64);
+
+        impl From<u64> for $name {
+            fn from(id: u64) -> Self {
+                $name(id)
+            }
+        }
+
+        impl From<$name> for u64 {
+            fn from(id: $name) -> u64 {
+                id.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+    };
+}
+
+id!(
+    /// The globally unique id of a repository
+    RepoId
+);
+id!(
+    /// The globally unique id of an issue
+    IssueId
+);
+id!(
+    /// The number of an issue within its repository
+    IssueNumber
+);
+id!(
+    /// The globally unique id of a pull request
+    PullId
+);
+id!(
+    /// The number of a pull request within its repository. Pull requests
+    /// share their numbering with issues
+    PullNumber
+);
+id!(
+    /// The id of a Github App installation
+    InstallationId
+);
+
+impl From<PullNumber> for IssueNumber {
+    fn from(number: PullNumber) -> Self {
+        IssueNumber(number.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn transparent() {
+        let number: IssueNumber = serde_json::from_str("1347").unwrap();
+        assert_eq!(number, IssueNumber(1347));
+        assert_eq!(serde_json::to_string(&RepoId(1296269)).unwrap(), "1296269");
+        assert_eq!(format!("/issues/{}", number), "/issues/1347");
+    }
+}
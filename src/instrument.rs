@@ -0,0 +1,30 @@
+//! Runs futures within a tracing span
+
+use futures::{Future as StdFuture, Poll};
+use tracing::Span;
+
+/// A future which enters `span` each time it is polled, so that events
+/// emitted and fields recorded while it runs belong to that span
+pub struct Instrumented<F> {
+    inner: F,
+    span: Span,
+}
+
+impl<F> Instrumented<F> {
+    pub fn new(inner: F, span: Span) -> Self {
+        Instrumented { inner, span }
+    }
+}
+
+impl<F> StdFuture for Instrumented<F>
+where
+    F: StdFuture,
+{
+    type Item = F::Item;
+    type Error = F::Error;
+
+    fn poll(&mut self) -> Poll<F::Item, F::Error> {
+        let _enter = self.span.enter();
+        self.inner.poll()
+    }
+}
@@ -3,15 +3,43 @@
 use std::collections::HashMap;
 use std::fmt;
 
-use futures::future;
+use futures::{future, Future as StdFuture};
 use hyper::client::connect::Connect;
+use serde_json::Value;
 use url::form_urlencoded;
 
 use comments::Comments;
+use cross_references::{CrossReference, ReferenceSource, ReferencingPull, CROSS_REFERENCES_QUERY};
+use graphql::{json_vars, paginate};
+use ids::{IssueId, IssueNumber};
 use labels::Label;
-use users::User;
+use users::{AuthorAssociation, User};
 use {serde_json, unfold, Future, Github, SortDirection, Stream};
 
+const PINNED_ISSUES_QUERY: &str = r#"
+query($owner: String!, $repo: String!, $cursor: String) {
+  repository(owner: $owner, name: $repo) {
+    pinnedIssues(first: 100, after: $cursor) {
+      nodes { issue { id number title url state } }
+      pageInfo { hasNextPage endCursor }
+    }
+  }
+}"#;
+
+const PIN_MUTATION: &str = r#"
+mutation($issue: ID!) {
+  pinIssue(input: {issueId: $issue}) {
+    issue { id }
+  }
+}"#;
+
+const UNPIN_MUTATION: &str = r#"
+mutation($issue: ID!) {
+  unpinIssue(input: {issueId: $issue}) {
+    issue { id }
+  }
+}"#;
+
 /// enum representation of github pull and issue state
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum State {
@@ -40,6 +68,14 @@ impl Default for State {
     }
 }
 
+string_enum! {
+    /// The state of an issue or pull request
+    pub enum IssueState {
+        Open => "open",
+        Closed => "closed",
+    }
+}
+
 /// Sort options available for github issues
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Sort {
@@ -73,21 +109,22 @@ pub struct IssueAssignees<C: Clone + Connect + 'static> {
     github: Github<C>,
     owner: String,
     repo: String,
-    number: u64,
+    number: IssueNumber,
 }
 
 impl<C: Clone + Connect + 'static> IssueAssignees<C> {
     #[doc(hidden)]
-    pub fn new<O, R>(github: Github<C>, owner: O, repo: R, number: u64) -> Self
+    pub fn new<O, R, N>(github: Github<C>, owner: O, repo: R, number: N) -> Self
     where
         O: Into<String>,
         R: Into<String>,
+        N: Into<IssueNumber>,
     {
         IssueAssignees {
             github,
             owner: owner.into(),
             repo: repo.into(),
-            number,
+            number: number.into(),
         }
     }
 
@@ -111,21 +148,22 @@ pub struct IssueLabels<C: Clone + Connect + 'static> {
     github: Github<C>,
     owner: String,
     repo: String,
-    number: u64,
+    number: IssueNumber,
 }
 
 impl<C: Clone + Connect + 'static> IssueLabels<C> {
     #[doc(hidden)]
-    pub fn new<O, R>(github: Github<C>, owner: O, repo: R, number: u64) -> Self
+    pub fn new<O, R, N>(github: Github<C>, owner: O, repo: R, number: N) -> Self
     where
         O: Into<String>,
         R: Into<String>,
+        N: Into<IssueNumber>,
     {
         IssueLabels {
             github,
             owner: owner.into(),
             repo: repo.into(),
-            number,
+            number: number.into(),
         }
     }
 
@@ -170,21 +208,22 @@ where
     github: Github<C>,
     owner: String,
     repo: String,
-    number: u64,
+    number: IssueNumber,
 }
 
 impl<C: Clone + Connect + 'static> IssueRef<C> {
     #[doc(hidden)]
-    pub fn new<O, R>(github: Github<C>, owner: O, repo: R, number: u64) -> Self
+    pub fn new<O, R, N>(github: Github<C>, owner: O, repo: R, number: N) -> Self
     where
         O: Into<String>,
         R: Into<String>,
+        N: Into<IssueNumber>,
     {
         IssueRef {
             github,
             owner: owner.into(),
             repo: repo.into(),
-            number,
+            number: number.into(),
         }
     }
 
@@ -234,6 +273,60 @@ impl<C: Clone + Connect + 'static> IssueRef<C> {
             self.number,
         )
     }
+
+    /// List the issues and pull requests which reference this issue, oldest first
+    pub fn cross_references(&self) -> Future<Vec<CrossReference>> {
+        paginate(
+            self.github.graphql(),
+            CROSS_REFERENCES_QUERY.to_owned(),
+            json_vars(&[
+                ("owner", Value::String(self.owner.clone())),
+                ("repo", Value::String(self.repo.clone())),
+                ("number", Value::from(u64::from(self.number))),
+            ]),
+            "/repository/issue/timelineItems".to_owned(),
+        )
+    }
+
+    /// List the pull requests which will close this issue once merged, or
+    /// which closed it when they were
+    pub fn closing_pulls(&self) -> Future<Vec<ReferencingPull>> {
+        Box::new(self.cross_references().map(|references| {
+            references
+                .into_iter()
+                .filter(CrossReference::closes)
+                .filter_map(|reference| match reference.source {
+                    ReferenceSource::PullRequest(pull) => Some(pull),
+                    _ => None,
+                })
+                .collect()
+        }))
+    }
+
+    /// Pin this issue to the top of the repository's issues. A repository
+    /// may pin up to three issues
+    pub fn pin(&self) -> Future<()> {
+        self.pinning(PIN_MUTATION)
+    }
+
+    /// Unpin this issue
+    pub fn unpin(&self) -> Future<()> {
+        self.pinning(UNPIN_MUTATION)
+    }
+
+    fn pinning(&self, mutation: &'static str) -> Future<()> {
+        // pinning is only exposed through the GraphQL api, which addresses
+        // issues by their node id
+        let graphql = self.github.graphql();
+        Box::new(self.get().and_then(move |issue| {
+            graphql
+                .query::<_, Value>(
+                    mutation,
+                    &json_vars(&[("issue", Value::String(issue.node_id))]),
+                )
+                .map(|_| ())
+        }))
+    }
 }
 
 /// Provides access to operations available for a repository issues
@@ -269,7 +362,10 @@ impl<C: Clone + Connect + 'static> Issues<C> {
         format!("/repos/{}/{}/issues{}", self.owner, self.repo, more)
     }
 
-    pub fn get(&self, number: u64) -> IssueRef<C> {
+    pub fn get<N>(&self, number: N) -> IssueRef<C>
+    where
+        N: Into<IssueNumber>,
+    {
         IssueRef::new(
             self.github.clone(),
             self.owner.as_str(),
@@ -311,6 +407,22 @@ impl<C: Clone + Connect + 'static> Issues<C> {
             identity,
         )
     }
+
+    /// List the issues pinned to the top of this repository's issues
+    pub fn pinned(&self) -> Future<Vec<PinnedIssue>> {
+        Box::new(
+            paginate::<_, PinnedIssueNode>(
+                self.github.graphql(),
+                PINNED_ISSUES_QUERY.to_owned(),
+                json_vars(&[
+                    ("owner", Value::String(self.owner.clone())),
+                    ("repo", Value::String(self.repo.clone())),
+                ]),
+                "/repository/pinnedIssues".to_owned(),
+            )
+            .map(|nodes| nodes.into_iter().map(|node| node.issue).collect()),
+        )
+    }
 }
 
 // representations
@@ -432,13 +544,65 @@ impl IssueListOptionsBuilder {
     }
 }
 
+pub struct IssueOptionsBuilder(IssueOptions);
+
+impl IssueOptionsBuilder {
+    /// set the body of the issue
+    pub fn body<B>(&mut self, body: B) -> &mut Self
+    where
+        B: Into<String>,
+    {
+        self.0.body = Some(body.into());
+        self
+    }
+
+    /// set the logins of the users to assign the issue to
+    pub fn assignees<A>(&mut self, assignees: Vec<A>) -> &mut Self
+    where
+        A: Into<String>,
+    {
+        self.0.assignees = assignees.into_iter().map(|a| a.into()).collect();
+        self
+    }
+
+    /// set the number of the milestone to associate the issue with
+    pub fn milestone(&mut self, milestone: u64) -> &mut Self {
+        self.0.milestone = Some(milestone);
+        self
+    }
+
+    /// set the names of the labels to apply to the issue
+    pub fn labels<L>(&mut self, labels: Vec<L>) -> &mut Self
+    where
+        L: Into<String>,
+    {
+        self.0.labels = labels.into_iter().map(|l| l.into()).collect();
+        self
+    }
+
+    /// create a new set of issue options
+    pub fn build(&self) -> IssueOptions {
+        IssueOptions {
+            title: self.0.title.clone(),
+            body: self.0.body.clone(),
+            assignee: self.0.assignee.clone(),
+            assignees: self.0.assignees.clone(),
+            milestone: self.0.milestone,
+            labels: self.0.labels.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct IssueOptions {
     pub title: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub body: Option<String>,
+    /// deprecated by Github in favor of `assignees`
     #[serde(skip_serializing_if = "Option::is_none")]
     pub assignee: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub assignees: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub milestone: Option<u64>,
     pub labels: Vec<String>,
@@ -462,6 +626,7 @@ impl IssueOptions {
             title: title.into(),
             body: body.map(|b| b.into()),
             assignee: assignee.map(|a| a.into()),
+            assignees: Vec::new(),
             milestone,
             labels: labels
                 .into_iter()
@@ -469,21 +634,37 @@ impl IssueOptions {
                 .collect::<Vec<String>>(),
         }
     }
+
+    pub fn builder<T>(title: T) -> IssueOptionsBuilder
+    where
+        T: Into<String>,
+    {
+        IssueOptionsBuilder(IssueOptions {
+            title: title.into(),
+            body: None,
+            assignee: None,
+            assignees: Vec::new(),
+            milestone: None,
+            labels: Vec::new(),
+        })
+    }
 }
 
 #[derive(Debug, Deserialize)]
 pub struct Issue {
-    pub id: u64,
+    pub id: IssueId,
+    pub node_id: String,
     pub url: String,
     pub labels_url: String,
     pub comments_url: String,
     pub events_url: String,
     pub html_url: String,
-    pub number: u64,
-    pub state: String,
+    pub number: IssueNumber,
+    pub state: IssueState,
     pub title: String,
     pub body: Option<String>,
     pub user: User,
+    pub author_association: AuthorAssociation,
     pub labels: Vec<Label>,
     pub assignee: Option<User>,
     pub locked: bool,
@@ -495,6 +676,23 @@ pub struct Issue {
     pub assignees: Vec<User>,
 }
 
+#[derive(Debug, Deserialize)]
+struct PinnedIssueNode {
+    issue: PinnedIssue,
+}
+
+/// An issue pinned to the top of a repository's issues
+#[derive(Debug, Deserialize)]
+pub struct PinnedIssue {
+    /// the issue's node id
+    pub id: String,
+    pub number: IssueNumber,
+    pub title: String,
+    pub url: String,
+    /// `OPEN` or `CLOSED`
+    pub state: String,
+}
+
 /// A reference to a pull request.
 #[derive(Debug, Deserialize)]
 pub struct PullRef {
@@ -508,6 +706,43 @@ pub struct PullRef {
 mod tests {
     use super::*;
 
+    #[test]
+    fn deserialize_pinned_issue() {
+        let node: PinnedIssueNode = serde_json::from_str(
+            r#"{
+              "issue": {
+                "id": "I_kwDOAHz1OX4uYAah",
+                "number": 42,
+                "title": "Announcing 1.0",
+                "url": "https://github.com/octocat/hello-world/issues/42",
+                "state": "OPEN"
+              }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(node.issue.number, IssueNumber::from(42u64));
+        assert_eq!(node.issue.state, "OPEN");
+    }
+
+    #[test]
+    fn issue_state_fallback() {
+        let states: Vec<IssueState> =
+            serde_json::from_str(r#"["open", "closed", "merged"]"#).unwrap();
+        assert_eq!(
+            states,
+            vec![
+                IssueState::Open,
+                IssueState::Closed,
+                IssueState::Unknown("merged".into())
+            ]
+        );
+        assert_eq!(
+            serde_json::to_string(&IssueState::Unknown("merged".into())).unwrap(),
+            r#""merged""#
+        );
+        assert_eq!(IssueState::Closed.to_string(), "closed");
+    }
+
     #[test]
     fn default_state() {
         let default: State = Default::default();
@@ -539,6 +774,19 @@ mod tests {
         test_serialize(tests)
     }
 
+    #[test]
+    fn issue_options_reqs() {
+        let options = IssueOptions::builder("title")
+            .assignees(vec!["octocat", "hubot"])
+            .milestone(1)
+            .labels(vec!["bug"])
+            .build();
+        assert_eq!(
+            serde_json::to_string(&options).unwrap(),
+            r#"{"title":"title","assignees":["octocat","hubot"],"milestone":1,"labels":["bug"]}"#
+        );
+    }
+
     #[test]
     fn sort_default() {
         let default: Sort = Default::default();
@@ -0,0 +1,388 @@
+//! Incremental deserialization of json arrays
+//!
+//! Response bodies are usually buffered in full before they are deserialized.
+//! For list responses with thousands of items this holds the whole body and
+//! every item in memory at once. `ArrayItems` instead scans the body as it
+//! arrives and deserializes each element of the top level array as soon as it
+//! is complete, keeping only the element being read in memory. `Decoded`
+//! decompresses compressed bodies as they arrive so they may be scanned too.
+//!
+//! As bodies are never held in full, `ResponseLimits::max_body_size` bounds
+//! each element rather than the whole body, while `ResponseLimits::max_depth`
+//! bounds the nesting of the body as it does for buffered responses.
+
+use std::collections::VecDeque;
+use std::io::Write;
+use std::mem;
+
+use flate2::write::{GzDecoder, ZlibDecoder};
+use futures::{Async, Poll, Stream as StdStream};
+use hyper::Chunk;
+use serde::de::DeserializeOwned;
+use serde_json;
+
+use limits::ResponseLimits;
+use {Error, ErrorKind};
+
+/// A stream of the elements of a json array read from a stream of chunks
+pub struct ArrayItems<S, D> {
+    body: S,
+    limits: ResponseLimits,
+    buf: Vec<u8>,
+    /// the position of the next byte to scan in `buf`
+    pos: usize,
+    /// the position in `buf` the element being read starts at
+    start: usize,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    complete: bool,
+    items: VecDeque<D>,
+}
+
+impl<S, D> ArrayItems<S, D>
+where
+    S: StdStream<Item = Chunk, Error = Error>,
+    D: DeserializeOwned,
+{
+    pub fn new(body: S, limits: ResponseLimits) -> Self {
+        ArrayItems {
+            body,
+            limits,
+            buf: Vec::new(),
+            pos: 0,
+            start: 0,
+            depth: 0,
+            in_string: false,
+            escaped: false,
+            complete: false,
+            items: VecDeque::new(),
+        }
+    }
+
+    /// scan the buffered bytes, deserializing the elements completed so far
+    fn scan(&mut self) -> Result<(), Error> {
+        while self.pos < self.buf.len() && !self.complete {
+            let byte = self.buf[self.pos];
+            if self.in_string {
+                if self.escaped {
+                    self.escaped = false;
+                } else if byte == b'\\' {
+                    self.escaped = true;
+                } else if byte == b'"' {
+                    self.in_string = false;
+                }
+            } else {
+                match byte {
+                    b'"' => self.in_string = true,
+                    b'[' | b'{' => {
+                        if self.depth == 0 {
+                            if byte != b'[' {
+                                return Err(expected_array());
+                            }
+                            self.start = self.pos + 1;
+                        }
+                        self.depth += 1;
+                        match self.limits.max_depth {
+                            Some(max) if self.depth > max => {
+                                return Err(ErrorKind::ResponseTooDeep(max).into())
+                            }
+                            _ => (),
+                        }
+                    }
+                    b']' | b'}' => {
+                        if self.depth == 1 {
+                            let end = self.pos;
+                            self.element(end)?;
+                            self.complete = true;
+                        }
+                        self.depth = self.depth.saturating_sub(1);
+                    }
+                    b',' if self.depth == 1 => {
+                        let end = self.pos;
+                        self.element(end)?;
+                        self.start = self.pos + 1;
+                    }
+                    b' ' | b'\t' | b'\r' | b'\n' => (),
+                    _ if self.depth == 0 => return Err(expected_array()),
+                    _ => (),
+                }
+            }
+            self.pos += 1;
+        }
+        // drop the bytes of elements already deserialized
+        if self.start > 0 && !self.complete {
+            self.buf.drain(..self.start);
+            self.pos -= self.start;
+            self.start = 0;
+        }
+        match self.limits.max_body_size {
+            Some(max) if !self.complete && self.buf.len() > max => {
+                Err(ErrorKind::ResponseTooLarge(max).into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// deserialize the element between `start` and `end`. whitespace only
+    /// elements are those of empty arrays
+    fn element(&mut self, end: usize) -> Result<(), Error> {
+        match self.limits.max_body_size {
+            Some(max) if end - self.start > max => {
+                return Err(ErrorKind::ResponseTooLarge(max).into())
+            }
+            _ => (),
+        }
+        let slice = &self.buf[self.start..end];
+        if slice
+            .iter()
+            .all(|b| [b' ', b'\t', b'\r', b'\n'].contains(b))
+        {
+            return Ok(());
+        }
+        let item = serde_json::from_slice::<D>(slice).map_err(ErrorKind::Codec)?;
+        self.items.push_back(item);
+        Ok(())
+    }
+}
+
+/// A stream of the chunks of a response body, decompressed according to
+/// its `Content-Encoding` as they arrive
+pub struct Decoded<S> {
+    body: S,
+    decoder: Option<Decoder>,
+    finished: bool,
+}
+
+enum Decoder {
+    Gzip(GzDecoder<Vec<u8>>),
+    Deflate(ZlibDecoder<Vec<u8>>),
+}
+
+impl<S> Decoded<S>
+where
+    S: StdStream<Item = Chunk, Error = Error>,
+{
+    pub fn new(body: S, encoding: Option<String>) -> Self {
+        let decoder = match encoding.as_ref().map(|e| e.as_str()) {
+            Some("gzip") => Some(Decoder::Gzip(GzDecoder::new(Vec::new()))),
+            Some("deflate") => Some(Decoder::Deflate(ZlibDecoder::new(Vec::new()))),
+            _ => None,
+        };
+        Decoded {
+            body,
+            decoder,
+            finished: false,
+        }
+    }
+}
+
+impl Decoder {
+    /// decompress `chunk`, returning the bytes decompressed so far
+    fn write(&mut self, chunk: &[u8]) -> Result<Vec<u8>, Error> {
+        let decompressed = match *self {
+            Decoder::Gzip(ref mut decoder) => {
+                decoder.write_all(chunk)?;
+                decoder.get_mut()
+            }
+            Decoder::Deflate(ref mut decoder) => {
+                decoder.write_all(chunk)?;
+                decoder.get_mut()
+            }
+        };
+        Ok(mem::replace(decompressed, Vec::new()))
+    }
+
+    /// decompress what remains once the body has ended
+    fn finish(&mut self) -> Result<Vec<u8>, Error> {
+        let decompressed = match *self {
+            Decoder::Gzip(ref mut decoder) => {
+                decoder.try_finish()?;
+                decoder.get_mut()
+            }
+            Decoder::Deflate(ref mut decoder) => {
+                decoder.try_finish()?;
+                decoder.get_mut()
+            }
+        };
+        Ok(mem::replace(decompressed, Vec::new()))
+    }
+}
+
+impl<S> StdStream for Decoded<S>
+where
+    S: StdStream<Item = Chunk, Error = Error>,
+{
+    type Item = Chunk;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<Chunk>, Error> {
+        loop {
+            if self.finished {
+                return Ok(Async::Ready(None));
+            }
+            let decoder = match self.decoder {
+                Some(ref mut decoder) => decoder,
+                None => return self.body.poll(),
+            };
+            let decompressed = match self.body.poll()? {
+                Async::Ready(Some(chunk)) => decoder.write(&chunk)?,
+                Async::Ready(None) => {
+                    self.finished = true;
+                    decoder.finish()?
+                }
+                Async::NotReady => return Ok(Async::NotReady),
+            };
+            // compressed chunks may be too small to decompress anything yet
+            if !decompressed.is_empty() {
+                return Ok(Async::Ready(Some(Chunk::from(decompressed))));
+            }
+        }
+    }
+}
+
+fn expected_array() -> Error {
+    Error::from("expected a json array")
+}
+
+impl<S, D> StdStream for ArrayItems<S, D>
+where
+    S: StdStream<Item = Chunk, Error = Error>,
+    D: DeserializeOwned,
+{
+    type Item = D;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<D>, Error> {
+        loop {
+            if let Some(item) = self.items.pop_front() {
+                return Ok(Async::Ready(Some(item)));
+            }
+            if self.complete {
+                return Ok(Async::Ready(None));
+            }
+            match self.body.poll()? {
+                Async::Ready(Some(chunk)) => {
+                    self.buf.extend_from_slice(&chunk);
+                    self.scan()?;
+                }
+                Async::Ready(None) => {
+                    return Err(Error::from("unexpected end of json array"));
+                }
+                Async::NotReady => return Ok(Async::NotReady),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{stream, Future};
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Item {
+        login: String,
+    }
+
+    fn items(chunks: Vec<&'static str>) -> Result<Vec<Item>, Error> {
+        limited_items(chunks, ResponseLimits::default())
+    }
+
+    fn limited_items(
+        chunks: Vec<&'static str>,
+        limits: ResponseLimits,
+    ) -> Result<Vec<Item>, Error> {
+        ArrayItems::new(
+            stream::iter_ok::<_, Error>(chunks.into_iter().map(Chunk::from)),
+            limits,
+        )
+        .collect()
+        .wait()
+    }
+
+    #[test]
+    fn split_chunks() {
+        assert_eq!(
+            items(vec![
+                r#" [{"login":"oc"#,
+                r#"to\"cat"}, {"lo"#,
+                r#"gin":"a,]}"}"#,
+                "]",
+            ])
+            .unwrap(),
+            vec![
+                Item {
+                    login: "octo\"cat".into()
+                },
+                Item {
+                    login: "a,]}".into()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_array() {
+        assert_eq!(items(vec!["[ ]"]).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn not_an_array() {
+        assert!(items(vec![r#"{"message":"Not Found"}"#]).is_err());
+    }
+
+    #[test]
+    fn gzipped() {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(br#"[{"login":"octocat"},{"login":"hubot"}]"#)
+            .unwrap();
+        let compressed = encoder.finish().unwrap();
+        let chunks = compressed
+            .chunks(3)
+            .map(|chunk| Chunk::from(chunk.to_vec()))
+            .collect::<Vec<_>>();
+        let body = Decoded::new(stream::iter_ok::<_, Error>(chunks), Some("gzip".into()));
+        let logins = ArrayItems::new(body, ResponseLimits::default())
+            .map(|item: Item| item.login)
+            .collect()
+            .wait()
+            .unwrap();
+        assert_eq!(logins, vec!["octocat", "hubot"]);
+    }
+
+    #[test]
+    fn limits() {
+        let limits = ResponseLimits {
+            max_body_size: Some(20),
+            max_depth: Some(2),
+        };
+        // elements are bounded one at a time, not the whole body
+        assert_eq!(
+            limited_items(
+                vec![r#"[{"login":"octocat"},"#, r#"{"login":"hubot"}]"#],
+                limits
+            )
+            .unwrap()
+            .len(),
+            2
+        );
+        match limited_items(vec![r#"[{"login":"octocat-and-then-some"}]"#], limits) {
+            Err(Error(ErrorKind::ResponseTooLarge(20), _)) => (),
+            other => panic!("expected too large: {:?}", other),
+        }
+        match limited_items(vec![r#"[{"login":["octocat"]}]"#], limits) {
+            Err(Error(ErrorKind::ResponseTooDeep(2), _)) => (),
+            other => panic!("expected too deep: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn truncated() {
+        assert!(items(vec![r#"[{"login":"octocat"}"#]).is_err());
+    }
+}
@@ -7,7 +7,11 @@ use futures::future;
 use hyper::client::connect::Connect;
 use serde_json;
 
-use {Future, Github};
+use {unfold, Future, Github, Stream};
+
+fn identity<T>(x: T) -> T {
+    x
+}
 
 pub struct Keys<C>
 where
@@ -44,6 +48,15 @@ impl<C: Clone + Connect + 'static> Keys<C> {
         self.github.get(&self.path(""))
     }
 
+    /// provides a stream over all pages of this repo's deploy keys
+    pub fn iter(&self) -> Stream<Key> {
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&self.path("")),
+            identity,
+        )
+    }
+
     pub fn get(&self, id: u64) -> Future<Key> {
         self.github.get(&self.path(&format!("/{}", id)))
     }
@@ -60,14 +73,32 @@ pub struct Key {
     pub id: u64,
     pub key: String,
     pub title: String,
+    pub url: String,
     pub verified: bool,
     pub created_at: String,
     pub read_only: bool,
+    pub added_by: Option<String>,
+    pub last_used: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct KeyOptions {
     pub title: String,
     pub key: String,
+    /// read only keys can pull from but not push to the repository
     pub read_only: bool,
 }
+
+impl KeyOptions {
+    pub fn new<T, K>(title: T, key: K, read_only: bool) -> KeyOptions
+    where
+        T: Into<String>,
+        K: Into<String>,
+    {
+        KeyOptions {
+            title: title.into(),
+            key: key.into(),
+            read_only,
+        }
+    }
+}
@@ -2,9 +2,10 @@
 
 use futures::future;
 use hyper::client::connect::Connect;
+use percent_encoding::{utf8_percent_encode, PATH_SEGMENT_ENCODE_SET};
 use serde_json;
 
-use {unfold, Future, Github, Stream};
+use {unfold, Error, Future, Github, Stream};
 
 fn identity<T>(x: T) -> T {
     x
@@ -37,17 +38,42 @@ impl<C: Clone + Connect + 'static> Labels<C> {
         format!("/repos/{}/{}/labels{}", self.owner, self.repo, more)
     }
 
+    fn label_path(&self, name: &str) -> String {
+        self.path(&format!(
+            "/{}",
+            utf8_percent_encode(name, PATH_SEGMENT_ENCODE_SET)
+        ))
+    }
+
     pub fn create(&self, lab: &LabelOptions) -> Future<Label> {
+        if let Err(err) = validate_color(&lab.color) {
+            return Box::new(future::err(err));
+        }
         self.github.post(&self.path(""), json!(lab))
     }
 
+    /// replaces the name, color and description of the label named `prevname`
     pub fn update(&self, prevname: &str, lab: &LabelOptions) -> Future<Label> {
-        self.github
-            .patch(&self.path(&format!("/{}", prevname)), json!(lab))
+        let edit = LabelEditOptions {
+            new_name: Some(lab.name.clone()),
+            color: Some(lab.color.clone()),
+            description: lab.description.clone(),
+        };
+        self.edit(prevname, &edit)
+    }
+
+    /// updates only the fields of the label named `name` set in `lab`
+    pub fn edit(&self, name: &str, lab: &LabelEditOptions) -> Future<Label> {
+        if let Some(ref color) = lab.color {
+            if let Err(err) = validate_color(color) {
+                return Box::new(future::err(err));
+            }
+        }
+        self.github.patch(&self.label_path(name), json!(lab))
     }
 
     pub fn delete(&self, name: &str) -> Future<()> {
-        self.github.delete(&self.path(&format!("/{}", name)))
+        self.github.delete(&self.label_path(name))
     }
 
     pub fn list(&self) -> Future<Vec<Label>> {
@@ -66,13 +92,59 @@ impl<C: Clone + Connect + 'static> Labels<C> {
 
 // representations
 
+/// strips the leading `#` Github rejects from hex colors
+fn normalize_color(color: String) -> String {
+    if color.starts_with('#') {
+        color[1..].to_owned()
+    } else {
+        color
+    }
+}
+
+/// Github only accepts six digit hex colors
+fn validate_color(color: &str) -> ::Result<()> {
+    if color.len() == 6 && color.chars().all(|c| c.is_ascii_hexdigit()) {
+        Ok(())
+    } else {
+        Err(Error::from(format!(
+            "invalid label color {:?}, expected six hex digits",
+            color
+        )))
+    }
+}
+
+pub struct LabelOptionsBuilder(LabelOptions);
+
+impl LabelOptionsBuilder {
+    /// set the description of the label
+    pub fn description<D>(&mut self, description: D) -> &mut Self
+    where
+        D: Into<String>,
+    {
+        self.0.description = Some(description.into());
+        self
+    }
+
+    /// create a new set of label options
+    pub fn build(&self) -> LabelOptions {
+        LabelOptions {
+            name: self.0.name.clone(),
+            color: self.0.color.clone(),
+            description: self.0.description.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct LabelOptions {
     pub name: String,
     pub color: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
 }
 
 impl LabelOptions {
+    /// a leading `#` is stripped from `color`
     pub fn new<N, C>(name: N, color: C) -> LabelOptions
     where
         N: Into<String>,
@@ -80,14 +152,107 @@ impl LabelOptions {
     {
         LabelOptions {
             name: name.into(),
-            color: color.into(),
+            color: normalize_color(color.into()),
+            description: None,
+        }
+    }
+
+    pub fn builder<N, C>(name: N, color: C) -> LabelOptionsBuilder
+    where
+        N: Into<String>,
+        C: Into<String>,
+    {
+        LabelOptionsBuilder(LabelOptions::new(name, color))
+    }
+}
+
+#[derive(Default)]
+pub struct LabelEditOptionsBuilder(LabelEditOptions);
+
+impl LabelEditOptionsBuilder {
+    /// rename the label
+    pub fn new_name<N>(&mut self, new_name: N) -> &mut Self
+    where
+        N: Into<String>,
+    {
+        self.0.new_name = Some(new_name.into());
+        self
+    }
+
+    /// set the color of the label. a leading `#` is stripped
+    pub fn color<C>(&mut self, color: C) -> &mut Self
+    where
+        C: Into<String>,
+    {
+        self.0.color = Some(normalize_color(color.into()));
+        self
+    }
+
+    /// set the description of the label
+    pub fn description<D>(&mut self, description: D) -> &mut Self
+    where
+        D: Into<String>,
+    {
+        self.0.description = Some(description.into());
+        self
+    }
+
+    /// create a new set of label edit options
+    pub fn build(&self) -> LabelEditOptions {
+        LabelEditOptions {
+            new_name: self.0.new_name.clone(),
+            color: self.0.color.clone(),
+            description: self.0.description.clone(),
         }
     }
 }
 
+#[derive(Debug, Default, Serialize)]
+pub struct LabelEditOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl LabelEditOptions {
+    pub fn builder() -> LabelEditOptionsBuilder {
+        LabelEditOptionsBuilder::default()
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Label {
     pub url: String,
     pub name: String,
     pub color: String,
+    pub description: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colors() {
+        assert_eq!(LabelOptions::new("bug", "#D73A4A").color, "D73A4A");
+        assert_eq!(LabelOptions::new("bug", "d73a4a").color, "d73a4a");
+        assert!(validate_color("d73a4a").is_ok());
+        assert!(validate_color("red").is_err());
+        assert!(validate_color("#d73a4a").is_err());
+    }
+
+    #[test]
+    fn edit_options() {
+        let options = LabelEditOptions::builder()
+            .new_name("good first issue")
+            .color("#7057ff")
+            .build();
+        assert_eq!(
+            serde_json::to_string(&options).unwrap(),
+            r#"{"new_name":"good first issue","color":"7057ff"}"#
+        );
+    }
 }
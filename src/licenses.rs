@@ -0,0 +1,58 @@
+//! Licenses interface
+//!
+//! See the [github docs](https://docs.github.com/en/rest/licenses) for more information
+
+use hyper::client::connect::Connect;
+
+use {Future, Github};
+
+/// Provides access to the open source licenses Github knows about
+pub struct Licenses<C: Clone + Connect + 'static> {
+    github: Github<C>,
+}
+
+impl<C: Clone + Connect + 'static> Licenses<C> {
+    #[doc(hidden)]
+    pub fn new(github: Github<C>) -> Self {
+        Self { github }
+    }
+
+    /// list commonly used licenses
+    pub fn list(&self) -> Future<Vec<LicenseInfo>> {
+        self.github.get("/licenses")
+    }
+
+    /// get a license by its key, like `mit`
+    pub fn get(&self, key: &str) -> Future<License> {
+        self.github.get(&format!("/licenses/{}", key))
+    }
+}
+
+// representations
+
+#[derive(Debug, Deserialize)]
+pub struct LicenseInfo {
+    pub key: String,
+    pub name: String,
+    pub spdx_id: Option<String>,
+    pub url: Option<String>,
+    pub node_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct License {
+    pub key: String,
+    pub name: String,
+    pub spdx_id: Option<String>,
+    pub url: Option<String>,
+    pub node_id: String,
+    pub html_url: String,
+    pub description: String,
+    pub implementation: String,
+    pub permissions: Vec<String>,
+    pub conditions: Vec<String>,
+    pub limitations: Vec<String>,
+    /// the license text
+    pub body: String,
+    pub featured: bool,
+}
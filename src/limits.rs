@@ -0,0 +1,136 @@
+//! Response limits
+//!
+//! Long running services may bound the responses they accept, so a
+//! pathological or unexpectedly huge response fails its request with a typed
+//! error rather than exhausting memory. Bodies larger than
+//! `ResponseLimits::max_body_size`, once decompressed, fail with
+//! `ErrorKind::ResponseTooLarge`. Json nested deeper than
+//! `ResponseLimits::max_depth` fails with `ErrorKind::ResponseTooDeep` before
+//! it's parsed. Responses deserialized incrementally as they arrive, like
+//! `Contributors::iter_streamed`, are never held in full, so the size limit
+//! bounds each of their items instead. Downloads of binary content, like
+//! release assets, are streamed to the caller and only bounded when they fail
+//!
+//! ```no_run
+//! # extern crate hubcaps;
+//! # use hubcaps::limits::ResponseLimits;
+//! # use hubcaps::Github;
+//! # fn main() {
+//! let github = Github::builder()
+//!     .agent("my-cool-user-agent/0.1.0")
+//!     .response_limits(ResponseLimits {
+//!         max_body_size: Some(10 * 1024 * 1024),
+//!         max_depth: Some(32),
+//!     })
+//!     .build();
+//! # }
+//! ```
+
+use futures::{Future as StdFuture, Stream as StdStream};
+use hyper::Body;
+
+use {decode, Error, ErrorKind, Future, Result};
+
+/// Bounds on the responses a client accepts. Both are unbounded by default
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ResponseLimits {
+    /// the largest response body accepted, in bytes once decompressed
+    pub max_body_size: Option<usize>,
+    /// the deepest nesting of json arrays and objects accepted
+    pub max_depth: Option<usize>,
+}
+
+impl ResponseLimits {
+    /// buffers and decompresses a response body according to its
+    /// `Content-Encoding`, within these limits
+    pub(crate) fn read(&self, body: Body, encoding: Option<String>) -> Future<Vec<u8>> {
+        let limits = *self;
+        Box::new(
+            body.map_err(Error::from)
+                .fold(Vec::new(), move |mut buffered, chunk| {
+                    // compressed bodies only ever grow as they're decompressed
+                    limits.check_size(buffered.len() + chunk.len())?;
+                    buffered.extend_from_slice(&chunk);
+                    Ok::<_, Error>(buffered)
+                })
+                .and_then(move |body| decode(encoding.as_ref(), &body, limits.max_body_size))
+                .and_then(move |body| {
+                    limits.check_depth(&body)?;
+                    Ok(body)
+                }),
+        )
+    }
+
+    fn check_size(&self, size: usize) -> Result<()> {
+        match self.max_body_size {
+            Some(max) if size > max => Err(ErrorKind::ResponseTooLarge(max).into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// checks the nesting of `json` without parsing it
+    fn check_depth(&self, json: &[u8]) -> Result<()> {
+        let max = match self.max_depth {
+            Some(max) => max,
+            None => return Ok(()),
+        };
+        let mut depth = 0usize;
+        let mut in_string = false;
+        let mut escaped = false;
+        for &byte in json {
+            if in_string {
+                match byte {
+                    _ if escaped => escaped = false,
+                    b'\\' => escaped = true,
+                    b'"' => in_string = false,
+                    _ => (),
+                }
+                continue;
+            }
+            match byte {
+                b'"' => in_string = true,
+                b'[' | b'{' => {
+                    depth += 1;
+                    if depth > max {
+                        return Err(ErrorKind::ResponseTooDeep(max).into());
+                    }
+                }
+                b']' | b'}' => depth = depth.saturating_sub(1),
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn depth() {
+        let limits = ResponseLimits {
+            max_body_size: None,
+            max_depth: Some(2),
+        };
+        assert!(limits.check_depth(br#"{"a":[1,2],"b":"[[[{{{"}"#).is_ok());
+        assert!(limits.check_depth(br#"{"a":"\"[[["}"#).is_ok());
+        match limits.check_depth(br#"{"a":[{"b":1}]}"#) {
+            Err(Error(ErrorKind::ResponseTooDeep(2), _)) => (),
+            other => panic!("expected too deep: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn size() {
+        let limits = ResponseLimits {
+            max_body_size: Some(4),
+            max_depth: None,
+        };
+        assert!(limits.check_size(4).is_ok());
+        match limits.check_size(5) {
+            Err(Error(ErrorKind::ResponseTooLarge(4), _)) => (),
+            other => panic!("expected too large: {:?}", other),
+        }
+    }
+}
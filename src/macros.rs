@@ -6,3 +6,67 @@ macro_rules! json {
         }
     }};
 }
+
+/// defines an enum of the string values of a field Github may add to,
+/// along with an `Unknown` variant holding values this crate does not yet
+/// know about, so matches stay exhaustive as Github adds values
+macro_rules! string_enum {
+    (
+        $(#[$enum_attr:meta])*
+        pub enum $name:ident {
+            $($(#[$attr:meta])* $variant:ident => $value:tt,)*
+        }
+    ) => {
+        $(#[$enum_attr])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($(#[$attr])* $variant,)*
+            /// a value this crate does not yet know about
+            Unknown(String),
+        }
+
+        impl $name {
+            /// the value as Github represents it
+            pub fn as_str(&self) -> &str {
+                match *self {
+                    $($name::$variant => $value,)*
+                    $name::Unknown(ref value) => value,
+                }
+            }
+        }
+
+        impl<'a> From<&'a str> for $name {
+            fn from(value: &'a str) -> $name {
+                match value {
+                    $($value => $name::$variant,)*
+                    other => $name::Unknown(other.to_owned()),
+                }
+            }
+        }
+
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                ::std::fmt::Display::fmt(self.as_str(), f)
+            }
+        }
+
+        impl ::serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+            where
+                S: ::serde::Serializer,
+            {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> ::serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> ::std::result::Result<$name, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+            {
+                let value = <String as ::serde::Deserialize>::deserialize(deserializer)?;
+                Ok($name::from(value.as_str()))
+            }
+        }
+    };
+}
@@ -0,0 +1,334 @@
+//! Organization members interface
+//!
+//! Besides listing members and managing their memberships one at a time,
+//! `OrgMembers::reconcile` brings an organization's members in line with a
+//! desired roster, reporting the progress of each change as it completes.
+//!
+//! See the [github docs](https://docs.github.com/en/rest/orgs/members) for more information
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+
+use futures::{future, Future as StdFuture, Stream as StdStream};
+use hyper::client::connect::Connect;
+use serde_json;
+
+use users::User;
+use {unfold, Error, Future, Github, Stream};
+
+fn identity<T>(x: T) -> T {
+    x
+}
+
+/// The role of a member of an organization
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemberRole {
+    Admin,
+    Member,
+}
+
+impl fmt::Display for MemberRole {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MemberRole::Admin => "admin",
+            MemberRole::Member => "member",
+        }
+        .fmt(f)
+    }
+}
+
+/// What `OrgMembers::reconcile` does with members missing from the roster
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExtraMembers {
+    /// leave them be
+    Keep,
+    /// make admins missing from the roster members
+    Demote,
+    /// remove them from the organization
+    Remove,
+}
+
+/// A change to the membership of an organization
+#[derive(Clone, Debug, PartialEq)]
+pub enum MembershipChange {
+    /// invite a user to the organization with a role
+    Add { login: String, role: MemberRole },
+    /// change the role of a member
+    SetRole { login: String, role: MemberRole },
+    /// remove a member from the organization
+    Remove { login: String },
+}
+
+/// The outcome of one change made by `OrgMembers::reconcile`
+#[derive(Debug)]
+pub struct Progress {
+    pub change: MembershipChange,
+    /// the reason the change failed, if it did
+    pub error: Option<Error>,
+    /// the number of changes completed so far, including this one
+    pub completed: usize,
+    /// the number of changes to make
+    pub total: usize,
+}
+
+/// Provides access to the members of an organization
+pub struct OrgMembers<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    org: String,
+}
+
+impl<C: Clone + Connect + 'static> OrgMembers<C> {
+    #[doc(hidden)]
+    pub fn new<O>(github: Github<C>, org: O) -> Self
+    where
+        O: Into<String>,
+    {
+        OrgMembers {
+            github,
+            org: org.into(),
+        }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!("/orgs/{}{}", self.org, more)
+    }
+
+    fn members_path(&self, role: Option<MemberRole>) -> String {
+        match role {
+            Some(role) => self.path(&format!("/members?role={}", role)),
+            None => self.path("/members"),
+        }
+    }
+
+    /// list the organization's members, optionally only those with a role
+    pub fn list(&self, role: Option<MemberRole>) -> Future<Vec<User>> {
+        self.github.get(&self.members_path(role))
+    }
+
+    /// provides a stream over all pages of the organization's members,
+    /// optionally only those with a role
+    pub fn iter(&self, role: Option<MemberRole>) -> Stream<User> {
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&self.members_path(role)),
+            identity,
+        )
+    }
+
+    /// get a user's membership of the organization, which is `pending`
+    /// until they accept an invitation
+    pub fn membership<U>(&self, user: U) -> Future<Membership>
+    where
+        U: Into<String>,
+    {
+        self.github
+            .get(&self.path(&format!("/memberships/{}", user.into())))
+    }
+
+    /// invite a user to the organization with a role, or change the role
+    /// of an existing member
+    pub fn set_membership<U>(&self, user: U, role: MemberRole) -> Future<Membership>
+    where
+        U: Into<String>,
+    {
+        let mut payload = BTreeMap::new();
+        payload.insert("role", role);
+        self.github.put(
+            &self.path(&format!("/memberships/{}", user.into())),
+            json!(payload),
+        )
+    }
+
+    /// remove a member from the organization, or cancel their pending invitation
+    pub fn remove_membership<U>(&self, user: U) -> Future<()>
+    where
+        U: Into<String>,
+    {
+        self.github
+            .delete(&self.path(&format!("/memberships/{}", user.into())))
+    }
+
+    fn apply(&self, change: &MembershipChange) -> Future<()> {
+        match *change {
+            MembershipChange::Add { ref login, role }
+            | MembershipChange::SetRole { ref login, role } => {
+                Box::new(self.set_membership(login.as_str(), role).map(|_| ()))
+            }
+            MembershipChange::Remove { ref login } => self.remove_membership(login.as_str()),
+        }
+    }
+
+    /// bring the organization's members in line with `roster`, a map of
+    /// logins to the roles they should have. users missing from the
+    /// organization are invited and roles which differ are changed. members
+    /// missing from the roster are handled according to `extras`.
+    ///
+    /// Changes are made concurrently, without exhausting the rate limit. The
+    /// returned stream yields the outcome of each change as it completes. A
+    /// failed change does not stop the others from being made.
+    pub fn reconcile(
+        &self,
+        roster: &BTreeMap<String, MemberRole>,
+        extras: ExtraMembers,
+    ) -> Stream<Progress> {
+        let members = OrgMembers::new(self.github.clone(), self.org.clone());
+        let roster = roster.clone();
+        let admins = self
+            .iter(Some(MemberRole::Admin))
+            .map(|user| (user.login, MemberRole::Admin))
+            .collect();
+        let others = self
+            .iter(Some(MemberRole::Member))
+            .map(|user| (user.login, MemberRole::Member))
+            .collect();
+        Box::new(
+            admins
+                .join(others)
+                .map(move |(admins, others)| {
+                    let current = admins.into_iter().chain(others).collect::<Vec<_>>();
+                    let changes = plan(&current, &roster, extras);
+                    let total = changes.len();
+                    let requests = changes
+                        .into_iter()
+                        .map(|change| {
+                            Box::new(
+                                members
+                                    .apply(&change)
+                                    .then(move |result| future::ok((change, result.err()))),
+                            )
+                                as Future<(MembershipChange, Option<Error>)>
+                        })
+                        .collect::<Vec<_>>();
+                    let mut completed = 0;
+                    members
+                        .github
+                        .batch()
+                        .run(requests)
+                        .map(move |(change, error)| {
+                            completed += 1;
+                            Progress {
+                                change,
+                                error,
+                                completed,
+                                total,
+                            }
+                        })
+                })
+                .flatten_stream(),
+        )
+    }
+}
+
+/// the changes which turn the `current` members into the `desired` ones.
+/// logins are compared ignoring case, as Github does
+fn plan(
+    current: &[(String, MemberRole)],
+    desired: &BTreeMap<String, MemberRole>,
+    extras: ExtraMembers,
+) -> Vec<MembershipChange> {
+    let existing = current
+        .iter()
+        .map(|&(ref login, role)| (login.to_lowercase(), role))
+        .collect::<HashMap<_, _>>();
+    let wanted = desired
+        .keys()
+        .map(|login| login.to_lowercase())
+        .collect::<HashSet<_>>();
+    let mut changes = Vec::new();
+    for (login, &role) in desired {
+        match existing.get(&login.to_lowercase()) {
+            None => changes.push(MembershipChange::Add {
+                login: login.clone(),
+                role,
+            }),
+            Some(&current) if current != role => changes.push(MembershipChange::SetRole {
+                login: login.clone(),
+                role,
+            }),
+            _ => (),
+        }
+    }
+    for &(ref login, role) in current {
+        if wanted.contains(&login.to_lowercase()) {
+            continue;
+        }
+        match extras {
+            ExtraMembers::Keep => (),
+            ExtraMembers::Demote if role == MemberRole::Admin => {
+                changes.push(MembershipChange::SetRole {
+                    login: login.clone(),
+                    role: MemberRole::Member,
+                })
+            }
+            ExtraMembers::Demote => (),
+            ExtraMembers::Remove => changes.push(MembershipChange::Remove {
+                login: login.clone(),
+            }),
+        }
+    }
+    changes
+}
+
+// representations
+
+#[derive(Debug, Deserialize)]
+pub struct Membership {
+    pub url: String,
+    /// `active` or `pending`
+    pub state: String,
+    /// `admin`, `member` or `billing_manager`
+    pub role: String,
+    pub organization_url: String,
+    pub user: User,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plan_changes() {
+        let current = vec![
+            ("Octocat".to_owned(), MemberRole::Admin),
+            ("hubot".to_owned(), MemberRole::Member),
+            ("monalisa".to_owned(), MemberRole::Admin),
+            ("defunkt".to_owned(), MemberRole::Member),
+        ];
+        let mut desired = BTreeMap::new();
+        desired.insert("octocat".to_owned(), MemberRole::Admin);
+        desired.insert("hubot".to_owned(), MemberRole::Admin);
+        desired.insert("mojombo".to_owned(), MemberRole::Member);
+
+        let changes = vec![
+            MembershipChange::SetRole {
+                login: "hubot".into(),
+                role: MemberRole::Admin,
+            },
+            MembershipChange::Add {
+                login: "mojombo".into(),
+                role: MemberRole::Member,
+            },
+        ];
+        assert_eq!(plan(&current, &desired, ExtraMembers::Keep), changes);
+
+        let mut demoted = changes.clone();
+        demoted.push(MembershipChange::SetRole {
+            login: "monalisa".into(),
+            role: MemberRole::Member,
+        });
+        assert_eq!(plan(&current, &desired, ExtraMembers::Demote), demoted);
+
+        let mut removed = changes.clone();
+        removed.push(MembershipChange::Remove {
+            login: "monalisa".into(),
+        });
+        removed.push(MembershipChange::Remove {
+            login: "defunkt".into(),
+        });
+        assert_eq!(plan(&current, &desired, ExtraMembers::Remove), removed);
+    }
+}
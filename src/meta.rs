@@ -0,0 +1,189 @@
+//! Meta interface
+//!
+//! See the [github docs](https://docs.github.com/en/rest/meta/meta) for more information
+
+use std::error::Error as StdError;
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use hyper::client::connect::Connect;
+use serde::de::{self, Deserialize, Deserializer};
+
+use {Future, Github};
+
+pub struct Meta<C: Clone + Connect + 'static> {
+    github: Github<C>,
+}
+
+impl<C: Clone + Connect + 'static> Meta<C> {
+    #[doc(hidden)]
+    pub fn new(github: Github<C>) -> Self {
+        Self { github }
+    }
+
+    /// get information about Github, including the ip ranges its services
+    /// are served from
+    pub fn get(&self) -> Future<MetaInfo> {
+        self.github.get("/meta")
+    }
+}
+
+/// An ip network in CIDR notation, like `192.30.252.0/22` or `2a0a:a440::/29`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Cidr {
+    pub addr: IpAddr,
+    pub prefix_len: u8,
+}
+
+impl Cidr {
+    /// returns true if the given address is within this network
+    pub fn contains(&self, addr: &IpAddr) -> bool {
+        match (self.addr, *addr) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                let bits = 32 - u32::from(self.prefix_len);
+                let mask = u32::max_value().checked_shl(bits).unwrap_or(0);
+                u32::from(net) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                let bits = 128 - u32::from(self.prefix_len);
+                let mask = u128::max_value().checked_shl(bits).unwrap_or(0);
+                u128::from(net) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Cidr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix_len)
+    }
+}
+
+/// An error parsing a `Cidr`
+#[derive(Debug, PartialEq)]
+pub struct CidrParseError(String);
+
+impl fmt::Display for CidrParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid CIDR {}", self.0)
+    }
+}
+
+impl StdError for CidrParseError {
+    fn description(&self) -> &str {
+        "invalid CIDR"
+    }
+}
+
+impl FromStr for Cidr {
+    type Err = CidrParseError;
+
+    fn from_str(s: &str) -> Result<Cidr, CidrParseError> {
+        let err = || CidrParseError(s.to_owned());
+        let mut parts = s.splitn(2, '/');
+        let addr = parts
+            .next()
+            .and_then(|addr| addr.parse::<IpAddr>().ok())
+            .ok_or_else(err)?;
+        let max = if addr.is_ipv4() { 32 } else { 128 };
+        // a bare address is a network of one
+        let prefix_len = match parts.next() {
+            Some(len) => len.parse::<u8>().map_err(|_| err())?,
+            None => max,
+        };
+        if prefix_len > max {
+            return Err(err());
+        }
+        Ok(Cidr { addr, prefix_len })
+    }
+}
+
+impl<'de> Deserialize<'de> for Cidr {
+    fn deserialize<D>(deserializer: D) -> Result<Cidr, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+// representations
+
+#[derive(Debug, Deserialize)]
+pub struct MetaInfo {
+    pub verifiable_password_authentication: bool,
+    pub ssh_key_fingerprints: Option<SshKeyFingerprints>,
+    #[serde(default)]
+    pub ssh_keys: Vec<String>,
+    #[serde(default)]
+    pub hooks: Vec<Cidr>,
+    #[serde(default)]
+    pub web: Vec<Cidr>,
+    #[serde(default)]
+    pub api: Vec<Cidr>,
+    #[serde(default)]
+    pub git: Vec<Cidr>,
+    #[serde(default)]
+    pub github_enterprise_importer: Vec<Cidr>,
+    #[serde(default)]
+    pub packages: Vec<Cidr>,
+    #[serde(default)]
+    pub pages: Vec<Cidr>,
+    #[serde(default)]
+    pub importer: Vec<Cidr>,
+    #[serde(default)]
+    pub actions: Vec<Cidr>,
+    #[serde(default)]
+    pub actions_macos: Vec<Cidr>,
+    #[serde(default)]
+    pub dependabot: Vec<Cidr>,
+    #[serde(default)]
+    pub copilot: Vec<Cidr>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SshKeyFingerprints {
+    #[serde(rename = "SHA256_RSA")]
+    pub sha256_rsa: Option<String>,
+    #[serde(rename = "SHA256_DSA")]
+    pub sha256_dsa: Option<String>,
+    #[serde(rename = "SHA256_ECDSA")]
+    pub sha256_ecdsa: Option<String>,
+    #[serde(rename = "SHA256_ED25519")]
+    pub sha256_ed25519: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn parse_cidr() {
+        let cidr = "192.30.252.0/22".parse::<Cidr>().unwrap();
+        assert_eq!(cidr.prefix_len, 22);
+        assert!(cidr.contains(&"192.30.255.1".parse().unwrap()));
+        assert!(!cidr.contains(&"192.30.248.1".parse().unwrap()));
+        assert_eq!(cidr.to_string(), "192.30.252.0/22");
+        assert!("2a0a:a440::/29".parse::<Cidr>().is_ok());
+        assert!("192.30.252.0/33".parse::<Cidr>().is_err());
+    }
+
+    #[test]
+    fn deserialize_meta() {
+        let meta = serde_json::from_str::<MetaInfo>(
+            r#"{
+                "verifiable_password_authentication":true,
+                "ssh_key_fingerprints":{"SHA256_ED25519":"+DiY3wvvV6TuJJhbpZisF/zLDA0zPMSvHdkr4UvCOqU"},
+                "hooks":["192.30.252.0/22"],
+                "git":["192.30.252.0/22","2a0a:a440::/29"]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(meta.git.len(), 2);
+        assert!(meta.web.is_empty());
+    }
+}
@@ -0,0 +1,107 @@
+//! Metrics interface
+//!
+//! A `Metrics` implementation registered on a `Github` client is notified
+//! once every request completes, with the family of the endpoint it was sent
+//! to, the response status and how long the request took. Endpoint families,
+//! like `repos/issues` or `orgs/members`, omit owners, names and ids so they
+//! are suitable as low cardinality labels of counters and histograms.
+//!
+//! ```no_run
+//! extern crate hubcaps;
+//! extern crate hyper;
+//!
+//! use std::time::Duration;
+//!
+//! use hubcaps::metrics::Metrics;
+//! use hubcaps::Github;
+//! use hyper::{Method, StatusCode};
+//!
+//! #[derive(Debug)]
+//! struct Log;
+//!
+//! impl Metrics for Log {
+//!     fn on_request(
+//!         &self,
+//!         method: &Method,
+//!         endpoint: &str,
+//!         status: Option<StatusCode>,
+//!         duration: Duration,
+//!     ) {
+//!         println!("{} {} {:?} took {:?}", method, endpoint, status, duration);
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let github = Github::builder()
+//!         .agent("my-cool-user-agent/0.1.0")
+//!         .metrics(Log)
+//!         .build()
+//!         .unwrap();
+//! }
+//! ```
+
+use std::fmt::Debug;
+use std::time::Duration;
+
+use hyper::{Method, StatusCode};
+
+/// Receives the outcome of every request a client sends
+pub trait Metrics: Debug + Send + Sync {
+    /// invoked once a request completes. `status` is `None` when no response
+    /// was received, for instance when the connection failed. `duration` is
+    /// measured until the response headers were received
+    fn on_request(
+        &self,
+        method: &Method,
+        endpoint: &str,
+        status: Option<StatusCode>,
+        duration: Duration,
+    );
+}
+
+/// the family of the endpoint at `path`: its top level resource followed by
+/// the first resource nested under an owner, like `repos/pulls` for
+/// `/repos/softprops/hubcaps/pulls/1/files`
+pub fn endpoint_family(path: &str) -> String {
+    // Github enterprise serves the api under /api/v3 and /api/graphql
+    let path = path
+        .trim_start_matches("/api/v3")
+        .trim_start_matches("/api");
+    let mut segments = path.split('/').filter(|s| !s.is_empty());
+    let resource = match segments.next() {
+        Some(resource) => resource,
+        None => return "/".into(),
+    };
+    let skip = match resource {
+        "repos" => 2,
+        "orgs" | "users" | "enterprises" | "gists" | "teams" | "installations" => 1,
+        _ => return resource.into(),
+    };
+    match segments.nth(skip) {
+        Some(nested) => format!("{}/{}", resource, nested),
+        None => resource.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn families() {
+        assert_eq!(endpoint_family("/"), "/");
+        assert_eq!(endpoint_family("/user/repos"), "user");
+        assert_eq!(endpoint_family("/repos/softprops/hubcaps"), "repos");
+        assert_eq!(
+            endpoint_family("/repos/softprops/hubcaps/pulls/1/files"),
+            "repos/pulls"
+        );
+        assert_eq!(endpoint_family("/orgs/rust-lang/members"), "orgs/members");
+        assert_eq!(
+            endpoint_family("/api/v3/repos/softprops/hubcaps/pulls/1/files"),
+            "repos/pulls"
+        );
+        assert_eq!(endpoint_family("/api/v3"), "/");
+        assert_eq!(endpoint_family("/api/graphql"), "graphql");
+    }
+}
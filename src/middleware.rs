@@ -0,0 +1,51 @@
+//! Middleware interface
+//!
+//! Middleware registered on a `Github` client sees every request before it
+//! is sent and every response as soon as its headers arrive. Use it to add
+//! custom headers, write audit logs or record metrics without patching this
+//! crate.
+//!
+//! ```no_run
+//! extern crate hubcaps;
+//! extern crate hyper;
+//!
+//! use hubcaps::middleware::Middleware;
+//! use hubcaps::Github;
+//! use hyper::{Body, Request, Response};
+//!
+//! #[derive(Debug)]
+//! struct Audit;
+//!
+//! impl Middleware for Audit {
+//!     fn before(&self, request: &mut Request<Body>) {
+//!         request
+//!             .headers_mut()
+//!             .insert("x-audit-origin", "billing-service".parse().unwrap());
+//!     }
+//!
+//!     fn after(&self, response: &Response<Body>) {
+//!         println!("github responded {}", response.status());
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let github = Github::builder()
+//!         .agent("my-cool-user-agent/0.1.0")
+//!         .middleware(Audit)
+//!         .build()
+//!         .unwrap();
+//! }
+//! ```
+
+use std::fmt::Debug;
+
+use hyper::{Body, Request, Response};
+
+/// Hooks invoked around every request a client sends
+pub trait Middleware: Debug + Send + Sync {
+    /// invoked with each request before it is sent
+    fn before(&self, _request: &mut Request<Body>) {}
+
+    /// invoked with each response once its headers have been received
+    fn after(&self, _response: &Response<Body>) {}
+}
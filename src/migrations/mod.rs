@@ -0,0 +1,268 @@
+//! Migrations interface
+//!
+//! Migrations export the repositories of an organization or user, along with
+//! their metadata, as an archive. See the [github docs](https://docs.github.com/en/rest/migrations)
+//! for more information
+
+use std::fmt;
+
+use futures::future;
+use hyper::client::connect::Connect;
+use hyper::Chunk;
+use serde_json;
+
+use repositories::Repo;
+use users::User;
+use {unfold, Future, Github, Stream};
+
+fn identity<T>(x: T) -> T {
+    x
+}
+
+/// The state of a migration
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MigrationState {
+    Pending,
+    Exporting,
+    Exported,
+    Failed,
+}
+
+impl fmt::Display for MigrationState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MigrationState::Pending => "pending",
+            MigrationState::Exporting => "exporting",
+            MigrationState::Exported => "exported",
+            MigrationState::Failed => "failed",
+        }
+        .fmt(f)
+    }
+}
+
+/// Provides access to the migrations of an organization or the authenticated user
+pub struct Migrations<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    path: String,
+}
+
+impl<C: Clone + Connect + 'static> Migrations<C> {
+    #[doc(hidden)]
+    pub fn new<P>(github: Github<C>, path: P) -> Self
+    where
+        P: Into<String>,
+    {
+        Migrations {
+            github,
+            path: path.into(),
+        }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!("{}{}", self.path, more)
+    }
+
+    /// start a migration, exporting the given repositories
+    pub fn start(&self, options: &MigrationOptions) -> Future<Migration> {
+        self.github.post(&self.path(""), json!(options))
+    }
+
+    /// list the most recent migrations
+    pub fn list(&self) -> Future<Vec<Migration>> {
+        self.github.get(&self.path(""))
+    }
+
+    /// provides a stream over all pages of migrations
+    pub fn iter(&self) -> Stream<Migration> {
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&self.path("")),
+            identity,
+        )
+    }
+
+    /// get a migration by id. poll this until its state is `Exported`
+    /// before downloading its archive
+    pub fn get(&self, id: u64) -> Future<Migration> {
+        self.github.get(&self.path(&format!("/{}", id)))
+    }
+
+    /// list the repositories a migration exports
+    pub fn repositories(&self, id: u64) -> Future<Vec<Repo>> {
+        self.github
+            .get(&self.path(&format!("/{}/repositories", id)))
+    }
+
+    /// provides a stream over all pages of the repositories a migration exports
+    pub fn iter_repositories(&self, id: u64) -> Stream<Repo> {
+        unfold(
+            self.github.clone(),
+            self.github
+                .get_pages(&self.path(&format!("/{}/repositories", id))),
+            identity,
+        )
+    }
+
+    /// download the gzipped tar archive of an exported migration
+    pub fn archive(&self, id: u64) -> Stream<Chunk> {
+        self.github
+            .download(&self.path(&format!("/{}/archive", id)))
+    }
+
+    /// delete the archive of a migration. archives are otherwise
+    /// deleted automatically after seven days
+    pub fn delete_archive(&self, id: u64) -> Future<()> {
+        self.github.delete(&self.path(&format!("/{}/archive", id)))
+    }
+
+    /// unlock a repository locked by a migration once you are done with it
+    pub fn unlock(&self, id: u64, repo_name: &str) -> Future<()> {
+        self.github
+            .delete(&self.path(&format!("/{}/repos/{}/lock", id, repo_name)))
+    }
+}
+
+// representations
+
+#[derive(Debug, Deserialize)]
+pub struct Migration {
+    pub id: u64,
+    pub node_id: String,
+    pub owner: Option<User>,
+    pub guid: String,
+    pub state: MigrationState,
+    pub lock_repositories: bool,
+    pub exclude_metadata: bool,
+    pub exclude_git_data: bool,
+    pub exclude_attachments: bool,
+    pub exclude_releases: bool,
+    pub exclude_owner_projects: bool,
+    pub org_metadata_only: bool,
+    pub repositories: Vec<Repo>,
+    pub url: String,
+    pub archive_url: Option<String>,
+    pub exclude: Option<Vec<String>>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// options for starting a migration
+#[derive(Debug, Default, Serialize)]
+pub struct MigrationOptions {
+    pub repositories: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lock_repositories: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude_metadata: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude_git_data: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude_attachments: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude_releases: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude_owner_projects: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub org_metadata_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude: Option<Vec<String>>,
+}
+
+impl MigrationOptions {
+    /// repositories are named by `name` for organization migrations
+    /// and by `owner/name` for user migrations
+    pub fn builder<R>(repositories: Vec<R>) -> MigrationOptionsBuilder
+    where
+        R: Into<String>,
+    {
+        MigrationOptionsBuilder(MigrationOptions {
+            repositories: repositories.into_iter().map(|r| r.into()).collect(),
+            ..Default::default()
+        })
+    }
+}
+
+pub struct MigrationOptionsBuilder(MigrationOptions);
+
+impl MigrationOptionsBuilder {
+    /// lock the repositories while they are being migrated
+    pub fn lock_repositories(&mut self, lock: bool) -> &mut Self {
+        self.0.lock_repositories = Some(lock);
+        self
+    }
+
+    /// exclude issues, pull requests and other metadata
+    pub fn exclude_metadata(&mut self, exclude: bool) -> &mut Self {
+        self.0.exclude_metadata = Some(exclude);
+        self
+    }
+
+    pub fn exclude_git_data(&mut self, exclude: bool) -> &mut Self {
+        self.0.exclude_git_data = Some(exclude);
+        self
+    }
+
+    pub fn exclude_attachments(&mut self, exclude: bool) -> &mut Self {
+        self.0.exclude_attachments = Some(exclude);
+        self
+    }
+
+    pub fn exclude_releases(&mut self, exclude: bool) -> &mut Self {
+        self.0.exclude_releases = Some(exclude);
+        self
+    }
+
+    pub fn exclude_owner_projects(&mut self, exclude: bool) -> &mut Self {
+        self.0.exclude_owner_projects = Some(exclude);
+        self
+    }
+
+    /// only export organization metadata. repositories must be empty
+    pub fn org_metadata_only(&mut self, only: bool) -> &mut Self {
+        self.0.org_metadata_only = Some(only);
+        self
+    }
+
+    /// related items to exclude, currently only `repositories`
+    pub fn exclude<E>(&mut self, exclude: Vec<E>) -> &mut Self
+    where
+        E: Into<String>,
+    {
+        self.0.exclude = Some(exclude.into_iter().map(|e| e.into()).collect());
+        self
+    }
+
+    pub fn build(&self) -> MigrationOptions {
+        MigrationOptions {
+            repositories: self.0.repositories.clone(),
+            lock_repositories: self.0.lock_repositories,
+            exclude_metadata: self.0.exclude_metadata,
+            exclude_git_data: self.0.exclude_git_data,
+            exclude_attachments: self.0.exclude_attachments,
+            exclude_releases: self.0.exclude_releases,
+            exclude_owner_projects: self.0.exclude_owner_projects,
+            org_metadata_only: self.0.org_metadata_only,
+            exclude: self.0.exclude.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn migration_reqs() {
+        let options = MigrationOptions::builder(vec!["hubcaps"])
+            .lock_repositories(true)
+            .build();
+        assert_eq!(
+            serde_json::to_string(&options).unwrap(),
+            r#"{"repositories":["hubcaps"],"lock_repositories":true}"#
+        )
+    }
+}
@@ -3,13 +3,15 @@ extern crate serde_json;
 
 use std::collections::HashMap;
 
-use futures::future;
+use futures::{stream, Stream as StdStream};
 use hyper::client::connect::Connect;
 use url::form_urlencoded;
 
+use poll;
 use users::User;
 use Future;
 use Github;
+use Stream;
 
 /// Provides access to notifications.
 /// See the [github docs](https://developer.github.com/v3/activity/notifications/)
@@ -64,6 +66,41 @@ impl<C: Clone + Connect + 'static> Notifications<C> {
         self.github.get(&uri.join("?"))
     }
 
+    /// Poll the authenticated user's notifications, yielding each one when
+    /// it first appears and again whenever it is updated.
+    ///
+    /// Polls are conditional requests, which Github answers without counting
+    /// them against the rate limit when nothing changed, and are spaced out by
+    /// the interval Github asks for in its `X-Poll-Interval` header.
+    ///
+    /// See the [github docs](https://developer.github.com/v3/activity/notifications/)
+    /// for more information.
+    pub fn poll(&self, options: &ThreadListOptions) -> Stream<Thread> {
+        let mut uri = vec!["/notifications".into()];
+        if let Some(query) = options.serialize() {
+            uri.push(query);
+        }
+        let mut seen = HashMap::new();
+        Box::new(
+            poll::pages(self.github.clone(), uri.join("?"))
+                .map(move |threads: Vec<Thread>| {
+                    // only the threads of the latest response are remembered,
+                    // so long running polls don't accumulate every thread
+                    let latest = threads
+                        .iter()
+                        .map(|thread| (thread.id.clone(), thread.updated_at.clone()))
+                        .collect::<HashMap<_, _>>();
+                    let updated = threads
+                        .into_iter()
+                        .filter(|thread| seen.get(&thread.id) != Some(&thread.updated_at))
+                        .collect::<Vec<_>>();
+                    seen = latest;
+                    stream::iter_ok(updated)
+                })
+                .flatten(),
+        )
+    }
+
     /// Mark notifications as read. Default: `now`
     ///
     /// See the [github docs](https://developer.github.com/v3/activity/notifications/#mark-as-read)
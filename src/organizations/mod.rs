@@ -1,10 +1,33 @@
 //! Organizations interface
 
+use futures::{Future as StdFuture, Stream as StdStream};
 use hyper::client::connect::Connect;
 
-use repositories::OrgRepositories;
+use actions::OrgActions;
+use audit_log::AuditLog;
+use billing::Billing;
+use code_scanning::OrgCodeScanning;
+use copilot::Copilot;
+use custom_properties::OrgCustomProperties;
+use dependabot::OrgDependabot;
+use events::Events;
+use members::OrgMembers;
+use migrations::Migrations;
+use packages::Packages;
+use personal_access_tokens::PersonalAccessTokens;
+use projects::Projects;
+use repo_policy::{self, RepoDrift, RepoPolicy};
+use repositories::{OrgRepoListOptions, OrgRepoType, OrgRepositories, Repo, RepoSlug};
+use repository_roles::RepositoryRoles;
+use rulesets::Rulesets;
+use scim::Scim;
+use search::{self, OpenPrSearchOptions, PrSummary};
+use secret_scanning::OrgSecretScanning;
+use security_advisories::OrgSecurityAdvisories;
+use stats::{self, WeeklyCommits};
+use team_sync::TeamSync;
 use teams::OrgTeams;
-use {Future, Github};
+use {Future, Github, Stream};
 
 /// Provides access to label operations available for an individual organization
 pub struct Organization<C>
@@ -32,10 +55,184 @@ impl<C: Clone + Connect + 'static> Organization<C> {
         OrgTeams::new(self.github.clone(), self.org.clone())
     }
 
+    /// returns a reference to an interface for connecting teams to
+    /// identity provider groups and external groups
+    pub fn team_sync(&self) -> TeamSync<C> {
+        TeamSync::new(self.github.clone(), self.org.clone())
+    }
+
+    /// returns a reference to an interface for managing the
+    /// organization's members and their roles
+    pub fn members(&self) -> OrgMembers<C> {
+        OrgMembers::new(self.github.clone(), self.org.clone())
+    }
+
     /// returns a reference to an interface for repo operations
     pub fn repos(&self) -> OrgRepositories<C> {
         OrgRepositories::new(self.github.clone(), self.org.clone())
     }
+
+    /// provides a stream over all of the organization's repositories,
+    /// fetching every page of the listing and keeping those which match
+    /// `options`
+    pub fn all_repos(&self, options: &AllReposOptions) -> Stream<Repo> {
+        let mut list = OrgRepoListOptions::builder();
+        list.per_page(100);
+        if let Some(repo_type) = options.repo_type {
+            list.repo_type(repo_type);
+        }
+        let archived = options.archived;
+        let language = options.language.clone();
+        Box::new(
+            self.repos()
+                .iter(&list.build())
+                .filter(move |repo| matches(repo, archived, language.as_ref())),
+        )
+    }
+
+    /// the weekly commit activity of the last year summed across the
+    /// organization's repositories matching `options`, oldest week first
+    pub fn commit_activity(&self, options: &AllReposOptions) -> Future<Vec<WeeklyCommits>> {
+        let github = self.github.clone();
+        Box::new(
+            self.all_repos(options)
+                .map(|repo| RepoSlug {
+                    owner: repo.owner.login,
+                    repo: repo.name,
+                })
+                .collect()
+                .and_then(move |repos| stats::commit_activity(&github, repos)),
+        )
+    }
+
+    /// provides a stream over summaries of the organization's open pull
+    /// requests matching `options`, across all pages of search results
+    pub fn search_open_prs(&self, options: &OpenPrSearchOptions) -> Stream<PrSummary> {
+        search::search_open_prs(self.github.search(), &self.org, options)
+    }
+
+    /// provides a stream over the organization's unarchived repositories
+    /// whose settings drifted from `policy`, without changing them
+    pub fn repo_settings_drift(&self, policy: &RepoPolicy) -> Stream<RepoDrift> {
+        repo_policy::reconcile(
+            self.github.clone(),
+            self.all_repos(&AllReposOptions::default()),
+            policy.clone(),
+            false,
+        )
+    }
+
+    /// update the settings of the organization's unarchived repositories
+    /// to match `policy`, yielding the drift of each repository updated
+    pub fn enforce_repo_settings(&self, policy: &RepoPolicy) -> Stream<RepoDrift> {
+        repo_policy::reconcile(
+            self.github.clone(),
+            self.all_repos(&AllReposOptions::default()),
+            policy.clone(),
+            true,
+        )
+    }
+
+    /// returns a reference to an interface for reviewing and revoking the
+    /// fine-grained personal access tokens which access the organization
+    pub fn personal_access_tokens(&self) -> PersonalAccessTokens<C> {
+        PersonalAccessTokens::new(self.github.clone(), self.org.clone())
+    }
+
+    /// returns a reference to an interface for custom repository roles
+    pub fn repository_roles(&self) -> RepositoryRoles<C> {
+        RepositoryRoles::new(self.github.clone(), self.org.clone())
+    }
+
+    /// returns a reference to an interface for code scanning alerts
+    /// across the organization's repositories
+    pub fn code_scanning(&self) -> OrgCodeScanning<C> {
+        OrgCodeScanning::new(self.github.clone(), self.org.clone())
+    }
+
+    /// returns a reference to an interface for secret scanning alerts
+    /// across the organization's repositories
+    pub fn secret_scanning(&self) -> OrgSecretScanning<C> {
+        OrgSecretScanning::new(self.github.clone(), format!("/orgs/{}", self.org))
+    }
+
+    /// returns a reference to an interface for the security advisories
+    /// of the organization's repositories
+    pub fn security_advisories(&self) -> OrgSecurityAdvisories<C> {
+        OrgSecurityAdvisories::new(self.github.clone(), self.org.clone())
+    }
+
+    /// returns a reference to an interface for Github Actions
+    /// operations, like managing self-hosted runner groups
+    pub fn actions(&self) -> OrgActions<C> {
+        OrgActions::new(self.github.clone(), self.org.clone())
+    }
+
+    /// returns a reference to an interface for Dependabot alerts
+    /// and organization secrets
+    pub fn dependabot(&self) -> OrgDependabot<C> {
+        OrgDependabot::new(self.github.clone(), self.org.clone())
+    }
+
+    /// returns a reference to an interface for exporting the
+    /// organization's repositories
+    pub fn migrations(&self) -> Migrations<C> {
+        Migrations::new(
+            self.github.clone(),
+            format!("/orgs/{}/migrations", self.org),
+        )
+    }
+
+    /// returns a reference to an interface for managing the
+    /// organization's Copilot seats
+    pub fn copilot(&self) -> Copilot<C> {
+        Copilot::new(self.github.clone(), self.org.clone())
+    }
+
+    /// returns a reference to the organization's public events
+    pub fn events(&self) -> Events<C> {
+        Events::new(self.github.clone(), format!("/orgs/{}/events", self.org))
+    }
+
+    /// returns a reference to an interface for the organization's packages
+    pub fn packages(&self) -> Packages<C> {
+        Packages::new(self.github.clone(), format!("/orgs/{}", self.org))
+    }
+
+    /// returns a reference to the organization's audit log
+    pub fn audit_log(&self) -> AuditLog<C> {
+        AuditLog::new(self.github.clone(), self.org.clone())
+    }
+
+    /// returns a reference to an interface for the organization's billing usage
+    pub fn billing(&self) -> Billing<C> {
+        Billing::new(self.github.clone(), format!("/orgs/{}", self.org))
+    }
+
+    /// returns a reference to an interface for the organization's rulesets
+    pub fn rulesets(&self) -> Rulesets<C> {
+        Rulesets::new(self.github.clone(), format!("/orgs/{}", self.org))
+    }
+
+    /// returns a reference to an interface for the organization's custom
+    /// property schema and the property values of its repositories
+    pub fn custom_properties(&self) -> OrgCustomProperties<C> {
+        OrgCustomProperties::new(self.github.clone(), self.org.clone())
+    }
+
+    /// returns a reference to an interface for the organization's
+    /// SCIM provisioned users
+    pub fn scim(&self) -> Scim<C> {
+        Scim::new(
+            self.github.clone(),
+            format!("/scim/v2/organizations/{}", self.org),
+        )
+    }
+
+    /// returns a reference to an interface for classic project operations
+    pub fn projects(&self) -> Projects<C> {
+        Projects::new(self.github.clone(), format!("/orgs/{}/projects", self.org))
+    }
 }
 
 pub struct Organizations<C>
@@ -92,6 +289,64 @@ impl<C: Clone + Connect + 'static> UserOrganizations<C> {
     }
 }
 
+/// whether `repo` has the given archived state and primary language, when set
+fn matches(repo: &Repo, archived: Option<bool>, language: Option<&String>) -> bool {
+    archived.map_or(true, |archived| repo.archived == archived)
+        && language.map_or(true, |language| {
+            repo.language
+                .as_ref()
+                .map_or(false, |l| l.eq_ignore_ascii_case(language))
+        })
+}
+
+/// Filters for `Organization::all_repos`
+#[derive(Default)]
+pub struct AllReposOptions {
+    repo_type: Option<OrgRepoType>,
+    archived: Option<bool>,
+    language: Option<String>,
+}
+
+impl AllReposOptions {
+    pub fn builder() -> AllReposOptionsBuilder {
+        AllReposOptionsBuilder::default()
+    }
+}
+
+#[derive(Default)]
+pub struct AllReposOptionsBuilder(AllReposOptions);
+
+impl AllReposOptionsBuilder {
+    /// only list repositories of this type
+    pub fn repo_type(&mut self, repo_type: OrgRepoType) -> &mut Self {
+        self.0.repo_type = Some(repo_type);
+        self
+    }
+
+    /// only keep archived, or unarchived, repositories
+    pub fn archived(&mut self, archived: bool) -> &mut Self {
+        self.0.archived = Some(archived);
+        self
+    }
+
+    /// only keep repositories whose primary language is `language`, ignoring case
+    pub fn language<L>(&mut self, language: L) -> &mut Self
+    where
+        L: Into<String>,
+    {
+        self.0.language = Some(language.into());
+        self
+    }
+
+    pub fn build(&self) -> AllReposOptions {
+        AllReposOptions {
+            repo_type: self.0.repo_type,
+            archived: self.0.archived,
+            language: self.0.language.clone(),
+        }
+    }
+}
+
 // representations
 
 #[derive(Debug, Deserialize)]
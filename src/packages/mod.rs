@@ -0,0 +1,384 @@
+//! Packages interface
+//!
+//! See the [github docs](https://docs.github.com/en/rest/packages) for more information
+
+use std::collections::HashMap;
+use std::fmt;
+
+use hyper::client::connect::Connect;
+use url::form_urlencoded;
+
+use code_scanning::AlertRepository;
+use users::User;
+use {unfold, Future, Github, Stream};
+
+fn identity<T>(x: T) -> T {
+    x
+}
+
+/// The registry a package is published to
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PackageType {
+    Npm,
+    Maven,
+    Rubygems,
+    Docker,
+    Nuget,
+    Container,
+}
+
+impl fmt::Display for PackageType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PackageType::Npm => "npm",
+            PackageType::Maven => "maven",
+            PackageType::Rubygems => "rubygems",
+            PackageType::Docker => "docker",
+            PackageType::Nuget => "nuget",
+            PackageType::Container => "container",
+        }
+        .fmt(f)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Visibility {
+    Public,
+    Private,
+    Internal,
+}
+
+impl fmt::Display for Visibility {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Visibility::Public => "public",
+            Visibility::Private => "private",
+            Visibility::Internal => "internal",
+        }
+        .fmt(f)
+    }
+}
+
+/// The state of a package version
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VersionState {
+    Active,
+    Deleted,
+}
+
+impl fmt::Display for VersionState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            VersionState::Active => "active",
+            VersionState::Deleted => "deleted",
+        }
+        .fmt(f)
+    }
+}
+
+/// Provides access to the packages of an organization, a user or the
+/// authenticated user
+pub struct Packages<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    owner: String,
+}
+
+impl<C: Clone + Connect + 'static> Packages<C> {
+    #[doc(hidden)]
+    pub fn new<O>(github: Github<C>, owner: O) -> Self
+    where
+        O: Into<String>,
+    {
+        Packages {
+            github,
+            owner: owner.into(),
+        }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!("{}/packages{}", self.owner, more)
+    }
+
+    fn package_path(&self, package_type: PackageType, name: &str, more: &str) -> String {
+        self.path(&format!("/{}/{}{}", package_type, name, more))
+    }
+
+    fn list_path(&self, package_type: PackageType, options: &PackageListOptions) -> String {
+        let mut uri = vec![self.path(&format!("?package_type={}", package_type))];
+        if let Some(query) = options.serialize() {
+            uri.push(query);
+        }
+        uri.join("&")
+    }
+
+    /// list packages of a given type
+    pub fn list(
+        &self,
+        package_type: PackageType,
+        options: &PackageListOptions,
+    ) -> Future<Vec<Package>> {
+        self.github.get(&self.list_path(package_type, options))
+    }
+
+    /// provides a stream over all pages of packages of a given type
+    pub fn iter(&self, package_type: PackageType, options: &PackageListOptions) -> Stream<Package> {
+        unfold(
+            self.github.clone(),
+            self.github
+                .get_pages(&self.list_path(package_type, options)),
+            identity,
+        )
+    }
+
+    /// get a package by type and name
+    pub fn get(&self, package_type: PackageType, name: &str) -> Future<Package> {
+        self.github.get(&self.package_path(package_type, name, ""))
+    }
+
+    /// delete a package. packages with more than 5,000 downloads can not be deleted
+    pub fn delete(&self, package_type: PackageType, name: &str) -> Future<()> {
+        self.github
+            .delete(&self.package_path(package_type, name, ""))
+    }
+
+    /// restore a package deleted within the last 30 days. the token
+    /// is only required when the package's namespace was reused
+    pub fn restore(
+        &self,
+        package_type: PackageType,
+        name: &str,
+        token: Option<&str>,
+    ) -> Future<()> {
+        let more = match token {
+            Some(token) => format!(
+                "/restore?{}",
+                form_urlencoded::Serializer::new(String::new())
+                    .append_pair("token", token)
+                    .finish()
+            ),
+            None => "/restore".to_owned(),
+        };
+        self.github
+            .post_no_response(&self.package_path(package_type, name, &more), Vec::new())
+    }
+
+    /// list the versions of a package
+    pub fn versions(
+        &self,
+        package_type: PackageType,
+        name: &str,
+        options: &VersionListOptions,
+    ) -> Future<Vec<PackageVersion>> {
+        let mut uri = vec![self.package_path(package_type, name, "/versions")];
+        if let Some(query) = options.serialize() {
+            uri.push(query);
+        }
+        self.github.get(&uri.join("?"))
+    }
+
+    /// provides a stream over all pages of the versions of a package
+    pub fn iter_versions(
+        &self,
+        package_type: PackageType,
+        name: &str,
+        options: &VersionListOptions,
+    ) -> Stream<PackageVersion> {
+        let mut uri = vec![self.package_path(package_type, name, "/versions")];
+        if let Some(query) = options.serialize() {
+            uri.push(query);
+        }
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&uri.join("?")),
+            identity,
+        )
+    }
+
+    /// get a version of a package by id
+    pub fn version(
+        &self,
+        package_type: PackageType,
+        name: &str,
+        version_id: u64,
+    ) -> Future<PackageVersion> {
+        self.github.get(&self.package_path(
+            package_type,
+            name,
+            &format!("/versions/{}", version_id),
+        ))
+    }
+
+    /// delete a version of a package. the last version of a
+    /// package can not be deleted, delete the package instead
+    pub fn delete_version(
+        &self,
+        package_type: PackageType,
+        name: &str,
+        version_id: u64,
+    ) -> Future<()> {
+        self.github.delete(&self.package_path(
+            package_type,
+            name,
+            &format!("/versions/{}", version_id),
+        ))
+    }
+
+    /// restore a version of a package deleted within the last 30 days
+    pub fn restore_version(
+        &self,
+        package_type: PackageType,
+        name: &str,
+        version_id: u64,
+    ) -> Future<()> {
+        self.github.post_no_response(
+            &self.package_path(
+                package_type,
+                name,
+                &format!("/versions/{}/restore", version_id),
+            ),
+            Vec::new(),
+        )
+    }
+}
+
+// representations
+
+#[derive(Debug, Deserialize)]
+pub struct Package {
+    pub id: u64,
+    pub name: String,
+    pub package_type: PackageType,
+    pub url: String,
+    pub html_url: String,
+    pub version_count: u64,
+    pub visibility: Visibility,
+    pub owner: Option<User>,
+    pub repository: Option<AlertRepository>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PackageVersion {
+    pub id: u64,
+    pub name: String,
+    pub url: String,
+    pub package_html_url: String,
+    pub html_url: Option<String>,
+    pub license: Option<String>,
+    pub description: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    pub deleted_at: Option<String>,
+    pub metadata: Option<VersionMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VersionMetadata {
+    pub package_type: PackageType,
+    pub container: Option<ContainerMetadata>,
+    pub docker: Option<DockerMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ContainerMetadata {
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DockerMetadata {
+    pub tag: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct PackageListOptions {
+    params: HashMap<&'static str, String>,
+}
+
+impl PackageListOptions {
+    pub fn builder() -> PackageListOptionsBuilder {
+        PackageListOptionsBuilder::default()
+    }
+
+    /// serialize options as a string. returns None if no options are defined
+    pub fn serialize(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded)
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct PackageListOptionsBuilder(PackageListOptions);
+
+impl PackageListOptionsBuilder {
+    pub fn visibility(&mut self, visibility: Visibility) -> &mut Self {
+        self.0.params.insert("visibility", visibility.to_string());
+        self
+    }
+
+    pub fn per_page(&mut self, n: usize) -> &mut Self {
+        self.0.params.insert("per_page", n.to_string());
+        self
+    }
+
+    pub fn build(&self) -> PackageListOptions {
+        PackageListOptions {
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct VersionListOptions {
+    params: HashMap<&'static str, String>,
+}
+
+impl VersionListOptions {
+    pub fn builder() -> VersionListOptionsBuilder {
+        VersionListOptionsBuilder::default()
+    }
+
+    /// serialize options as a string. returns None if no options are defined
+    pub fn serialize(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded)
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct VersionListOptionsBuilder(VersionListOptions);
+
+impl VersionListOptionsBuilder {
+    pub fn state(&mut self, state: VersionState) -> &mut Self {
+        self.0.params.insert("state", state.to_string());
+        self
+    }
+
+    pub fn per_page(&mut self, n: usize) -> &mut Self {
+        self.0.params.insert("per_page", n.to_string());
+        self
+    }
+
+    pub fn build(&self) -> VersionListOptions {
+        VersionListOptions {
+            params: self.0.params.clone(),
+        }
+    }
+}
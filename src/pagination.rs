@@ -0,0 +1,323 @@
+//! Pagination links
+//!
+//! Github links the pages of list responses to each other with a `Link`
+//! header. The typed list interfaces of this crate follow these links on your
+//! behalf. To paginate responses to `Github::request_raw`, follow
+//! `RawResponse::links` instead.
+//!
+//! Long crawls may be interrupted and resumed later with `Github::pages`,
+//! which yields whole pages along with a `ResumeToken` for each. Persist the
+//! token of the last page processed and pass it to `Github::resume_pages` to
+//! carry on from where the crawl left off rather than from the first page.
+//!
+//! ```
+//! # extern crate hubcaps;
+//! # extern crate hyper;
+//! # use hubcaps::pagination::Links;
+//! # use hyper::header::LINK;
+//! # use hyper::HeaderMap;
+//! # fn main() {
+//! let mut headers = HeaderMap::new();
+//! headers.insert(
+//!     LINK,
+//!     r#"<https://api.github.com/user/repos?page=3>; rel="next", <https://api.github.com/user/repos?page=50>; rel="last""#
+//!         .parse()
+//!         .unwrap(),
+//! );
+//! let links = Links::from_headers(&headers).unwrap();
+//! assert_eq!(links.next.unwrap().page, Some(3));
+//! assert_eq!(links.last.unwrap().page, Some(50));
+//! # }
+//! ```
+
+use std::str::FromStr;
+
+use futures::{future, stream, Future as StdFuture};
+use hyper::client::connect::Connect;
+use hyper::header::{ETAG, IF_NONE_MATCH, LINK};
+use hyper::{HeaderMap, Method, StatusCode};
+use hyperx::header::{Link, RelationType};
+use serde::de::DeserializeOwned;
+use serde_json;
+use url::Url;
+
+use {fault_from, Error, Future, Github, RawResponse, Stream};
+
+/// A link to a page of results
+#[derive(Clone, Debug, PartialEq)]
+pub struct PageLink {
+    /// the absolute url of the page
+    pub url: String,
+    /// the page's number, from its `page` query parameter. `None` for
+    /// cursor based pagination
+    pub page: Option<u32>,
+}
+
+impl PageLink {
+    fn new(url: &str) -> Self {
+        let page = Url::parse(url).ok().and_then(|url| {
+            url.query_pairs()
+                .find(|&(ref name, _)| name == "page")
+                .and_then(|(_, value)| value.parse().ok())
+        });
+        PageLink {
+            url: url.to_owned(),
+            page,
+        }
+    }
+
+    /// the path and query of the page, suitable for `Github::request_raw`
+    pub fn path(&self) -> String {
+        match Url::parse(&self.url) {
+            Ok(url) => match url.query() {
+                Some(query) => format!("{}?{}", url.path(), query),
+                None => url.path().to_owned(),
+            },
+            Err(_) => self.url.clone(),
+        }
+    }
+}
+
+/// The pages a response links to
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Links {
+    pub first: Option<PageLink>,
+    pub prev: Option<PageLink>,
+    pub next: Option<PageLink>,
+    pub last: Option<PageLink>,
+}
+
+impl Links {
+    /// parse the links carried by the `Link` header of `headers`, if any
+    pub fn from_headers(headers: &HeaderMap) -> Option<Links> {
+        headers
+            .get(LINK)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse().ok())
+    }
+
+    /// whether this is the last page of results
+    pub fn is_last(&self) -> bool {
+        self.next.is_none()
+    }
+}
+
+impl FromStr for Links {
+    type Err = ();
+
+    /// parse the value of a `Link` header
+    fn from_str(value: &str) -> Result<Links, ()> {
+        let link = value.parse::<Link>().map_err(|_| ())?;
+        let find = |relation: RelationType| {
+            link.values()
+                .iter()
+                .find(|v| v.rel().unwrap_or(&[]).contains(&relation))
+                .map(|v| PageLink::new(v.link()))
+        };
+        Ok(Links {
+            first: find(RelationType::First),
+            prev: find(RelationType::Prev),
+            next: find(RelationType::Next),
+            last: find(RelationType::Last),
+        })
+    }
+}
+
+/// A page of results, along with the token to resume a crawl from once it
+/// has been processed
+#[derive(Debug)]
+pub struct Page<D> {
+    pub items: Vec<D>,
+    pub resume: ResumeToken,
+}
+
+/// The position of a crawl: the url of the last page processed, and the
+/// etag Github reported for it. Tokens may be persisted with serde
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ResumeToken {
+    pub url: String,
+    pub etag: Option<String>,
+}
+
+enum Position {
+    /// fetch the page at this url
+    Page(String),
+    /// revalidate the page a crawl was resumed from, continuing after it if
+    /// it hasn't changed
+    Resume(ResumeToken),
+    Done,
+}
+
+/// streams the pages of results starting at `url`
+pub(crate) fn pages<C, D>(github: Github<C>, url: String) -> Stream<Page<D>>
+where
+    C: Clone + Connect + 'static,
+    D: DeserializeOwned + 'static + Send,
+{
+    unfold_pages(github, Position::Page(url))
+}
+
+/// streams the pages of results following the one `token` was taken from.
+/// when that page changed since, its items may have shifted between pages,
+/// so it's yielded again, before the pages following it
+pub(crate) fn resume_pages<C, D>(github: Github<C>, token: ResumeToken) -> Stream<Page<D>>
+where
+    C: Clone + Connect + 'static,
+    D: DeserializeOwned + 'static + Send,
+{
+    unfold_pages(github, Position::Resume(token))
+}
+
+fn unfold_pages<C, D>(github: Github<C>, position: Position) -> Stream<Page<D>>
+where
+    C: Clone + Connect + 'static,
+    D: DeserializeOwned + 'static + Send,
+{
+    Box::new(stream::unfold(position, move |position| match position {
+        Position::Page(url) => Some(fetch(&github, url)),
+        Position::Resume(token) => Some(revalidate(&github, token)),
+        Position::Done => None,
+    }))
+}
+
+fn fetch<C, D>(github: &Github<C>, url: String) -> Future<(Page<D>, Position)>
+where
+    C: Clone + Connect + 'static,
+    D: DeserializeOwned + 'static + Send,
+{
+    Box::new(
+        github
+            .request_raw(Method::GET, &url, None, HeaderMap::new())
+            .and_then(move |raw| page(url, raw)),
+    )
+}
+
+fn revalidate<C, D>(github: &Github<C>, token: ResumeToken) -> Future<(Page<D>, Position)>
+where
+    C: Clone + Connect + 'static,
+    D: DeserializeOwned + 'static + Send,
+{
+    let mut headers = HeaderMap::new();
+    if let Some(etag) = token.etag.as_ref().and_then(|etag| etag.parse().ok()) {
+        headers.insert(IF_NONE_MATCH, etag);
+    }
+    let github = github.clone();
+    Box::new(
+        github
+            .request_raw(Method::GET, &token.url, None, headers)
+            .and_then(move |raw| -> Future<(Page<D>, Position)> {
+                if raw.status != StatusCode::NOT_MODIFIED {
+                    return Box::new(future::result(page(token.url, raw)));
+                }
+                match raw.links().and_then(|links| links.next) {
+                    Some(next) => fetch(&github, next.url),
+                    // the page is unchanged, but Github didn't say which
+                    // follows it. fetch it again to find out, skipping its
+                    // items
+                    None => Box::new(fetch::<C, D>(&github, token.url).and_then(
+                        move |(page, position)| -> Future<(Page<D>, Position)> {
+                            match position {
+                                Position::Page(next) => fetch(&github, next),
+                                _ => Box::new(future::ok((
+                                    Page {
+                                        items: Vec::new(),
+                                        resume: page.resume,
+                                    },
+                                    Position::Done,
+                                ))),
+                            }
+                        },
+                    )),
+                }
+            }),
+    )
+}
+
+/// the page of results of a response to a request for `url`, and the
+/// position of the page following it
+fn page<D>(url: String, raw: RawResponse) -> Result<(Page<D>, Position), Error>
+where
+    D: DeserializeOwned,
+{
+    if !raw.status.is_success() {
+        return Err(fault_from(raw.status, &raw.body).into());
+    }
+    let items = serde_json::from_slice(&raw.body)?;
+    let position = match raw.links().and_then(|links| links.next) {
+        Some(next) => Position::Page(next.url),
+        None => Position::Done,
+    };
+    let etag = raw
+        .headers
+        .get(ETAG)
+        .and_then(|etag| etag.to_str().ok())
+        .map(str::to_owned);
+    Ok((
+        Page {
+            items,
+            resume: ResumeToken { url, etag },
+        },
+        position,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_links() {
+        let links: Links = r#"<https://api.github.com/repositories/1/issues?per_page=10&page=1>; rel="prev", <https://api.github.com/repositories/1/issues?per_page=10&page=3>; rel="next", <https://api.github.com/repositories/1/issues?per_page=10&page=1>; rel="first""#
+            .parse()
+            .unwrap();
+        assert_eq!(links.prev.unwrap().page, Some(1));
+        assert_eq!(links.first.unwrap().page, Some(1));
+        assert_eq!(links.last, None);
+        let next = links.next.unwrap();
+        assert_eq!(next.page, Some(3));
+        assert_eq!(next.path(), "/repositories/1/issues?per_page=10&page=3");
+    }
+
+    #[test]
+    fn pages_of_responses() {
+        let mut headers = HeaderMap::new();
+        headers.insert(ETAG, r#"W/"abc""#.parse().unwrap());
+        headers.insert(
+            LINK,
+            r#"<https://api.github.com/orgs/rust-lang/repos?page=2>; rel="next""#
+                .parse()
+                .unwrap(),
+        );
+        let raw = RawResponse {
+            status: StatusCode::OK,
+            headers,
+            body: b"[1, 2, 3]".to_vec(),
+        };
+        let url = "https://api.github.com/orgs/rust-lang/repos?page=1".to_owned();
+        let (page, position) = page::<u32>(url.clone(), raw).unwrap();
+        assert_eq!(page.items, vec![1, 2, 3]);
+        assert_eq!(
+            page.resume,
+            ResumeToken {
+                url,
+                etag: Some(r#"W/"abc""#.into()),
+            }
+        );
+        match position {
+            Position::Page(next) => {
+                assert_eq!(next, "https://api.github.com/orgs/rust-lang/repos?page=2")
+            }
+            _ => panic!("expected a next page"),
+        }
+    }
+
+    #[test]
+    fn parse_cursor_links() {
+        let links: Links =
+            r#"<https://api.github.com/orgs/rust-lang/audit-log?after=MTY%3D&before=>; rel="next""#
+                .parse()
+                .unwrap();
+        assert_eq!(links.next.unwrap().page, None);
+        assert!(links.first.is_none());
+    }
+}
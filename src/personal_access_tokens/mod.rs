@@ -0,0 +1,371 @@
+//! Fine-grained personal access tokens interface
+//!
+//! Organizations may require members to request access for fine-grained
+//! personal access tokens before they can use them on the organization's
+//! resources. Organization owners and GitHub Apps with the
+//! `organization_personal_access_token_requests` permission can review these
+//! requests, list the tokens granted access and revoke them. See the
+//! [github docs](https://docs.github.com/en/rest/orgs/personal-access-tokens)
+//! for more information.
+
+use std::collections::HashMap;
+
+use futures::future;
+use hyper::client::connect::Connect;
+use serde_json;
+use url::form_urlencoded;
+
+use users::User;
+use {unfold, Future, Github, Stream};
+
+fn identity<T>(x: T) -> T {
+    x
+}
+
+/// Provides access to the fine-grained personal access tokens of an
+/// organization
+pub struct PersonalAccessTokens<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    org: String,
+}
+
+impl<C: Clone + Connect + 'static> PersonalAccessTokens<C> {
+    #[doc(hidden)]
+    pub fn new<O>(github: Github<C>, org: O) -> Self
+    where
+        O: Into<String>,
+    {
+        PersonalAccessTokens {
+            github,
+            org: org.into(),
+        }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!("/orgs/{}{}", self.org, more)
+    }
+
+    fn query(&self, more: &str, options: &PatListOptions) -> String {
+        let mut uri = vec![self.path(more)];
+        if let Some(query) = options.serialize() {
+            uri.push(query);
+        }
+        uri.join("?")
+    }
+
+    /// list a page of the pending requests for access to the organization
+    pub fn requests(&self, options: &PatListOptions) -> Future<Vec<PatRequest>> {
+        self.github
+            .get(&self.query("/personal-access-token-requests", options))
+    }
+
+    /// provides a stream over all of the pending requests for access to the
+    /// organization
+    pub fn iter_requests(&self, options: &PatListOptions) -> Stream<PatRequest> {
+        unfold(
+            self.github.clone(),
+            self.github
+                .get_pages(&self.query("/personal-access-token-requests", options)),
+            identity,
+        )
+    }
+
+    /// approve or deny a pending request for access to the organization
+    pub fn review_request(&self, id: u64, review: &PatReview) -> Future<()> {
+        self.github.post_no_response(
+            &self.path(&format!("/personal-access-token-requests/{}", id)),
+            json!(review),
+        )
+    }
+
+    /// approve or deny several pending requests for access to the
+    /// organization at once
+    pub fn review_requests(&self, ids: &[u64], review: &PatReview) -> Future<()> {
+        let reviews = PatReviews {
+            pat_request_ids: ids.to_vec(),
+            review,
+        };
+        self.github.post_no_response(
+            &self.path("/personal-access-token-requests"),
+            json!(reviews),
+        )
+    }
+
+    /// provides a stream over the repositories a pending request asks to
+    /// access
+    pub fn request_repositories(&self, id: u64) -> Stream<PatRepository> {
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&self.path(&format!(
+                "/personal-access-token-requests/{}/repositories",
+                id
+            ))),
+            identity,
+        )
+    }
+
+    /// list a page of the tokens granted access to the organization
+    pub fn grants(&self, options: &PatListOptions) -> Future<Vec<PatGrant>> {
+        self.github
+            .get(&self.query("/personal-access-tokens", options))
+    }
+
+    /// provides a stream over all of the tokens granted access to the
+    /// organization
+    pub fn iter_grants(&self, options: &PatListOptions) -> Stream<PatGrant> {
+        unfold(
+            self.github.clone(),
+            self.github
+                .get_pages(&self.query("/personal-access-tokens", options)),
+            identity,
+        )
+    }
+
+    /// provides a stream over the repositories a token was granted access to
+    pub fn grant_repositories(&self, id: u64) -> Stream<PatRepository> {
+        unfold(
+            self.github.clone(),
+            self.github
+                .get_pages(&self.path(&format!("/personal-access-tokens/{}/repositories", id))),
+            identity,
+        )
+    }
+
+    /// revoke a token's access to the organization. its owner may request
+    /// access again
+    pub fn revoke(&self, id: u64) -> Future<()> {
+        let revocation = Revocation {
+            action: "revoke",
+            pat_ids: None,
+        };
+        self.github.post_no_response(
+            &self.path(&format!("/personal-access-tokens/{}", id)),
+            json!(revocation),
+        )
+    }
+
+    /// revoke the access of several tokens to the organization at once
+    pub fn revoke_all(&self, ids: &[u64]) -> Future<()> {
+        let revocation = Revocation {
+            action: "revoke",
+            pat_ids: Some(ids.to_vec()),
+        };
+        self.github
+            .post_no_response(&self.path("/personal-access-tokens"), json!(revocation))
+    }
+}
+
+// representations
+
+string_enum! {
+    /// How a request for access is reviewed
+    pub enum PatRequestAction {
+        Approve => "approve",
+        Deny => "deny",
+    }
+}
+
+/// The review of a request for access
+#[derive(Debug, Serialize)]
+pub struct PatReview {
+    pub action: PatRequestAction,
+    /// shown to the requester
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl PatReview {
+    pub fn approve() -> Self {
+        PatReview {
+            action: PatRequestAction::Approve,
+            reason: None,
+        }
+    }
+
+    pub fn deny() -> Self {
+        PatReview {
+            action: PatRequestAction::Deny,
+            reason: None,
+        }
+    }
+
+    /// explain the review to the requester
+    pub fn reason<R>(mut self, reason: R) -> Self
+    where
+        R: Into<String>,
+    {
+        self.reason = Some(reason.into());
+        self
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct PatReviews<'a> {
+    pat_request_ids: Vec<u64>,
+    #[serde(flatten)]
+    review: &'a PatReview,
+}
+
+#[derive(Debug, Serialize)]
+struct Revocation {
+    action: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pat_ids: Option<Vec<u64>>,
+}
+
+/// The permissions a token requested or was granted, by permission name
+#[derive(Debug, Default, Deserialize)]
+pub struct PatPermissions {
+    #[serde(default)]
+    pub organization: HashMap<String, String>,
+    #[serde(default)]
+    pub repository: HashMap<String, String>,
+    #[serde(default)]
+    pub other: HashMap<String, String>,
+}
+
+/// A pending request for a token's access to an organization
+#[derive(Debug, Deserialize)]
+pub struct PatRequest {
+    pub id: u64,
+    pub reason: Option<String>,
+    pub owner: User,
+    /// `none`, `all` or `subset`
+    pub repository_selection: String,
+    pub repositories_url: String,
+    pub permissions: PatPermissions,
+    pub created_at: String,
+    pub token_id: Option<u64>,
+    pub token_name: Option<String>,
+    pub token_expired: bool,
+    pub token_expires_at: Option<String>,
+    pub token_last_used_at: Option<String>,
+}
+
+/// A token granted access to an organization
+#[derive(Debug, Deserialize)]
+pub struct PatGrant {
+    pub id: u64,
+    pub owner: User,
+    /// `none`, `all` or `subset`
+    pub repository_selection: String,
+    pub repositories_url: String,
+    pub permissions: PatPermissions,
+    pub access_granted_at: String,
+    pub token_id: Option<u64>,
+    pub token_name: Option<String>,
+    pub token_expired: bool,
+    pub token_expires_at: Option<String>,
+    pub token_last_used_at: Option<String>,
+}
+
+/// A repository a token requested or was granted access to
+#[derive(Debug, Deserialize)]
+pub struct PatRepository {
+    pub id: u64,
+    pub name: String,
+    pub full_name: String,
+    pub private: bool,
+    pub html_url: String,
+}
+
+#[derive(Default)]
+pub struct PatListOptions {
+    params: HashMap<&'static str, String>,
+}
+
+impl PatListOptions {
+    pub fn builder() -> PatListOptionsBuilder {
+        PatListOptionsBuilder::default()
+    }
+
+    /// serialize options as a string. returns None if no options are defined
+    pub fn serialize(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded)
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct PatListOptionsBuilder(PatListOptions);
+
+impl PatListOptionsBuilder {
+    pub fn per_page(&mut self, n: usize) -> &mut Self {
+        self.0.params.insert("per_page", n.to_string());
+        self
+    }
+
+    /// only list tokens which requested or were granted access to the
+    /// repository with this name
+    pub fn repository<R>(&mut self, repository: R) -> &mut Self
+    where
+        R: Into<String>,
+    {
+        self.0.params.insert("repository", repository.into());
+        self
+    }
+
+    /// only list tokens which requested or were granted this permission,
+    /// like `issues` or `issues:read`
+    pub fn permission<P>(&mut self, permission: P) -> &mut Self
+    where
+        P: Into<String>,
+    {
+        self.0.params.insert("permission", permission.into());
+        self
+    }
+
+    /// only list tokens last used before this time
+    pub fn last_used_before<T>(&mut self, timestamp: T) -> &mut Self
+    where
+        T: Into<String>,
+    {
+        self.0.params.insert("last_used_before", timestamp.into());
+        self
+    }
+
+    /// only list tokens last used after this time
+    pub fn last_used_after<T>(&mut self, timestamp: T) -> &mut Self
+    where
+        T: Into<String>,
+    {
+        self.0.params.insert("last_used_after", timestamp.into());
+        self
+    }
+
+    pub fn build(&self) -> PatListOptions {
+        PatListOptions {
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize_reviews() {
+        let review = PatReview::deny().reason("Access is not needed");
+        let reviews = PatReviews {
+            pat_request_ids: vec![42, 73],
+            review: &review,
+        };
+        assert_eq!(
+            serde_json::to_value(&reviews).unwrap(),
+            serde_json::from_str::<serde_json::Value>(
+                r#"{"pat_request_ids":[42,73],"action":"deny","reason":"Access is not needed"}"#
+            )
+            .unwrap()
+        );
+    }
+}
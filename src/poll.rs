@@ -0,0 +1,103 @@
+//! Conditional polling of list endpoints
+//!
+//! Github asks clients watching for changes to send `If-None-Match` and
+//! `If-Modified-Since` headers, which are answered with a `304 Not Modified`
+//! that does not count against the rate limit, and to wait between polls for
+//! at least the number of seconds in the `X-Poll-Interval` header.
+
+use std::time::{Duration, Instant};
+
+use futures::{stream, Future as StdFuture};
+use hyper::client::connect::Connect;
+use hyper::header::{HeaderValue, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use hyper::{HeaderMap, Method, StatusCode};
+use serde::de::DeserializeOwned;
+use serde_json;
+use tokio_timer::Delay;
+
+use {fault_from, Error, Github, Stream};
+
+const X_POLL_INTERVAL: &str = "x-poll-interval";
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(60);
+
+struct Poll {
+    etag: Option<HeaderValue>,
+    last_modified: Option<HeaderValue>,
+    wait: Duration,
+}
+
+/// a stream of the contents of the first page of `uri` each time it changes.
+/// the first page is requested immediately, later ones once the poll
+/// interval has passed
+pub fn pages<C, D>(github: Github<C>, uri: String) -> Stream<Vec<D>>
+where
+    C: Clone + Connect + 'static,
+    D: DeserializeOwned + Send + 'static,
+{
+    let initial = Poll {
+        etag: None,
+        last_modified: None,
+        wait: Duration::from_secs(0),
+    };
+    Box::new(stream::unfold(initial, move |state| {
+        let github = github.clone();
+        let uri = uri.clone();
+        Some(
+            Delay::new(Instant::now() + state.wait)
+                .map_err(|err| Error::from(format!("poll timer failed: {}", err)))
+                .and_then(move |_| {
+                    let mut headers = HeaderMap::new();
+                    if let Some(ref etag) = state.etag {
+                        headers.insert(IF_NONE_MATCH, etag.clone());
+                    }
+                    if let Some(ref last_modified) = state.last_modified {
+                        headers.insert(IF_MODIFIED_SINCE, last_modified.clone());
+                    }
+                    github
+                        .request_raw(Method::GET, &uri, None, headers)
+                        .map(move |raw| (raw, state))
+                })
+                .and_then(|(raw, state)| {
+                    let next = Poll {
+                        etag: raw.headers.get(ETAG).cloned().or(state.etag),
+                        last_modified: raw
+                            .headers
+                            .get(LAST_MODIFIED)
+                            .cloned()
+                            .or(state.last_modified),
+                        wait: interval(&raw.headers),
+                    };
+                    if raw.status == StatusCode::NOT_MODIFIED {
+                        return Ok((Vec::new(), next));
+                    }
+                    if !raw.status.is_success() {
+                        return Err(fault_from(raw.status, &raw.body).into());
+                    }
+                    Ok((serde_json::from_slice::<Vec<D>>(&raw.body)?, next))
+                }),
+        )
+    }))
+}
+
+/// the time to wait before polling again, as requested by the `X-Poll-Interval` header
+fn interval(headers: &HeaderMap) -> Duration {
+    headers
+        .get(X_POLL_INTERVAL)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_INTERVAL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn poll_interval() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(interval(&headers), DEFAULT_INTERVAL);
+        headers.insert(X_POLL_INTERVAL, HeaderValue::from_static("120"));
+        assert_eq!(interval(&headers), Duration::from_secs(120));
+    }
+}
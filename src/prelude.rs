@@ -0,0 +1,44 @@
+//! A prelude of the most commonly used types
+//!
+//! Glob import it to bring the client, its credentials and errors, and the
+//! representations and options of the most commonly used resources into scope
+//! in one line.
+//!
+//! ```no_run
+//! extern crate hubcaps;
+//! extern crate tokio;
+//!
+//! use hubcaps::prelude::*;
+//! use tokio::runtime::Runtime;
+//!
+//! fn main() -> Result<()> {
+//!     let mut rt = Runtime::new()?;
+//!     let github = Github::new(
+//!         "my-cool-user-agent/0.1.0",
+//!         Credentials::Token("personal-access-token".into()),
+//!     );
+//!     let issue: Issue = rt.block_on(github.repo("softprops", "hubcaps").issue(1).get())?;
+//!     println!("{}", issue.title);
+//!     Ok(())
+//! }
+//! ```
+
+#[cfg(any(feature = "tls", feature = "rustls-tls"))]
+pub use builder::GithubBuilder;
+pub use comments::{Comment, CommentListOptions, CommentOptions};
+pub use errors::{Error, ErrorKind, Result};
+pub use gists::{Gist, GistOptions};
+pub use ids::{InstallationId, IssueId, IssueNumber, PullId, PullNumber, RepoId};
+pub use issues::{Issue, IssueListOptions, IssueOptions, IssueState, State};
+pub use labels::{Label, LabelEditOptions, LabelOptions};
+pub use organizations::Org;
+pub use pulls::{Pull, PullEditOptions, PullListOptions, PullOptions};
+pub use releases::{Release, ReleaseOptions};
+pub use repositories::{
+    OrgRepoListOptions, Repo, RepoEditOptions, RepoListOptions, RepoOptions, RepoSlug,
+    UserRepoListOptions,
+};
+pub use search::SearchIssuesOptions;
+pub use teams::Team;
+pub use users::{AuthenticatedUser, AuthorAssociation, User};
+pub use {Credentials, Github, RetryPolicy, SortDirection, TransientRetry};
@@ -0,0 +1,795 @@
+//! Projects (classic) interface
+//!
+//! See the [github docs](https://docs.github.com/en/rest/projects) for more information
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use futures::future;
+use hyper::client::connect::Connect;
+use serde_json;
+use url::form_urlencoded;
+
+use users::User;
+use {unfold, Future, Github, Stream};
+
+pub mod v2;
+
+fn identity<T>(x: T) -> T {
+    x
+}
+
+/// The state of a project
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum State {
+    /// Only open projects
+    Open,
+    /// Only closed projects
+    Closed,
+    /// All projects. Only applicable when listing projects
+    All,
+}
+
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            State::Open => "open",
+            State::Closed => "closed",
+            State::All => "all",
+        }
+        .fmt(f)
+    }
+}
+
+impl Default for State {
+    fn default() -> State {
+        State::Open
+    }
+}
+
+/// The permission granted to a collaborator or organization member on a project
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Permission {
+    Read,
+    Write,
+    Admin,
+    None,
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Permission::Read => "read",
+            Permission::Write => "write",
+            Permission::Admin => "admin",
+            Permission::None => "none",
+        }
+        .fmt(f)
+    }
+}
+
+/// Provides access to project operations. Listing and creating
+/// projects is scoped to the repository, organization or user this
+/// interface was obtained from
+pub struct Projects<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    path: String,
+}
+
+impl<C: Clone + Connect + 'static> Projects<C> {
+    #[doc(hidden)]
+    pub fn new<P>(github: Github<C>, path: P) -> Self
+    where
+        P: Into<String>,
+    {
+        Projects {
+            github,
+            path: path.into(),
+        }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!("{}{}", self.path, more)
+    }
+
+    /// list projects
+    pub fn list(&self, options: &ProjectListOptions) -> Future<Vec<Project>> {
+        let mut uri = vec![self.path("")];
+        if let Some(query) = options.serialize() {
+            uri.push(query);
+        }
+        self.github.get(&uri.join("?"))
+    }
+
+    /// provides a stream over all pages of projects
+    pub fn iter(&self, options: &ProjectListOptions) -> Stream<Project> {
+        let mut uri = vec![self.path("")];
+        if let Some(query) = options.serialize() {
+            uri.push(query);
+        }
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&uri.join("?")),
+            identity,
+        )
+    }
+
+    /// create a new project
+    pub fn create(&self, options: &ProjectOptions) -> Future<Project> {
+        self.github.post(&self.path(""), json!(options))
+    }
+
+    /// get a project by id
+    pub fn get(&self, id: u64) -> Future<Project> {
+        self.github.get(&format!("/projects/{}", id))
+    }
+
+    /// edit a project by id
+    pub fn edit(&self, id: u64, options: &ProjectEditOptions) -> Future<Project> {
+        self.github
+            .patch(&format!("/projects/{}", id), json!(options))
+    }
+
+    /// delete a project by id
+    pub fn delete(&self, id: u64) -> Future<()> {
+        self.github.delete(&format!("/projects/{}", id))
+    }
+
+    /// get a reference to the columns of a project
+    pub fn columns(&self, project_id: u64) -> Columns<C> {
+        Columns::new(self.github.clone(), project_id)
+    }
+
+    /// get a reference to the cards of a project column
+    pub fn cards(&self, column_id: u64) -> Cards<C> {
+        Cards::new(self.github.clone(), column_id)
+    }
+
+    /// get a reference to the collaborators of a project
+    pub fn collaborators(&self, project_id: u64) -> Collaborators<C> {
+        Collaborators::new(self.github.clone(), project_id)
+    }
+}
+
+/// Provides access to the columns of a project
+pub struct Columns<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    project_id: u64,
+}
+
+impl<C: Clone + Connect + 'static> Columns<C> {
+    #[doc(hidden)]
+    pub fn new(github: Github<C>, project_id: u64) -> Self {
+        Columns { github, project_id }
+    }
+
+    /// list the columns of this project
+    pub fn list(&self) -> Future<Vec<Column>> {
+        self.github
+            .get(&format!("/projects/{}/columns", self.project_id))
+    }
+
+    /// provides a stream over all pages of this project's columns
+    pub fn iter(&self) -> Stream<Column> {
+        unfold(
+            self.github.clone(),
+            self.github
+                .get_pages(&format!("/projects/{}/columns", self.project_id)),
+            identity,
+        )
+    }
+
+    /// create a new column in this project
+    pub fn create<N>(&self, name: N) -> Future<Column>
+    where
+        N: Into<String>,
+    {
+        let mut payload = BTreeMap::new();
+        payload.insert("name", name.into());
+        self.github.post(
+            &format!("/projects/{}/columns", self.project_id),
+            json!(payload),
+        )
+    }
+
+    /// get a column by id
+    pub fn get(&self, id: u64) -> Future<Column> {
+        self.github.get(&format!("/projects/columns/{}", id))
+    }
+
+    /// rename a column
+    pub fn rename<N>(&self, id: u64, name: N) -> Future<Column>
+    where
+        N: Into<String>,
+    {
+        let mut payload = BTreeMap::new();
+        payload.insert("name", name.into());
+        self.github
+            .patch(&format!("/projects/columns/{}", id), json!(payload))
+    }
+
+    /// delete a column by id
+    pub fn delete(&self, id: u64) -> Future<()> {
+        self.github.delete(&format!("/projects/columns/{}", id))
+    }
+
+    /// move a column within this project
+    pub fn move_column(&self, id: u64, position: ColumnPosition) -> Future<()> {
+        let mut payload = BTreeMap::new();
+        payload.insert("position", position.to_string());
+        self.github
+            .post_no_response(&format!("/projects/columns/{}/moves", id), json!(payload))
+    }
+}
+
+/// Where to move a column to
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColumnPosition {
+    First,
+    Last,
+    /// after the column with the given id
+    After(u64),
+}
+
+impl fmt::Display for ColumnPosition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ColumnPosition::First => write!(f, "first"),
+            ColumnPosition::Last => write!(f, "last"),
+            ColumnPosition::After(id) => write!(f, "after:{}", id),
+        }
+    }
+}
+
+/// Provides access to the cards of a project column
+pub struct Cards<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    column_id: u64,
+}
+
+impl<C: Clone + Connect + 'static> Cards<C> {
+    #[doc(hidden)]
+    pub fn new(github: Github<C>, column_id: u64) -> Self {
+        Cards { github, column_id }
+    }
+
+    fn path(&self, options: &CardListOptions) -> String {
+        let mut uri = vec![format!("/projects/columns/{}/cards", self.column_id)];
+        if let Some(query) = options.serialize() {
+            uri.push(query);
+        }
+        uri.join("?")
+    }
+
+    /// list the cards of this column
+    pub fn list(&self, options: &CardListOptions) -> Future<Vec<Card>> {
+        self.github.get(&self.path(options))
+    }
+
+    /// provides a stream over all pages of this column's cards
+    pub fn iter(&self, options: &CardListOptions) -> Stream<Card> {
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&self.path(options)),
+            identity,
+        )
+    }
+
+    /// create a new card in this column
+    pub fn create(&self, options: &CardOptions) -> Future<Card> {
+        self.github.post(
+            &format!("/projects/columns/{}/cards", self.column_id),
+            json!(options),
+        )
+    }
+
+    /// get a card by id
+    pub fn get(&self, id: u64) -> Future<Card> {
+        self.github.get(&format!("/projects/columns/cards/{}", id))
+    }
+
+    /// edit a card by id
+    pub fn edit(&self, id: u64, options: &CardEditOptions) -> Future<Card> {
+        self.github
+            .patch(&format!("/projects/columns/cards/{}", id), json!(options))
+    }
+
+    /// delete a card by id
+    pub fn delete(&self, id: u64) -> Future<()> {
+        self.github
+            .delete(&format!("/projects/columns/cards/{}", id))
+    }
+
+    /// move a card within its column or to another column
+    pub fn move_card(&self, id: u64, options: &CardMoveOptions) -> Future<()> {
+        self.github.post_no_response(
+            &format!("/projects/columns/cards/{}/moves", id),
+            json!(options),
+        )
+    }
+}
+
+/// Provides access to the collaborators of a project
+pub struct Collaborators<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    project_id: u64,
+}
+
+impl<C: Clone + Connect + 'static> Collaborators<C> {
+    #[doc(hidden)]
+    pub fn new(github: Github<C>, project_id: u64) -> Self {
+        Collaborators { github, project_id }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!("/projects/{}/collaborators{}", self.project_id, more)
+    }
+
+    /// list the collaborators of this project
+    pub fn list(&self) -> Future<Vec<User>> {
+        self.github.get(&self.path(""))
+    }
+
+    /// provides a stream over all pages of this project's collaborators
+    pub fn iter(&self) -> Stream<User> {
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&self.path("")),
+            identity,
+        )
+    }
+
+    /// add a collaborator to this project or update their permission
+    pub fn add<U>(&self, username: U, permission: Permission) -> Future<()>
+    where
+        U: Into<String>,
+    {
+        let mut payload = BTreeMap::new();
+        payload.insert("permission", permission.to_string());
+        self.github
+            .put_no_response(&self.path(&format!("/{}", username.into())), json!(payload))
+    }
+
+    /// remove a collaborator from this project
+    pub fn remove<U>(&self, username: U) -> Future<()>
+    where
+        U: Into<String>,
+    {
+        self.github
+            .delete(&self.path(&format!("/{}", username.into())))
+    }
+
+    /// get a collaborator's permission level for this project
+    pub fn permission<U>(&self, username: U) -> Future<CollaboratorPermission>
+    where
+        U: Into<String>,
+    {
+        self.github
+            .get(&self.path(&format!("/{}/permission", username.into())))
+    }
+}
+
+// representations
+
+#[derive(Debug, Deserialize)]
+pub struct Project {
+    pub id: u64,
+    pub node_id: String,
+    pub number: u64,
+    pub name: String,
+    pub body: Option<String>,
+    pub state: State,
+    pub creator: User,
+    pub url: String,
+    pub html_url: String,
+    pub owner_url: String,
+    pub columns_url: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Column {
+    pub id: u64,
+    pub node_id: String,
+    pub name: String,
+    pub url: String,
+    pub project_url: String,
+    pub cards_url: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Card {
+    pub id: u64,
+    pub node_id: String,
+    pub note: Option<String>,
+    pub creator: Option<User>,
+    pub archived: bool,
+    pub url: String,
+    pub column_url: String,
+    pub project_url: String,
+    /// the api url of the issue or pull request this card refers to, if any
+    pub content_url: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CollaboratorPermission {
+    pub permission: Permission,
+    pub user: User,
+}
+
+#[derive(Default)]
+pub struct ProjectListOptions {
+    params: HashMap<&'static str, String>,
+}
+
+impl ProjectListOptions {
+    pub fn builder() -> ProjectListOptionsBuilder {
+        ProjectListOptionsBuilder::default()
+    }
+
+    /// serialize options as a string. returns None if no options are defined
+    pub fn serialize(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded)
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ProjectListOptionsBuilder(ProjectListOptions);
+
+impl ProjectListOptionsBuilder {
+    pub fn state(&mut self, state: State) -> &mut Self {
+        self.0.params.insert("state", state.to_string());
+        self
+    }
+
+    pub fn per_page(&mut self, n: usize) -> &mut Self {
+        self.0.params.insert("per_page", n.to_string());
+        self
+    }
+
+    pub fn build(&self) -> ProjectListOptions {
+        ProjectListOptions {
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+/// options for creating a project
+#[derive(Debug, Default, Serialize)]
+pub struct ProjectOptions {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+impl ProjectOptions {
+    pub fn builder<N>(name: N) -> ProjectOptionsBuilder
+    where
+        N: Into<String>,
+    {
+        ProjectOptionsBuilder(ProjectOptions {
+            name: name.into(),
+            ..Default::default()
+        })
+    }
+}
+
+pub struct ProjectOptionsBuilder(ProjectOptions);
+
+impl ProjectOptionsBuilder {
+    pub fn body<B>(&mut self, body: B) -> &mut Self
+    where
+        B: Into<String>,
+    {
+        self.0.body = Some(body.into());
+        self
+    }
+
+    pub fn build(&self) -> ProjectOptions {
+        ProjectOptions {
+            name: self.0.name.clone(),
+            body: self.0.body.clone(),
+        }
+    }
+}
+
+/// options for editing a project
+#[derive(Debug, Default, Serialize)]
+pub struct ProjectEditOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<State>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organization_permission: Option<Permission>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub private: Option<bool>,
+}
+
+impl ProjectEditOptions {
+    pub fn builder() -> ProjectEditOptionsBuilder {
+        ProjectEditOptionsBuilder::default()
+    }
+}
+
+#[derive(Default)]
+pub struct ProjectEditOptionsBuilder(ProjectEditOptions);
+
+impl ProjectEditOptionsBuilder {
+    pub fn name<N>(&mut self, name: N) -> &mut Self
+    where
+        N: Into<String>,
+    {
+        self.0.name = Some(name.into());
+        self
+    }
+
+    pub fn body<B>(&mut self, body: B) -> &mut Self
+    where
+        B: Into<String>,
+    {
+        self.0.body = Some(body.into());
+        self
+    }
+
+    pub fn state(&mut self, state: State) -> &mut Self {
+        self.0.state = Some(state);
+        self
+    }
+
+    /// the baseline permission all organization members have on
+    /// an organization project
+    pub fn organization_permission(&mut self, permission: Permission) -> &mut Self {
+        self.0.organization_permission = Some(permission);
+        self
+    }
+
+    /// whether an organization project is only visible to its collaborators
+    pub fn private(&mut self, private: bool) -> &mut Self {
+        self.0.private = Some(private);
+        self
+    }
+
+    pub fn build(&self) -> ProjectEditOptions {
+        ProjectEditOptions {
+            name: self.0.name.clone(),
+            body: self.0.body.clone(),
+            state: self.0.state,
+            organization_permission: self.0.organization_permission,
+            private: self.0.private,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct CardListOptions {
+    params: HashMap<&'static str, String>,
+}
+
+impl CardListOptions {
+    pub fn builder() -> CardListOptionsBuilder {
+        CardListOptionsBuilder::default()
+    }
+
+    /// serialize options as a string. returns None if no options are defined
+    pub fn serialize(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded)
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct CardListOptionsBuilder(CardListOptions);
+
+impl CardListOptionsBuilder {
+    /// filter cards by archived state. one of `all`, `archived` or `not_archived`.
+    /// defaults to `not_archived`
+    pub fn archived_state<S>(&mut self, state: S) -> &mut Self
+    where
+        S: Into<String>,
+    {
+        self.0.params.insert("archived_state", state.into());
+        self
+    }
+
+    pub fn per_page(&mut self, n: usize) -> &mut Self {
+        self.0.params.insert("per_page", n.to_string());
+        self
+    }
+
+    pub fn build(&self) -> CardListOptions {
+        CardListOptions {
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+/// options for creating a card. cards contain either a note
+/// or refer to an issue or pull request
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum CardOptions {
+    Note {
+        note: String,
+    },
+    Content {
+        content_id: u64,
+        /// either `Issue` or `PullRequest`
+        content_type: String,
+    },
+}
+
+impl CardOptions {
+    /// a card containing a note
+    pub fn note<N>(note: N) -> Self
+    where
+        N: Into<String>,
+    {
+        CardOptions::Note { note: note.into() }
+    }
+
+    /// a card referring to an issue by its id
+    pub fn issue(id: u64) -> Self {
+        CardOptions::Content {
+            content_id: id,
+            content_type: "Issue".into(),
+        }
+    }
+
+    /// a card referring to a pull request by its id
+    pub fn pull_request(id: u64) -> Self {
+        CardOptions::Content {
+            content_id: id,
+            content_type: "PullRequest".into(),
+        }
+    }
+}
+
+/// options for editing a card
+#[derive(Debug, Default, Serialize)]
+pub struct CardEditOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived: Option<bool>,
+}
+
+impl CardEditOptions {
+    pub fn builder() -> CardEditOptionsBuilder {
+        CardEditOptionsBuilder::default()
+    }
+}
+
+#[derive(Default)]
+pub struct CardEditOptionsBuilder(CardEditOptions);
+
+impl CardEditOptionsBuilder {
+    pub fn note<N>(&mut self, note: N) -> &mut Self
+    where
+        N: Into<String>,
+    {
+        self.0.note = Some(note.into());
+        self
+    }
+
+    pub fn archived(&mut self, archived: bool) -> &mut Self {
+        self.0.archived = Some(archived);
+        self
+    }
+
+    pub fn build(&self) -> CardEditOptions {
+        CardEditOptions {
+            note: self.0.note.clone(),
+            archived: self.0.archived,
+        }
+    }
+}
+
+/// options for moving a card
+#[derive(Debug, Serialize)]
+pub struct CardMoveOptions {
+    /// one of `top`, `bottom` or `after:<card_id>`
+    pub position: String,
+    /// the column to move the card to. defaults to the card's current column
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column_id: Option<u64>,
+}
+
+impl CardMoveOptions {
+    /// move a card to the top of a column
+    pub fn top() -> Self {
+        CardMoveOptions {
+            position: "top".into(),
+            column_id: None,
+        }
+    }
+
+    /// move a card to the bottom of a column
+    pub fn bottom() -> Self {
+        CardMoveOptions {
+            position: "bottom".into(),
+            column_id: None,
+        }
+    }
+
+    /// move a card to just after another card
+    pub fn after(card_id: u64) -> Self {
+        CardMoveOptions {
+            position: format!("after:{}", card_id),
+            column_id: None,
+        }
+    }
+
+    /// move the card into another column
+    pub fn column(mut self, column_id: u64) -> Self {
+        self.column_id = Some(column_id);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn column_position_display() {
+        for (position, expect) in &[
+            (ColumnPosition::First, "first"),
+            (ColumnPosition::Last, "last"),
+            (ColumnPosition::After(42), "after:42"),
+        ] {
+            assert_eq!(position.to_string(), *expect)
+        }
+    }
+
+    #[test]
+    fn card_reqs() {
+        for (options, expect) in vec![
+            (CardOptions::note("todo"), r#"{"note":"todo"}"#),
+            (
+                CardOptions::issue(1),
+                r#"{"content_id":1,"content_type":"Issue"}"#,
+            ),
+        ] {
+            assert_eq!(serde_json::to_string(&options).unwrap(), expect)
+        }
+        assert_eq!(
+            serde_json::to_string(&CardMoveOptions::after(2).column(3)).unwrap(),
+            r#"{"position":"after:2","column_id":3}"#
+        )
+    }
+}
@@ -0,0 +1,339 @@
+//! Projects interface
+//!
+//! Projects (formerly projects beta) are only exposed through Github's
+//! GraphQL api. These helpers wrap the most common queries and mutations.
+//! Projects, items and fields are addressed by their GraphQL node ids
+//!
+//! See the [github docs](https://docs.github.com/en/issues/planning-and-tracking-with-projects/automating-your-project/using-the-api-to-manage-projects)
+//! for more information.
+
+use futures::{future, Future as StdFuture};
+use hyper::client::connect::Connect;
+use serde_json::Value;
+
+use graphql::{extract, json_vars, paginate, GraphQL};
+use {Error, Future, Github};
+
+const PROJECT_FIELDS: &str = "id number title shortDescription url closed public";
+
+const ITEMS_QUERY: &str = r#"
+query($project: ID!, $cursor: String) {
+  node(id: $project) {
+    ... on ProjectV2 {
+      items(first: 100, after: $cursor) {
+        nodes {
+          id
+          type
+          isArchived
+          content {
+            __typename
+            ... on Issue { id number title url }
+            ... on PullRequest { id number title url }
+            ... on DraftIssue { id title }
+          }
+        }
+        pageInfo { hasNextPage endCursor }
+      }
+    }
+  }
+}"#;
+
+const FIELDS_QUERY: &str = r#"
+query($project: ID!, $cursor: String) {
+  node(id: $project) {
+    ... on ProjectV2 {
+      fields(first: 100, after: $cursor) {
+        nodes {
+          ... on ProjectV2FieldCommon { id name dataType }
+          ... on ProjectV2SingleSelectField { options { id name } }
+        }
+        pageInfo { hasNextPage endCursor }
+      }
+    }
+  }
+}"#;
+
+const ADD_ITEM_MUTATION: &str = r#"
+mutation($project: ID!, $content: ID!) {
+  addProjectV2ItemById(input: {projectId: $project, contentId: $content}) {
+    item { id }
+  }
+}"#;
+
+const UPDATE_FIELD_MUTATION: &str = r#"
+mutation($project: ID!, $item: ID!, $field: ID!, $value: ProjectV2FieldValue!) {
+  updateProjectV2ItemFieldValue(
+    input: {projectId: $project, itemId: $item, fieldId: $field, value: $value}
+  ) {
+    projectV2Item { id }
+  }
+}"#;
+
+/// Provides access to projects
+pub struct ProjectsV2<C>
+where
+    C: Clone + Connect + 'static,
+{
+    graphql: GraphQL<C>,
+}
+
+impl<C: Clone + Connect + 'static> ProjectsV2<C> {
+    #[doc(hidden)]
+    pub fn new(github: Github<C>) -> Self {
+        Self {
+            graphql: GraphQL::new(github),
+        }
+    }
+
+    /// list all projects owned by an organization
+    pub fn org_projects<O>(&self, org: O) -> Future<Vec<ProjectV2>>
+    where
+        O: Into<String>,
+    {
+        self.owner_projects("organization", org.into())
+    }
+
+    /// list all projects owned by a user
+    pub fn user_projects<U>(&self, user: U) -> Future<Vec<ProjectV2>>
+    where
+        U: Into<String>,
+    {
+        self.owner_projects("user", user.into())
+    }
+
+    fn owner_projects(&self, owner_type: &str, login: String) -> Future<Vec<ProjectV2>> {
+        let query = format!(
+            r#"query($login: String!, $cursor: String) {{
+  {}(login: $login) {{
+    projectsV2(first: 100, after: $cursor) {{
+      nodes {{ {} }}
+      pageInfo {{ hasNextPage endCursor }}
+    }}
+  }}
+}}"#,
+            owner_type, PROJECT_FIELDS
+        );
+        paginate(
+            self.graphql.clone(),
+            query,
+            json_vars(&[("login", Value::String(login))]),
+            format!("/{}/projectsV2", owner_type),
+        )
+    }
+
+    /// list all items of a project
+    pub fn items<P>(&self, project_id: P) -> Future<Vec<ProjectV2Item>>
+    where
+        P: Into<String>,
+    {
+        paginate(
+            self.graphql.clone(),
+            ITEMS_QUERY.to_owned(),
+            json_vars(&[("project", Value::String(project_id.into()))]),
+            "/node/items".to_owned(),
+        )
+    }
+
+    /// list all fields of a project
+    pub fn fields<P>(&self, project_id: P) -> Future<Vec<ProjectV2Field>>
+    where
+        P: Into<String>,
+    {
+        paginate(
+            self.graphql.clone(),
+            FIELDS_QUERY.to_owned(),
+            json_vars(&[("project", Value::String(project_id.into()))]),
+            "/node/fields".to_owned(),
+        )
+    }
+
+    /// add an issue or pull request to a project by its node id,
+    /// returning the node id of the new project item.
+    /// Adding content that is already part of the project returns the existing item
+    pub fn add_item<P, N>(&self, project_id: P, content_id: N) -> Future<String>
+    where
+        P: Into<String>,
+        N: Into<String>,
+    {
+        let variables = json_vars(&[
+            ("project", Value::String(project_id.into())),
+            ("content", Value::String(content_id.into())),
+        ]);
+        Box::new(
+            self.graphql
+                .query::<_, Value>(ADD_ITEM_MUTATION, &variables)
+                .and_then(|data| extract::<String>(&data, "/addProjectV2ItemById/item/id")),
+        )
+    }
+
+    /// update the value of a project item's field, resolving the field
+    /// and single select options by name
+    pub fn set_field<P, I, F>(
+        &self,
+        project_id: P,
+        item_id: I,
+        field_name: F,
+        value: FieldValue,
+    ) -> Future<()>
+    where
+        P: Into<String>,
+        I: Into<String>,
+        F: Into<String>,
+    {
+        let graphql = self.graphql.clone();
+        let (project_id, item_id, field_name) =
+            (project_id.into(), item_id.into(), field_name.into());
+        Box::new(self.fields(project_id.clone()).and_then(move |fields| {
+            let resolved = fields
+                .into_iter()
+                .find(|field| field.name == field_name)
+                .ok_or_else(|| Error::from(format!("project has no field named {}", field_name)))
+                .and_then(|field| {
+                    let value = value.resolve(&field)?;
+                    Ok((field.id, value))
+                });
+            let (field_id, value) = match resolved {
+                Ok(resolved) => resolved,
+                Err(err) => return Box::new(future::err(err)) as Future<()>,
+            };
+            let variables = json_vars(&[
+                ("project", Value::String(project_id)),
+                ("item", Value::String(item_id)),
+                ("field", Value::String(field_id)),
+                ("value", value),
+            ]);
+            Box::new(
+                graphql
+                    .query::<_, Value>(UPDATE_FIELD_MUTATION, &variables)
+                    .map(|_| ()),
+            ) as Future<()>
+        }))
+    }
+}
+
+// representations
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectV2 {
+    /// the project's node id
+    pub id: String,
+    pub number: u64,
+    pub title: String,
+    pub short_description: Option<String>,
+    pub url: String,
+    pub closed: bool,
+    pub public: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectV2Item {
+    /// the item's node id
+    pub id: String,
+    /// one of `ISSUE`, `PULL_REQUEST`, `DRAFT_ISSUE` or `REDACTED`
+    #[serde(rename = "type")]
+    pub item_type: String,
+    pub is_archived: bool,
+    pub content: Option<ProjectV2ItemContent>,
+}
+
+/// the issue, pull request or draft issue a project item refers to
+#[derive(Debug, Deserialize)]
+pub struct ProjectV2ItemContent {
+    #[serde(rename = "__typename")]
+    pub typename: String,
+    pub id: Option<String>,
+    pub number: Option<u64>,
+    pub title: Option<String>,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectV2Field {
+    /// the field's node id
+    pub id: String,
+    pub name: String,
+    /// one of `TEXT`, `NUMBER`, `DATE`, `SINGLE_SELECT`, `ITERATION` or one of
+    /// the built in field types like `TITLE` and `ASSIGNEES`
+    pub data_type: String,
+    /// the options of a single select field
+    pub options: Option<Vec<ProjectV2FieldOption>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProjectV2FieldOption {
+    pub id: String,
+    pub name: String,
+}
+
+/// A value to set a project item's field to
+#[derive(Clone, Debug, PartialEq)]
+pub enum FieldValue {
+    Text(String),
+    Number(f64),
+    /// an ISO 8601 date, like `2019-01-31`
+    Date(String),
+    /// the name of one of a single select field's options
+    SingleSelect(String),
+}
+
+impl FieldValue {
+    fn resolve(self, field: &ProjectV2Field) -> Result<Value, Error> {
+        Ok(match self {
+            FieldValue::Text(text) => json_vars(&[("text", Value::String(text))]),
+            FieldValue::Number(n) => json_vars(&[("number", n.into())]),
+            FieldValue::Date(date) => json_vars(&[("date", Value::String(date))]),
+            FieldValue::SingleSelect(name) => {
+                let option = field
+                    .options
+                    .iter()
+                    .flat_map(|options| options.iter())
+                    .find(|option| option.name == name)
+                    .ok_or_else(|| {
+                        Error::from(format!("field {} has no option named {}", field.name, name))
+                    })?;
+                json_vars(&[("singleSelectOptionId", Value::String(option.id.clone()))])
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field() -> ProjectV2Field {
+        ProjectV2Field {
+            id: "F_1".into(),
+            name: "Status".into(),
+            data_type: "SINGLE_SELECT".into(),
+            options: Some(vec![ProjectV2FieldOption {
+                id: "O_1".into(),
+                name: "Done".into(),
+            }]),
+        }
+    }
+
+    #[test]
+    fn field_value_resolve() {
+        for (value, expect) in vec![
+            (FieldValue::Text("hi".into()), r#"{"text":"hi"}"#),
+            (FieldValue::Number(1.5), r#"{"number":1.5}"#),
+            (
+                FieldValue::Date("2019-01-31".into()),
+                r#"{"date":"2019-01-31"}"#,
+            ),
+            (
+                FieldValue::SingleSelect("Done".into()),
+                r#"{"singleSelectOptionId":"O_1"}"#,
+            ),
+        ] {
+            assert_eq!(value.resolve(&field()).unwrap().to_string(), expect)
+        }
+        assert!(FieldValue::SingleSelect("Todo".into())
+            .resolve(&field())
+            .is_err())
+    }
+}
@@ -2,6 +2,7 @@
 
 use hyper::client::connect::Connect;
 
+use ids::PullNumber;
 use users::User;
 use {unfold, Future, Github, Stream};
 
@@ -17,21 +18,22 @@ where
     github: Github<C>,
     owner: String,
     repo: String,
-    number: u64,
+    number: PullNumber,
 }
 
 impl<C: Clone + Connect + 'static> PullCommits<C> {
     #[doc(hidden)]
-    pub fn new<O, R>(github: Github<C>, owner: O, repo: R, number: u64) -> Self
+    pub fn new<O, R, N>(github: Github<C>, owner: O, repo: R, number: N) -> Self
     where
         O: Into<String>,
         R: Into<String>,
+        N: Into<PullNumber>,
     {
         PullCommits {
             github,
             owner: owner.into(),
             repo: repo.into(),
-            number,
+            number: number.into(),
         }
     }
 
@@ -72,6 +74,13 @@ pub struct PullCommit {
     pub parents: Vec<CommitRef>,
 }
 
+impl PullCommit {
+    /// whether the commit is signed with a signature Github verified
+    pub fn is_signed_and_verified(&self) -> bool {
+        self.commit.is_signed_and_verified()
+    }
+}
+
 /// Representation of a pull request commit details
 #[derive(Debug, Deserialize)]
 pub struct CommitDetails {
@@ -81,6 +90,34 @@ pub struct CommitDetails {
     pub message: String,
     pub tree: CommitRef,
     pub comment_count: u64,
+    /// Github's verification of the commit's signature. absent from
+    /// older Github Enterprise servers
+    pub verification: Option<Verification>,
+}
+
+impl CommitDetails {
+    /// whether the commit is signed with a signature Github verified
+    pub fn is_signed_and_verified(&self) -> bool {
+        self.verification
+            .as_ref()
+            .map(|verification| verification.verified && verification.signature.is_some())
+            .unwrap_or(false)
+    }
+}
+
+/// Representation of the verification of a commit's signature
+#[derive(Debug, Deserialize)]
+pub struct Verification {
+    pub verified: bool,
+    /// why the commit is or isn't verified, like `valid`, `unsigned`,
+    /// `unknown_key` or `expired_key`
+    pub reason: String,
+    /// the ascii-armored signature, if the commit is signed
+    pub signature: Option<String>,
+    /// the signed contents of the commit
+    pub payload: Option<String>,
+    /// when Github verified the signature
+    pub verified_at: Option<String>,
 }
 
 /// Representation of a reference to a commit
@@ -97,3 +134,37 @@ pub struct UserStamp {
     pub email: String,
     pub date: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn signed_and_verified() {
+        let details = |verification: &str| -> CommitDetails {
+            serde_json::from_str(&format!(
+                r#"{{
+                  "url": "https://api.github.com/repos/octocat/Hello-World/git/commits/6dcb09b",
+                  "author": {{"name": "Monalisa Octocat", "email": "support@github.com", "date": "2011-04-14T16:00:49Z"}},
+                  "committer": null,
+                  "message": "Fix all the bugs",
+                  "tree": {{"url": "https://api.github.com/repos/octocat/Hello-World/tree/6dcb09b", "sha": "6dcb09b"}},
+                  "comment_count": 0,
+                  "verification": {}
+                }}"#,
+                verification
+            ))
+            .unwrap()
+        };
+        assert!(details(
+            r#"{"verified": true, "reason": "valid", "signature": "-----BEGIN PGP SIGNATURE-----", "payload": "tree 6dcb09b"}"#
+        )
+        .is_signed_and_verified());
+        assert!(!details(
+            r#"{"verified": false, "reason": "unsigned", "signature": null, "payload": null}"#
+        )
+        .is_signed_and_verified());
+        assert!(!details("null").is_signed_and_verified());
+    }
+}
@@ -3,23 +3,42 @@
 use std::collections::HashMap;
 use std::fmt;
 
-use futures::future;
+use futures::{future, Future as StdFuture, Stream as StdStream};
 use hyper::client::connect::Connect;
 use serde_json;
+use serde_json::Value;
 use url::form_urlencoded;
 
+use codeowners::{self, Owner};
 use comments::Comments;
-use issues::{IssueAssignees, IssueLabels, Sort as IssueSort, State};
+use graphql::json_vars;
+use ids::{IssueNumber, PullId, PullNumber};
+use issues::{IssueAssignees, IssueLabels, IssueState, Sort as IssueSort, State};
 use labels::Label;
 use pull_commits::PullCommits;
 use review_comments::ReviewComments;
-use users::User;
+use review_threads::ReviewThreads;
+use users::{AuthorAssociation, User};
 use {unfold, Future, Github, SortDirection, Stream};
 
 fn identity<T>(x: T) -> T {
     x
 }
 
+const ENABLE_AUTO_MERGE_MUTATION: &str = r#"
+mutation($pull: ID!, $method: PullRequestMergeMethod!) {
+  enablePullRequestAutoMerge(input: {pullRequestId: $pull, mergeMethod: $method}) {
+    pullRequest { id }
+  }
+}"#;
+
+const DISABLE_AUTO_MERGE_MUTATION: &str = r#"
+mutation($pull: ID!) {
+  disablePullRequestAutoMerge(input: {pullRequestId: $pull}) {
+    pullRequest { id }
+  }
+}"#;
+
 /// Sort directions for pull requests
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Sort {
@@ -59,21 +78,22 @@ where
     github: Github<C>,
     owner: String,
     repo: String,
-    number: u64,
+    number: PullNumber,
 }
 
 impl<C: Clone + Connect + 'static> PullRequest<C> {
     #[doc(hidden)]
-    pub fn new<O, R>(github: Github<C>, owner: O, repo: R, number: u64) -> Self
+    pub fn new<O, R, N>(github: Github<C>, owner: O, repo: R, number: N) -> Self
     where
         O: Into<String>,
         R: Into<String>,
+        N: Into<PullNumber>,
     {
         PullRequest {
             github,
             owner: owner.into(),
             repo: repo.into(),
-            number,
+            number: number.into(),
         }
     }
 
@@ -129,6 +149,51 @@ impl<C: Clone + Connect + 'static> PullRequest<C> {
         self.github.get(&self.path("/files"))
     }
 
+    /// provides a stream over all of the file diffs of this pull, up to
+    /// the 3000 Github lists
+    pub fn iter_files(&self) -> Stream<FileDiff> {
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&self.path("/files?per_page=100")),
+            identity,
+        )
+    }
+
+    /// request reviews of this pull from users and teams
+    pub fn request_reviews(&self, options: &ReviewRequestOptions) -> Future<Pull> {
+        self.github
+            .post(&self.path("/requested_reviewers"), json!(options))
+    }
+
+    /// the code owners of the files this pull changes, according to the
+    /// `CODEOWNERS` file of its base branch
+    pub fn code_owners(&self) -> Future<Vec<Owner>> {
+        let github = self.github.clone();
+        let (owner, repo) = (self.owner.clone(), self.repo.clone());
+        let files = self.iter_files().map(|file| file.filename).collect();
+        Box::new(self.get().join(files).and_then(move |(pull, files)| {
+            codeowners::fetch(github, owner, repo, Some(pull.base.commit_ref))
+                .map(move |codeowners| codeowners.owners_for(&files))
+        }))
+    }
+
+    /// request reviews of this pull from the code owners of the files it
+    /// changes, other than its author. owners named by email, and teams of
+    /// other organizations, can't be requested to review and are skipped
+    pub fn request_code_owner_reviews(&self) -> Future<Pull> {
+        let github = self.github.clone();
+        let (owner, repo, number) = (self.owner.clone(), self.repo.clone(), self.number);
+        Box::new(self.get().join(self.code_owners()).and_then(
+            move |(pull, owners)| -> Future<Pull> {
+                let options = ReviewRequestOptions::from_owners(&owners, &owner, &pull.user.login);
+                if options.is_empty() {
+                    return Box::new(future::ok(pull));
+                }
+                PullRequest::new(github, owner, repo, number).request_reviews(&options)
+            },
+        ))
+    }
+
     /// returns issue comments interface
     pub fn comments(&self) -> Comments<C> {
         Comments::new(
@@ -149,6 +214,17 @@ impl<C: Clone + Connect + 'static> PullRequest<C> {
         )
     }
 
+    /// returns a reference to the review threads of this pull request,
+    /// which can be resolved and unresolved
+    pub fn review_threads(&self) -> ReviewThreads<C> {
+        ReviewThreads::new(
+            self.github.clone(),
+            self.owner.clone(),
+            self.repo.clone(),
+            self.number,
+        )
+    }
+
     /// returns pull commits interface
     pub fn commits(&self) -> PullCommits<C> {
         PullCommits::new(
@@ -158,6 +234,32 @@ impl<C: Clone + Connect + 'static> PullRequest<C> {
             self.number,
         )
     }
+
+    /// queue the pull request to be merged with `method` as soon as its
+    /// required reviews and status checks pass. the repository must allow
+    /// auto-merge
+    pub fn enable_auto_merge(&self, method: MergeMethod) -> Future<()> {
+        let method = Value::String(method.as_str().to_uppercase());
+        self.auto_merge(ENABLE_AUTO_MERGE_MUTATION, vec![("method", method)])
+    }
+
+    /// take the pull request out of the auto-merge queue
+    pub fn disable_auto_merge(&self) -> Future<()> {
+        self.auto_merge(DISABLE_AUTO_MERGE_MUTATION, vec![])
+    }
+
+    fn auto_merge(&self, mutation: &'static str, vars: Vec<(&'static str, Value)>) -> Future<()> {
+        // auto-merge is only exposed through the GraphQL api, which
+        // addresses pull requests by their node id
+        let graphql = self.github.graphql();
+        Box::new(self.get().and_then(move |pull| {
+            let mut vars = vars;
+            vars.push(("pull", Value::String(pull.node_id)));
+            graphql
+                .query::<_, Value>(mutation, &json_vars(&vars))
+                .map(|_| ())
+        }))
+    }
 }
 
 /// A structure for interfacing with a repositories list of pull requests
@@ -189,7 +291,10 @@ impl<C: Clone + Connect + 'static> PullRequests<C> {
     }
 
     /// Get a reference to a structure for interfacing with a specific pull request
-    pub fn get(&self, number: u64) -> PullRequest<C> {
+    pub fn get<N>(&self, number: N) -> PullRequest<C>
+    where
+        N: Into<PullNumber>,
+    {
         PullRequest::new(
             self.github.clone(),
             self.owner.as_str(),
@@ -231,7 +336,8 @@ impl<C: Clone + Connect + 'static> PullRequests<C> {
 /// representation of a github pull request
 #[derive(Debug, Deserialize)]
 pub struct Pull {
-    pub id: u64,
+    pub id: PullId,
+    pub node_id: String,
     pub url: String,
     pub html_url: String,
     pub diff_url: String,
@@ -242,8 +348,8 @@ pub struct Pull {
     pub review_comment_url: String,
     pub comments_url: String,
     pub statuses_url: String,
-    pub number: u64,
-    pub state: String,
+    pub number: PullNumber,
+    pub state: IssueState,
     pub title: String,
     pub body: Option<String>,
     pub created_at: String,
@@ -254,6 +360,7 @@ pub struct Pull {
     pub base: Commit,
     // links
     pub user: User,
+    pub author_association: AuthorAssociation,
     pub assignee: Option<User>,
     pub assignees: Vec<User>,
     pub merge_commit_sha: Option<String>,
@@ -265,6 +372,26 @@ pub struct Pull {
     pub deletions: Option<u64>,
     pub changed_files: Option<u64>,
     pub labels: Vec<Label>,
+    /// present when the pull request is queued to merge automatically
+    pub auto_merge: Option<AutoMerge>,
+}
+
+string_enum! {
+    /// The ways a pull request may be merged
+    pub enum MergeMethod {
+        Merge => "merge",
+        Squash => "squash",
+        Rebase => "rebase",
+    }
+}
+
+/// The auto-merge settings of a pull request
+#[derive(Debug, Deserialize)]
+pub struct AutoMerge {
+    pub enabled_by: User,
+    pub merge_method: MergeMethod,
+    pub commit_title: Option<String>,
+    pub commit_message: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -346,13 +473,79 @@ impl PullEditOptions {
     }
 }
 
+pub struct PullOptionsBuilder(PullOptions);
+
+impl PullOptionsBuilder {
+    /// set the title of the pull
+    pub fn title<T>(&mut self, title: T) -> &mut Self
+    where
+        T: Into<String>,
+    {
+        self.0.title = title.into();
+        self
+    }
+
+    /// set the body of the pull
+    pub fn body<B>(&mut self, body: B) -> &mut Self
+    where
+        B: Into<String>,
+    {
+        self.0.body = Some(body.into());
+        self
+    }
+
+    /// convert an existing issue into the pull, which keeps the issue's
+    /// title and body
+    pub fn issue<N>(&mut self, issue: N) -> &mut Self
+    where
+        N: Into<IssueNumber>,
+    {
+        self.0.issue = Some(issue.into());
+        self
+    }
+
+    /// set whether maintainers of the base repository may push to the head
+    /// branch of a pull from a fork
+    pub fn maintainer_can_modify(&mut self, modify: bool) -> &mut Self {
+        self.0.maintainer_can_modify = Some(modify);
+        self
+    }
+
+    /// open the pull as a draft
+    pub fn draft(&mut self, draft: bool) -> &mut Self {
+        self.0.draft = Some(draft);
+        self
+    }
+
+    /// create a new set of pull options
+    pub fn build(&self) -> PullOptions {
+        PullOptions {
+            title: self.0.title.clone(),
+            head: self.0.head.clone(),
+            base: self.0.base.clone(),
+            body: self.0.body.clone(),
+            issue: self.0.issue,
+            maintainer_can_modify: self.0.maintainer_can_modify,
+            draft: self.0.draft,
+        }
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct PullOptions {
+    /// required unless `issue` is set
+    #[serde(skip_serializing_if = "String::is_empty")]
     pub title: String,
     pub head: String,
     pub base: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue: Option<IssueNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maintainer_can_modify: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub draft: Option<bool>,
 }
 
 impl PullOptions {
@@ -368,7 +561,67 @@ impl PullOptions {
             head: head.into(),
             base: base.into(),
             body: body.map(|b| b.into()),
+            issue: None,
+            maintainer_can_modify: None,
+            draft: None,
+        }
+    }
+
+    /// `head` is the branch to merge, prefixed by `owner:` for branches of
+    /// forks, and `base` the branch to merge it into
+    pub fn builder<H, B>(head: H, base: B) -> PullOptionsBuilder
+    where
+        H: Into<String>,
+        B: Into<String>,
+    {
+        PullOptionsBuilder(PullOptions {
+            title: String::new(),
+            head: head.into(),
+            base: base.into(),
+            body: None,
+            issue: None,
+            maintainer_can_modify: None,
+            draft: None,
+        })
+    }
+}
+
+/// users and teams to request reviews from
+#[derive(Debug, Default, Serialize)]
+pub struct ReviewRequestOptions {
+    /// user logins
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub reviewers: Vec<String>,
+    /// slugs of teams of the repository's organization
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub team_reviewers: Vec<String>,
+}
+
+impl ReviewRequestOptions {
+    /// requests reviews from the users and the teams of organization `org`
+    /// among `owners`, other than `author`
+    pub fn from_owners(owners: &[Owner], org: &str, author: &str) -> Self {
+        let mut options = ReviewRequestOptions::default();
+        for owner in owners {
+            match *owner {
+                Owner::User(ref login) if !login.eq_ignore_ascii_case(author) => {
+                    options.reviewers.push(login.clone())
+                }
+                Owner::Team {
+                    org: ref team_org,
+                    ref slug,
+                } if team_org.eq_ignore_ascii_case(org) => {
+                    options.team_reviewers.push(slug.clone())
+                }
+                _ => (),
+            }
         }
+        options
+    }
+
+    /// whether no one is requested to review
+    pub fn is_empty(&self) -> bool {
+        self.reviewers.is_empty() && self.team_reviewers.is_empty()
     }
 }
 
@@ -450,6 +703,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn review_request_from_owners() {
+        let owners = vec![
+            Owner::User("octocat".into()),
+            Owner::User("Author".into()),
+            Owner::Team {
+                org: "softprops".into(),
+                slug: "maintainers".into(),
+            },
+            Owner::Team {
+                org: "elsewhere".into(),
+                slug: "ops".into(),
+            },
+            Owner::Email("docs@example.com".into()),
+        ];
+        test_encoding(vec![(
+            ReviewRequestOptions::from_owners(&owners, "softprops", "author"),
+            r#"{"reviewers":["octocat"],"team_reviewers":["maintainers"]}"#,
+        )]);
+    }
+
+    #[test]
+    fn deserialize_auto_merge() {
+        let auto_merge: AutoMerge = serde_json::from_str(
+            r#"{
+              "enabled_by": {
+                "login": "octocat",
+                "id": 1,
+                "avatar_url": "https://github.com/images/error/octocat_happy.gif",
+                "gravatar_id": "",
+                "url": "https://api.github.com/users/octocat",
+                "html_url": "https://github.com/octocat",
+                "followers_url": "https://api.github.com/users/octocat/followers",
+                "following_url": "https://api.github.com/users/octocat/following{/other_user}",
+                "gists_url": "https://api.github.com/users/octocat/gists{/gist_id}",
+                "starred_url": "https://api.github.com/users/octocat/starred{/owner}{/repo}",
+                "subscriptions_url": "https://api.github.com/users/octocat/subscriptions",
+                "organizations_url": "https://api.github.com/users/octocat/orgs",
+                "repos_url": "https://api.github.com/users/octocat/repos",
+                "events_url": "https://api.github.com/users/octocat/events{/privacy}",
+                "received_events_url": "https://api.github.com/users/octocat/received_events",
+                "type": "User",
+                "site_admin": false
+              },
+              "merge_method": "squash",
+              "commit_title": "Add a feature (#42)",
+              "commit_message": null
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(auto_merge.merge_method, MergeMethod::Squash);
+        assert_eq!(auto_merge.enabled_by.login, "octocat");
+    }
+
     #[test]
     fn pull_list_reqs() {
         fn test_serialize(tests: Vec<(PullListOptions, Option<String>)>) {
@@ -469,6 +776,27 @@ mod tests {
         test_serialize(tests)
     }
 
+    #[test]
+    fn pullreq_creates() {
+        let tests = vec![
+            (
+                PullOptions::builder("octocat:feature", "main")
+                    .title("test")
+                    .draft(true)
+                    .build(),
+                r#"{"title":"test","head":"octocat:feature","base":"main","draft":true}"#,
+            ),
+            (
+                PullOptions::builder("feature", "main")
+                    .issue(5)
+                    .maintainer_can_modify(false)
+                    .build(),
+                r#"{"head":"feature","base":"main","issue":5,"maintainer_can_modify":false}"#,
+            ),
+        ];
+        test_encoding(tests)
+    }
+
     #[test]
     fn pullreq_edits() {
         let tests = vec![
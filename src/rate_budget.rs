@@ -0,0 +1,314 @@
+//! Rate limit budgets
+//!
+//! Github meters requests against separate rate limit resources: the rest
+//! api's `core` resource, `search`, `code_search` and `graphql`. A
+//! `RateBudget` partitions a token's quota of each resource, keeping some
+//! headroom in reserve or capping how much of it a client may use, so one
+//! subsystem of a service can't starve the others. Requests which would
+//! overdraw a partition fail with `ErrorKind::BudgetExhausted`, or are held
+//! back until the rate limit window resets
+//!
+//! ```no_run
+//! # extern crate hubcaps;
+//! # use std::time::Duration;
+//! # use hubcaps::rate_budget::{BudgetPolicy, RateBudget, Resource};
+//! # use hubcaps::Github;
+//! # fn main() {
+//! let budget = RateBudget::builder()
+//!     .reserve(Resource::Core, 500)
+//!     .cap(Resource::Search, 10)
+//!     .policy(BudgetPolicy::WaitForReset(Duration::from_secs(60)))
+//!     .build();
+//! let github = Github::builder()
+//!     .agent("my-cool-user-agent/0.1.0")
+//!     .rate_budget(budget)
+//!     .build();
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use hyper::HeaderMap;
+use url::Url;
+
+use {ErrorKind, Result};
+
+const X_RATELIMIT_RESOURCE: &str = "x-ratelimit-resource";
+const X_RATELIMIT_REMAINING: &str = "x-ratelimit-remaining";
+const X_RATELIMIT_RESET: &str = "x-ratelimit-reset";
+
+string_enum! {
+    /// The rate limit resources Github meters requests against
+    pub enum Resource {
+        /// the rest api, other than search
+        Core => "core",
+        Search => "search",
+        CodeSearch => "code_search",
+        Graphql => "graphql",
+    }
+}
+
+impl Resource {
+    /// the resource a request to `uri`, a path or absolute url, is
+    /// metered against
+    pub fn of(uri: &str) -> Resource {
+        let path = Url::parse(uri)
+            .map(|url| url.path().to_owned())
+            .unwrap_or_else(|_| uri.to_owned());
+        // Github enterprise serves the api under /api/v3 and /api/graphql
+        let path = path
+            .trim_start_matches("/api/v3")
+            .trim_start_matches("/api");
+        if path.starts_with("/graphql") {
+            Resource::Graphql
+        } else if path.starts_with("/search/code") {
+            Resource::CodeSearch
+        } else if path.starts_with("/search/") {
+            Resource::Search
+        } else {
+            Resource::Core
+        }
+    }
+}
+
+/// How much of a resource's quota a client may use
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Partition {
+    /// keep this many requests of the quota in reserve for other clients
+    Reserve(u32),
+    /// send no more than this many requests of each rate limit window.
+    /// only the requests this budget admits count towards the cap, not
+    /// those of other clients sharing the token
+    Cap(u32),
+}
+
+/// Controls how requests which would overdraw a partition are handled
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BudgetPolicy {
+    /// fail fast with `ErrorKind::BudgetExhausted`
+    Fail,
+    /// wait for the rate limit window to reset, provided the reset is no
+    /// further away than the given duration
+    WaitForReset(Duration),
+}
+
+impl Default for BudgetPolicy {
+    fn default() -> BudgetPolicy {
+        BudgetPolicy::Fail
+    }
+}
+
+/// The usage of a resource, as reported by the most recent response and
+/// counting requests sent since
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Usage {
+    remaining: u32,
+    /// requests this budget admitted during the window
+    admitted: u32,
+    /// seconds since the epoch, or 0 until a response reports the window
+    reset: u64,
+}
+
+impl Default for Usage {
+    fn default() -> Usage {
+        Usage {
+            remaining: u32::max_value(),
+            admitted: 0,
+            reset: 0,
+        }
+    }
+}
+
+/// Partitions of the rate limit quota of each resource. The usage a budget
+/// tracks is shared by all clones of a client
+#[derive(Clone, Debug, Default)]
+pub struct RateBudget {
+    partitions: HashMap<Resource, Partition>,
+    policy: BudgetPolicy,
+    usage: Arc<Mutex<HashMap<Resource, Usage>>>,
+}
+
+impl RateBudget {
+    pub fn builder() -> RateBudgetBuilder {
+        RateBudgetBuilder::default()
+    }
+
+    /// a budget with the same partitions, which hasn't tracked any usage.
+    /// for clients authenticating with different credentials
+    pub(crate) fn fresh(&self) -> RateBudget {
+        RateBudget {
+            partitions: self.partitions.clone(),
+            policy: self.policy,
+            usage: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// admit a request to `uri`, counting it against its resource. returns
+    /// how long to hold it back for, if it would overdraw its partition
+    pub(crate) fn admit(&self, uri: &str) -> Result<Option<Duration>> {
+        let resource = Resource::of(uri);
+        let partition = match self.partitions.get(&resource) {
+            Some(partition) => *partition,
+            None => return Ok(None),
+        };
+        let now = now();
+        let mut usage = self.usage.lock().unwrap();
+        let usage = usage.entry(resource.clone()).or_insert_with(Usage::default);
+        if usage.reset <= now {
+            // nothing is known about a window until a response reports it,
+            // but the requests admitted meanwhile are counted towards it
+            if usage.reset != 0 {
+                *usage = Usage::default();
+            }
+            usage.admitted += 1;
+            return Ok(None);
+        }
+        let exhausted = match partition {
+            Partition::Reserve(headroom) => usage.remaining <= headroom,
+            Partition::Cap(cap) => usage.admitted >= cap,
+        };
+        if !exhausted {
+            usage.remaining = usage.remaining.saturating_sub(1);
+            usage.admitted += 1;
+            return Ok(None);
+        }
+        let reset = Duration::from_secs(usage.reset - now);
+        match self.policy {
+            BudgetPolicy::WaitForReset(max_wait) if reset <= max_wait => Ok(Some(reset)),
+            _ => Err(ErrorKind::BudgetExhausted {
+                resource: resource.to_string(),
+                reset,
+            }
+            .into()),
+        }
+    }
+
+    /// record the usage reported by the headers of a response to `uri`
+    pub(crate) fn observe(&self, uri: &str, headers: &HeaderMap) {
+        let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+        let number = |name: &str| header(name).and_then(|value| value.parse::<u64>().ok());
+        let resource = header(X_RATELIMIT_RESOURCE)
+            .map(Resource::from)
+            .unwrap_or_else(|| Resource::of(uri));
+        if let (Some(remaining), Some(reset)) =
+            (number(X_RATELIMIT_REMAINING), number(X_RATELIMIT_RESET))
+        {
+            let mut usage = self.usage.lock().unwrap();
+            let usage = usage.entry(resource).or_insert_with(Usage::default);
+            // requests admitted before the window was reported count towards it
+            if usage.reset != 0 && usage.reset != reset {
+                usage.admitted = 0;
+            }
+            usage.remaining = remaining as u32;
+            usage.reset = reset;
+        }
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|now| now.as_secs())
+        .unwrap_or_default()
+}
+
+#[derive(Default)]
+pub struct RateBudgetBuilder(RateBudget);
+
+impl RateBudgetBuilder {
+    /// keep `headroom` requests of the resource's quota in reserve
+    pub fn reserve(&mut self, resource: Resource, headroom: u32) -> &mut Self {
+        self.0
+            .partitions
+            .insert(resource, Partition::Reserve(headroom));
+        self
+    }
+
+    /// send no more than `cap` requests of each of the resource's rate limit
+    /// windows, counting only those this budget admits
+    pub fn cap(&mut self, resource: Resource, cap: u32) -> &mut Self {
+        self.0.partitions.insert(resource, Partition::Cap(cap));
+        self
+    }
+
+    /// how to handle requests which would overdraw a partition. defaults to
+    /// `BudgetPolicy::Fail`
+    pub fn policy(&mut self, policy: BudgetPolicy) -> &mut Self {
+        self.0.policy = policy;
+        self
+    }
+
+    pub fn build(&self) -> RateBudget {
+        self.0.fresh()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Error;
+
+    fn headers(resource: &str, remaining: u32) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(X_RATELIMIT_RESOURCE, resource.parse().unwrap());
+        headers.insert(
+            X_RATELIMIT_REMAINING,
+            remaining.to_string().parse().unwrap(),
+        );
+        headers.insert(X_RATELIMIT_RESET, (now() + 60).to_string().parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn resources() {
+        assert_eq!(Resource::of("/repos/softprops/hubcaps"), Resource::Core);
+        assert_eq!(
+            Resource::of("https://api.github.com/search/issues?q=bug"),
+            Resource::Search
+        );
+        assert_eq!(Resource::of("/search/code?q=fn"), Resource::CodeSearch);
+        assert_eq!(
+            Resource::of("https://github.example.com/api/graphql"),
+            Resource::Graphql
+        );
+        assert_eq!(
+            Resource::of("https://github.example.com/api/v3/search/repositories"),
+            Resource::Search
+        );
+    }
+
+    #[test]
+    fn reserves_headroom() {
+        let budget = RateBudget::builder().reserve(Resource::Core, 500).build();
+        let uri = "/repos/softprops/hubcaps";
+        assert_eq!(budget.admit(uri).unwrap(), None);
+        budget.observe(uri, &headers("core", 501));
+        assert_eq!(budget.admit(uri).unwrap(), None);
+        match budget.admit(uri) {
+            Err(Error(ErrorKind::BudgetExhausted { ref resource, .. }, _)) => {
+                assert_eq!(resource, "core")
+            }
+            other => panic!("expected exhausted budget: {:?}", other),
+        }
+        // other resources are unaffected
+        assert_eq!(budget.admit("/search/issues?q=bug").unwrap(), None);
+    }
+
+    #[test]
+    fn caps_usage() {
+        let budget = RateBudget::builder()
+            .cap(Resource::Search, 2)
+            .policy(BudgetPolicy::WaitForReset(Duration::from_secs(120)))
+            .build();
+        let uri = "/search/issues?q=bug";
+        // requests admitted before a response reports the window count towards it
+        assert_eq!(budget.admit(uri).unwrap(), None);
+        // the usage of other clients sharing the token doesn't count towards the cap
+        budget.observe(uri, &headers("search", 21));
+        assert_eq!(budget.admit(uri).unwrap(), None);
+        let wait = budget.admit(uri).unwrap().expect("expected to wait");
+        assert!(wait <= Duration::from_secs(60));
+    }
+}
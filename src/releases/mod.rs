@@ -1,11 +1,37 @@
 //! Releases interface
 extern crate serde_json;
 
-use futures::future;
+use futures::{future, Future as StdFuture, Stream as StdStream};
 use hyper::client::connect::Connect;
+use hyper::{Chunk, StatusCode};
+use mime::Mime;
+use url::form_urlencoded;
 
 use users::User;
-use {Future, Github};
+use {unfold, Error, ErrorKind, Future, Github, Stream};
+
+fn identity<T>(x: T) -> T {
+    x
+}
+
+/// Github responds with a 422 and an `already_exists` field error when
+/// an asset with the same name was previously uploaded to a release
+fn already_exists(err: &Error) -> bool {
+    match *err {
+        Error(
+            ErrorKind::Fault {
+                code: StatusCode::UNPROCESSABLE_ENTITY,
+                ref error,
+            },
+            _,
+        ) => error
+            .errors
+            .as_ref()
+            .map(|errors| errors.iter().any(|e| e.code == "already_exists"))
+            .unwrap_or(false),
+        _ => false,
+    }
+}
 
 /// Provides access to assets for a release.
 /// See the [github docs](https://developer.github.com/v3/repos/releases/)
@@ -35,9 +61,6 @@ impl<C: Clone + Connect + 'static> Assets<C> {
         }
     }
 
-    // todo: upload asset
-    // todo: edit asset
-
     fn path(&self, more: &str) -> String {
         format!(
             "/repos/{}/{}/releases/{}/assets{}",
@@ -45,14 +68,121 @@ impl<C: Clone + Connect + 'static> Assets<C> {
         )
     }
 
-    // todo: stream interface to download
+    /// individual assets are addressed independently of their release
+    fn asset_path(&self, more: &str) -> String {
+        format!(
+            "/repos/{}/{}/releases/assets{}",
+            self.owner, self.repo, more
+        )
+    }
+
+    /// Upload an asset to this release.
+    ///
+    /// Assets are uploaded to Github's upload host rather than the api host.
+    /// When `overwrite` is set on the options, an existing asset with the same
+    /// name is deleted and the upload retried.
+    ///
+    /// See the [github docs](https://developer.github.com/v3/repos/releases/#upload-a-release-asset)
+    /// for more information.
+    pub fn upload<B>(&self, options: &AssetUploadOptions, content: B) -> Future<Asset>
+    where
+        B: Into<Vec<u8>>,
+    {
+        let content = content.into();
+        let uri = format!("{}?{}", self.path(""), options.serialize());
+        let upload =
+            self.github
+                .upload::<Asset>(&uri, content.clone(), options.content_type.clone());
+        if !options.overwrite {
+            return upload;
+        }
+        let github = self.github.clone();
+        let assets = self.path("");
+        let asset = self.asset_path("");
+        let name = options.name.clone();
+        let content_type = options.content_type.clone();
+        Box::new(upload.or_else(move |err| -> Future<Asset> {
+            if !already_exists(&err) {
+                return Box::new(future::err(err));
+            }
+            debug!("release asset {} already exists, replacing it", name);
+            let existing = unfold(github.clone(), github.get_pages(&assets), identity)
+                .filter(move |a: &Asset| a.name == name)
+                .into_future()
+                .map_err(|(err, _)| err);
+            Box::new(existing.and_then(move |(existing, _)| {
+                let deleted: Future<()> = match existing {
+                    Some(existing) => github.delete(&format!("{}/{}", asset, existing.id)),
+                    None => Box::new(future::ok(())),
+                };
+                deleted.and_then(move |_| github.upload(&uri, content, content_type))
+            }))
+        }))
+    }
+
+    /// Upload an asset to this release from a stream of `length` bytes, so
+    /// large artifacts needn't be held in memory.
+    ///
+    /// Streams can't be replayed, so when `overwrite` is set on the options an
+    /// existing asset with the same name is deleted before uploading, rather
+    /// than after a failed upload.
+    ///
+    /// See the [github docs](https://developer.github.com/v3/repos/releases/#upload-a-release-asset)
+    /// for more information.
+    pub fn upload_stream<S>(
+        &self,
+        options: &AssetUploadOptions,
+        length: u64,
+        content: S,
+    ) -> Future<Asset>
+    where
+        S: StdStream<Item = Chunk, Error = Error> + Send + 'static,
+    {
+        let uri = format!("{}?{}", self.path(""), options.serialize());
+        let content_type = options.content_type.clone();
+        if !options.overwrite {
+            return self
+                .github
+                .upload_stream(&uri, length, content, content_type);
+        }
+        let github = self.github.clone();
+        let asset = self.asset_path("");
+        let name = options.name.clone();
+        let existing = self
+            .iter()
+            .filter(move |a: &Asset| a.name == name)
+            .into_future()
+            .map_err(|(err, _)| err);
+        Box::new(existing.and_then(move |(existing, _)| {
+            let deleted: Future<()> = match existing {
+                Some(existing) => {
+                    debug!(
+                        "release asset {} already exists, replacing it",
+                        existing.name
+                    );
+                    github.delete(&format!("{}/{}", asset, existing.id))
+                }
+                None => Box::new(future::ok(())),
+            };
+            deleted.and_then(move |_| github.upload_stream(&uri, length, content, content_type))
+        }))
+    }
 
     /// Get the asset information.
     ///
     /// See the [github docs](https://developer.github.com/v3/repos/releases/#get-a-single-release-asset)
     /// for more information.
     pub fn get(&self, id: u64) -> Future<Asset> {
-        self.github.get(&self.path(&format!("/{}", id)))
+        self.github.get(&self.asset_path(&format!("/{}", id)))
+    }
+
+    /// Edit an asset's name or label.
+    ///
+    /// See the [github docs](https://developer.github.com/v3/repos/releases/#edit-a-release-asset)
+    /// for more information.
+    pub fn edit(&self, id: u64, options: &AssetEditOptions) -> Future<Asset> {
+        self.github
+            .patch(&self.asset_path(&format!("/{}", id)), json!(options))
     }
 
     /// Delete an asset by id.
@@ -60,7 +190,18 @@ impl<C: Clone + Connect + 'static> Assets<C> {
     /// See the [github docs](https://developer.github.com/v3/repos/releases/#delete-a-release-asset)
     /// for more information.
     pub fn delete(&self, id: u64) -> Future<()> {
-        self.github.delete(&self.path(&format!("/{}", id)))
+        self.github.delete(&self.asset_path(&format!("/{}", id)))
+    }
+
+    /// Download the binary content of an asset.
+    ///
+    /// Github redirects these requests to a storage host which is followed
+    /// transparently, yielding the asset's bytes as they arrive.
+    ///
+    /// See the [github docs](https://developer.github.com/v3/repos/releases/#get-a-single-release-asset)
+    /// for more information.
+    pub fn download(&self, id: u64) -> Stream<Chunk> {
+        self.github.download(&self.asset_path(&format!("/{}", id)))
     }
 
     /// List assets for a release.
@@ -70,6 +211,18 @@ impl<C: Clone + Connect + 'static> Assets<C> {
     pub fn list(&self) -> Future<Vec<Asset>> {
         self.github.get(&self.path(""))
     }
+
+    /// Provides a stream over all pages of assets for a release.
+    ///
+    /// See the [github docs](https://developer.github.com/v3/repos/releases/#list-assets-for-a-release)
+    /// for more information.
+    pub fn iter(&self) -> Stream<Asset> {
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&self.path("")),
+            identity,
+        )
+    }
 }
 
 pub struct ReleaseRef<C>
@@ -186,6 +339,15 @@ impl<C: Clone + Connect + 'static> Releases<C> {
         self.github.get(&self.path(""))
     }
 
+    /// provides a stream over all pages of releases
+    pub fn iter(&self) -> Stream<Release> {
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&self.path("")),
+            identity,
+        )
+    }
+
     /// Return the latest full release. Draft releases and prereleases are not returned.
     ///
     /// See the [github docs](https://developer.github.com/v3/repos/releases/#get-the-latest-release)
@@ -206,6 +368,43 @@ impl<C: Clone + Connect + 'static> Releases<C> {
             .get(&self.path(&format!("/tags/{}", tag_name.into())))
     }
 
+    /// Generate the name and body of release notes for a tag, based on the
+    /// pull requests merged since the previous release. The notes are not saved.
+    ///
+    /// See the [github docs](https://docs.github.com/en/rest/releases/releases#generate-release-notes-content-for-a-release)
+    /// for more information.
+    pub fn generate_notes<T>(
+        &self,
+        tag_name: T,
+        options: &ReleaseNotesOptions,
+    ) -> Future<ReleaseNotes>
+    where
+        T: Into<String>,
+    {
+        let request = GenerateNotesRequest {
+            tag_name: tag_name.into(),
+            options,
+        };
+        self.github
+            .post(&self.path("/generate-notes"), json!(request))
+    }
+
+    /// Upload an asset to a release by id.
+    ///
+    /// See the [github docs](https://developer.github.com/v3/repos/releases/#upload-a-release-asset)
+    /// for more information.
+    pub fn upload_asset<B>(
+        &self,
+        id: u64,
+        options: &AssetUploadOptions,
+        content: B,
+    ) -> Future<Asset>
+    where
+        B: Into<Vec<u8>>,
+    {
+        self.get(id).assets().upload(options, content)
+    }
+
     /// Get a reference to a specific release associated with a repository
     pub fn get(&self, id: u64) -> ReleaseRef<C> {
         ReleaseRef::new(
@@ -246,16 +445,89 @@ pub struct Release {
     pub id: u64,
     pub tag_name: String,
     pub target_commitish: String,
-    pub name: String,
-    pub body: String,
+    pub name: Option<String>,
+    pub body: Option<String>,
     pub draft: bool,
     pub prerelease: bool,
     pub created_at: String,
-    pub published_at: String,
+    /// `None` for drafts
+    pub published_at: Option<String>,
     pub author: User,
     pub assets: Vec<Asset>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ReleaseNotes {
+    pub name: String,
+    pub body: String,
+}
+
+#[derive(Serialize)]
+struct GenerateNotesRequest<'a> {
+    tag_name: String,
+    #[serde(flatten)]
+    options: &'a ReleaseNotesOptions,
+}
+
+/// options for generating release notes
+#[derive(Debug, Default, Serialize)]
+pub struct ReleaseNotesOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_commitish: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_tag_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub configuration_file_path: Option<String>,
+}
+
+impl ReleaseNotesOptions {
+    pub fn builder() -> ReleaseNotesOptionsBuilder {
+        ReleaseNotesOptionsBuilder::default()
+    }
+}
+
+/// builder interface for ReleaseNotesOptions
+#[derive(Default)]
+pub struct ReleaseNotesOptionsBuilder(ReleaseNotesOptions);
+
+impl ReleaseNotesOptionsBuilder {
+    /// the commitish the tag will be created from when it doesn't exist yet
+    pub fn commitish<C>(&mut self, commit: C) -> &mut Self
+    where
+        C: Into<String>,
+    {
+        self.0.target_commitish = Some(commit.into());
+        self
+    }
+
+    /// the tag to use as the starting point of the notes. defaults to the latest release
+    pub fn previous_tag_name<T>(&mut self, tag: T) -> &mut Self
+    where
+        T: Into<String>,
+    {
+        self.0.previous_tag_name = Some(tag.into());
+        self
+    }
+
+    /// path to a configuration file in the repository used to generate the notes.
+    /// defaults to `.github/release.yml`
+    pub fn configuration_file_path<P>(&mut self, path: P) -> &mut Self
+    where
+        P: Into<String>,
+    {
+        self.0.configuration_file_path = Some(path.into());
+        self
+    }
+
+    pub fn build(&self) -> ReleaseNotesOptions {
+        ReleaseNotesOptions {
+            target_commitish: self.0.target_commitish.clone(),
+            previous_tag_name: self.0.previous_tag_name.clone(),
+            configuration_file_path: self.0.configuration_file_path.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Default, Serialize)]
 pub struct ReleaseOptions {
     pub tag_name: String,
@@ -269,6 +541,23 @@ pub struct ReleaseOptions {
     pub draft: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub prerelease: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discussion_category_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generate_release_notes: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub make_latest: Option<MakeLatest>,
+}
+
+/// Whether a release is marked as the latest release of its repository
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MakeLatest {
+    True,
+    False,
+    /// the latest release is the most recently created non-draft,
+    /// non-prerelease release by semantic version
+    Legacy,
 }
 
 /// builder interface for ReleaseOptions
@@ -319,15 +608,42 @@ impl ReleaseOptionsBuilder {
         self
     }
 
+    /// start a discussion of the release in the given category
+    pub fn discussion_category_name<D>(&mut self, category: D) -> &mut Self
+    where
+        D: Into<String>,
+    {
+        self.0.discussion_category_name = Some(category.into());
+        self
+    }
+
+    /// generate the name and body of the release from the changes since
+    /// the previous release. a given name or body takes precedence
+    pub fn generate_release_notes(&mut self, generate: bool) -> &mut Self {
+        self.0.generate_release_notes = Some(generate);
+        self
+    }
+
+    /// set whether the release is marked as the repository's latest release
+    pub fn make_latest(&mut self, make_latest: MakeLatest) -> &mut Self {
+        self.0.make_latest = Some(make_latest);
+        self
+    }
+
     pub fn build(&self) -> ReleaseOptions {
-        ReleaseOptions::new(
-            self.0.tag_name.as_str(),
-            self.0.target_commitish.clone(),
-            self.0.name.clone(),
-            self.0.body.clone(),
-            self.0.draft,
-            self.0.prerelease,
-        )
+        ReleaseOptions {
+            discussion_category_name: self.0.discussion_category_name.clone(),
+            generate_release_notes: self.0.generate_release_notes,
+            make_latest: self.0.make_latest,
+            ..ReleaseOptions::new(
+                self.0.tag_name.as_str(),
+                self.0.target_commitish.clone(),
+                self.0.name.clone(),
+                self.0.body.clone(),
+                self.0.draft,
+                self.0.prerelease,
+            )
+        }
     }
 }
 
@@ -353,6 +669,7 @@ impl ReleaseOptions {
             body: body.map(|b| b.into()),
             draft,
             prerelease,
+            ..Default::default()
         }
     }
 
@@ -363,3 +680,160 @@ impl ReleaseOptions {
         ReleaseOptionsBuilder::new(tag)
     }
 }
+
+/// options for uploading a release asset
+#[derive(Debug)]
+pub struct AssetUploadOptions {
+    pub name: String,
+    pub label: Option<String>,
+    pub content_type: Mime,
+    /// replace an existing asset of the same name rather than failing
+    pub overwrite: bool,
+}
+
+impl AssetUploadOptions {
+    pub fn builder<N>(name: N, content_type: Mime) -> AssetUploadOptionsBuilder
+    where
+        N: Into<String>,
+    {
+        AssetUploadOptionsBuilder::new(name, content_type)
+    }
+
+    /// serialize the name and label as upload query parameters
+    pub fn serialize(&self) -> String {
+        let mut query = form_urlencoded::Serializer::new(String::new());
+        query.append_pair("name", &self.name);
+        if let Some(ref label) = self.label {
+            query.append_pair("label", label);
+        }
+        query.finish()
+    }
+}
+
+/// builder interface for AssetUploadOptions
+pub struct AssetUploadOptionsBuilder(AssetUploadOptions);
+
+impl AssetUploadOptionsBuilder {
+    pub(crate) fn new<N>(name: N, content_type: Mime) -> Self
+    where
+        N: Into<String>,
+    {
+        AssetUploadOptionsBuilder(AssetUploadOptions {
+            name: name.into(),
+            label: None,
+            content_type,
+            overwrite: false,
+        })
+    }
+
+    /// an alternate short description of the asset used in place of its filename
+    pub fn label<L>(&mut self, label: L) -> &mut Self
+    where
+        L: Into<String>,
+    {
+        self.0.label = Some(label.into());
+        self
+    }
+
+    /// replace an existing asset with the same name
+    pub fn overwrite(&mut self, overwrite: bool) -> &mut Self {
+        self.0.overwrite = overwrite;
+        self
+    }
+
+    pub fn build(&self) -> AssetUploadOptions {
+        AssetUploadOptions {
+            name: self.0.name.clone(),
+            label: self.0.label.clone(),
+            content_type: self.0.content_type.clone(),
+            overwrite: self.0.overwrite,
+        }
+    }
+}
+
+/// options for editing a release asset
+#[derive(Debug, Default, Serialize)]
+pub struct AssetEditOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+impl AssetEditOptions {
+    pub fn builder() -> AssetEditOptionsBuilder {
+        AssetEditOptionsBuilder::default()
+    }
+}
+
+/// builder interface for AssetEditOptions
+#[derive(Default)]
+pub struct AssetEditOptionsBuilder(AssetEditOptions);
+
+impl AssetEditOptionsBuilder {
+    /// rename the asset's file name
+    pub fn name<N>(&mut self, name: N) -> &mut Self
+    where
+        N: Into<String>,
+    {
+        self.0.name = Some(name.into());
+        self
+    }
+
+    /// an alternate short description of the asset used in place of its filename
+    pub fn label<L>(&mut self, label: L) -> &mut Self
+    where
+        L: Into<String>,
+    {
+        self.0.label = Some(label.into());
+        self
+    }
+
+    pub fn build(&self) -> AssetEditOptions {
+        AssetEditOptions {
+            name: self.0.name.clone(),
+            label: self.0.label.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_notes_reqs() {
+        for (options, expect) in vec![
+            (ReleaseNotesOptions::default(), r#"{"tag_name":"v1.0.0"}"#),
+            (
+                ReleaseNotesOptions::builder()
+                    .previous_tag_name("v0.9.0")
+                    .build(),
+                r#"{"tag_name":"v1.0.0","previous_tag_name":"v0.9.0"}"#,
+            ),
+        ] {
+            let request = GenerateNotesRequest {
+                tag_name: "v1.0.0".into(),
+                options: &options,
+            };
+            assert_eq!(serde_json::to_string(&request).unwrap(), expect)
+        }
+    }
+
+    #[test]
+    fn release_reqs() {
+        let options = ReleaseOptions::builder("v1.0.0")
+            .commitish("main")
+            .draft(true)
+            .generate_release_notes(true)
+            .make_latest(MakeLatest::Legacy)
+            .build();
+        assert_eq!(
+            serde_json::to_string(&options).unwrap(),
+            concat!(
+                r#"{"tag_name":"v1.0.0","target_commitish":"main","draft":true,"#,
+                r#""generate_release_notes":true,"make_latest":"legacy"}"#
+            )
+        );
+    }
+}
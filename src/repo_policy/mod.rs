@@ -0,0 +1,461 @@
+//! Repository policy interface
+//!
+//! A `RepoPolicy` declares the settings every repository of an organization
+//! should have, like whether head branches are deleted once merged or how
+//! default branches are protected. `Organization::repo_settings_drift`
+//! reports the repositories whose settings drifted from a policy, and
+//! `Organization::enforce_repo_settings` also updates them to match it.
+//! Archived repositories are read-only, so they are left alone.
+
+use std::fmt;
+
+use futures::{future, Future as StdFuture, Stream as StdStream};
+use hyper::client::connect::Connect;
+use hyper::StatusCode;
+
+use branches::{Protection, StatusChecks};
+use repositories::{Repo, RepoEditOptions, RepoSlug, Repository};
+use {Error, ErrorKind, Future, Github, Stream};
+
+/// compares each of `repos` with `policy`, concurrently, yielding those
+/// which drifted from it. drifted repositories are updated when `enforce`
+/// is set
+pub(crate) fn reconcile<C>(
+    github: Github<C>,
+    repos: Stream<Repo>,
+    policy: RepoPolicy,
+    enforce: bool,
+) -> Stream<RepoDrift>
+where
+    C: Clone + Connect + 'static,
+{
+    Box::new(
+        repos
+            .filter(|repo| !repo.archived)
+            .map(|repo| RepoSlug {
+                owner: repo.owner.login,
+                repo: repo.name,
+            })
+            .collect()
+            .map(move |slugs| {
+                let requests = slugs
+                    .into_iter()
+                    .map(|slug| reconcile_repo(github.clone(), slug, policy.clone(), enforce))
+                    .collect::<Vec<_>>();
+                github.batch().run(requests)
+            })
+            .flatten_stream()
+            .filter(|drift| !drift.drift.is_empty()),
+    )
+}
+
+fn reconcile_repo<C>(
+    github: Github<C>,
+    slug: RepoSlug,
+    policy: RepoPolicy,
+    enforce: bool,
+) -> Future<RepoDrift>
+where
+    C: Clone + Connect + 'static,
+{
+    let repository = Repository::from_slug(github.clone(), slug.clone());
+    Box::new(repository.get().and_then(move |repo| {
+        let mut drift = settings_drift(&policy, &repo);
+        let branch = repo.default_branch.clone();
+        let protection_drifted: Future<bool> = match policy.default_branch_protection {
+            Some(ref protection) => {
+                let protection = protection.clone();
+                Box::new(
+                    current_protection(&github, &slug, &branch)
+                        .map(move |current| !protection_matches(&protection, current.as_ref())),
+                )
+            }
+            None => Box::new(future::ok(false)),
+        };
+        protection_drifted.and_then(move |protection_drifted| -> Future<RepoDrift> {
+            if protection_drifted {
+                drift.push(Drift::BranchProtection {
+                    branch: branch.clone(),
+                });
+            }
+            if !enforce || drift.is_empty() {
+                return Box::new(future::ok(RepoDrift { repo: slug, drift }));
+            }
+            let edit: Future<()> = match edit_options(&repo.name, &drift) {
+                Some(options) => Box::new(repository.edit(&options).map(|_| ())),
+                None => Box::new(future::ok(())),
+            };
+            let protect: Future<()> = match (protection_drifted, policy.default_branch_protection) {
+                (true, Some(protection)) => Box::new(
+                    repository
+                        .branches()
+                        .protection(branch, &protection)
+                        .map(|_| ()),
+                ),
+                _ => Box::new(future::ok(())),
+            };
+            Box::new(
+                edit.join(protect)
+                    .map(move |_| RepoDrift { repo: slug, drift }),
+            )
+        })
+    }))
+}
+
+/// the protection of a branch, if it is protected
+fn current_protection<C>(
+    github: &Github<C>,
+    slug: &RepoSlug,
+    branch: &str,
+) -> Future<Option<CurrentProtection>>
+where
+    C: Clone + Connect + 'static,
+{
+    Box::new(
+        github
+            .get::<CurrentProtection>(&format!(
+                "/repos/{}/{}/branches/{}/protection",
+                slug.owner, slug.repo, branch
+            ))
+            .map(Some)
+            .or_else(|err| match err {
+                Error(
+                    ErrorKind::Fault {
+                        code: StatusCode::NOT_FOUND,
+                        ..
+                    },
+                    _,
+                ) => Ok(None),
+                otherwise => Err(otherwise),
+            }),
+    )
+}
+
+/// the settings of `repo` which differ from `policy`
+fn settings_drift(policy: &RepoPolicy, repo: &Repo) -> Vec<Drift> {
+    let settings = [
+        (
+            Setting::DeleteBranchOnMerge,
+            policy.delete_branch_on_merge,
+            repo.delete_branch_on_merge,
+        ),
+        (
+            Setting::AllowSquashMerge,
+            policy.allow_squash_merge,
+            repo.allow_squash_merge,
+        ),
+        (
+            Setting::AllowMergeCommit,
+            policy.allow_merge_commit,
+            repo.allow_merge_commit,
+        ),
+        (
+            Setting::AllowRebaseMerge,
+            policy.allow_rebase_merge,
+            repo.allow_rebase_merge,
+        ),
+        (Setting::HasIssues, policy.has_issues, Some(repo.has_issues)),
+        (Setting::HasWiki, policy.has_wiki, Some(repo.has_wiki)),
+    ];
+    settings
+        .iter()
+        .filter_map(|&(setting, expected, actual)| match expected {
+            Some(expected) if actual != Some(expected) => Some(Drift::Setting {
+                setting,
+                expected,
+                actual,
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// the options which update the settings which drifted, if any did
+fn edit_options(name: &str, drift: &[Drift]) -> Option<RepoEditOptions> {
+    let mut options = RepoEditOptions::builder(name);
+    let mut edited = false;
+    for drift in drift {
+        if let Drift::Setting {
+            setting, expected, ..
+        } = *drift
+        {
+            edited = true;
+            match setting {
+                Setting::DeleteBranchOnMerge => options.delete_branch_on_merge(expected),
+                Setting::AllowSquashMerge => options.allow_squash_merge(expected),
+                Setting::AllowMergeCommit => options.allow_merge_commit(expected),
+                Setting::AllowRebaseMerge => options.allow_rebase_merge(expected),
+                Setting::HasIssues => options.has_issues(expected),
+                Setting::HasWiki => options.has_wiki(expected),
+            };
+        }
+    }
+    if edited {
+        Some(options.build())
+    } else {
+        None
+    }
+}
+
+/// whether a branch's protection enforces `desired`
+fn protection_matches(desired: &Protection, current: Option<&CurrentProtection>) -> bool {
+    let current = match current {
+        Some(current) => current,
+        None => return false,
+    };
+    let checks = match (
+        &desired.required_status_checks,
+        &current.required_status_checks,
+    ) {
+        (&Some(ref desired), &Some(ref current)) => {
+            desired.strict == current.strict && same(&desired.contexts, &current.contexts)
+        }
+        (&None, &None) => true,
+        _ => false,
+    };
+    let admins = current
+        .enforce_admins
+        .as_ref()
+        .map_or(false, |admins| admins.enabled)
+        == desired.enforce_admins;
+    let reviews = match (
+        &desired.required_pull_request_reviews,
+        &current.required_pull_request_reviews,
+    ) {
+        (&Some(ref desired), &Some(ref current)) => {
+            desired.dismiss_stale_reviews == current.dismiss_stale_reviews
+                && desired.require_code_owner_reviews == current.require_code_owner_reviews
+        }
+        (&None, &None) => true,
+        _ => false,
+    };
+    let restrictions = match (&desired.restrictions, &current.restrictions) {
+        (&Some(ref desired), &Some(ref current)) => {
+            let users = current
+                .users
+                .iter()
+                .map(|user| user.login.clone())
+                .collect::<Vec<_>>();
+            let teams = current
+                .teams
+                .iter()
+                .map(|team| team.slug.clone())
+                .collect::<Vec<_>>();
+            same(&desired.users, &users) && same(&desired.teams, &teams)
+        }
+        (&None, &None) => true,
+        _ => false,
+    };
+    checks && admins && reviews && restrictions
+}
+
+/// whether two lists hold the same values, in any order
+fn same(a: &[String], b: &[String]) -> bool {
+    let mut a = a.to_vec();
+    let mut b = b.to_vec();
+    a.sort();
+    b.sort();
+    a == b
+}
+
+// representations
+
+/// The settings every repository should have. Settings which aren't set
+/// are left as they are
+#[derive(Clone, Debug, Default)]
+pub struct RepoPolicy {
+    delete_branch_on_merge: Option<bool>,
+    allow_squash_merge: Option<bool>,
+    allow_merge_commit: Option<bool>,
+    allow_rebase_merge: Option<bool>,
+    has_issues: Option<bool>,
+    has_wiki: Option<bool>,
+    default_branch_protection: Option<Protection>,
+}
+
+impl RepoPolicy {
+    pub fn builder() -> RepoPolicyBuilder {
+        RepoPolicyBuilder::default()
+    }
+}
+
+#[derive(Default)]
+pub struct RepoPolicyBuilder(RepoPolicy);
+
+impl RepoPolicyBuilder {
+    /// delete head branches once their pull requests are merged
+    pub fn delete_branch_on_merge(&mut self, delete_branch_on_merge: bool) -> &mut Self {
+        self.0.delete_branch_on_merge = Some(delete_branch_on_merge);
+        self
+    }
+
+    pub fn allow_squash_merge(&mut self, allow_squash_merge: bool) -> &mut Self {
+        self.0.allow_squash_merge = Some(allow_squash_merge);
+        self
+    }
+
+    pub fn allow_merge_commit(&mut self, allow_merge_commit: bool) -> &mut Self {
+        self.0.allow_merge_commit = Some(allow_merge_commit);
+        self
+    }
+
+    pub fn allow_rebase_merge(&mut self, allow_rebase_merge: bool) -> &mut Self {
+        self.0.allow_rebase_merge = Some(allow_rebase_merge);
+        self
+    }
+
+    pub fn has_issues(&mut self, has_issues: bool) -> &mut Self {
+        self.0.has_issues = Some(has_issues);
+        self
+    }
+
+    pub fn has_wiki(&mut self, has_wiki: bool) -> &mut Self {
+        self.0.has_wiki = Some(has_wiki);
+        self
+    }
+
+    /// the protection of each repository's default branch
+    pub fn default_branch_protection(&mut self, protection: Protection) -> &mut Self {
+        self.0.default_branch_protection = Some(protection);
+        self
+    }
+
+    pub fn build(&self) -> RepoPolicy {
+        self.0.clone()
+    }
+}
+
+/// A repository setting a `RepoPolicy` may declare
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Setting {
+    DeleteBranchOnMerge,
+    AllowSquashMerge,
+    AllowMergeCommit,
+    AllowRebaseMerge,
+    HasIssues,
+    HasWiki,
+}
+
+impl fmt::Display for Setting {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Setting::DeleteBranchOnMerge => "delete_branch_on_merge",
+            Setting::AllowSquashMerge => "allow_squash_merge",
+            Setting::AllowMergeCommit => "allow_merge_commit",
+            Setting::AllowRebaseMerge => "allow_rebase_merge",
+            Setting::HasIssues => "has_issues",
+            Setting::HasWiki => "has_wiki",
+        }
+        .fmt(f)
+    }
+}
+
+/// A difference between a repository and a `RepoPolicy`
+#[derive(Clone, Debug, PartialEq)]
+pub enum Drift {
+    /// a setting differs from the policy. the actual value of merge
+    /// settings is `None` when they weren't reported
+    Setting {
+        setting: Setting,
+        expected: bool,
+        actual: Option<bool>,
+    },
+    /// the default branch is unprotected, or protected differently
+    BranchProtection { branch: String },
+}
+
+/// The ways a repository drifted from a `RepoPolicy`
+#[derive(Clone, Debug, PartialEq)]
+pub struct RepoDrift {
+    pub repo: RepoSlug,
+    pub drift: Vec<Drift>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CurrentProtection {
+    required_status_checks: Option<StatusChecks>,
+    enforce_admins: Option<Enabled>,
+    required_pull_request_reviews: Option<CurrentReviews>,
+    restrictions: Option<CurrentRestrictions>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Enabled {
+    enabled: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct CurrentReviews {
+    #[serde(default)]
+    dismiss_stale_reviews: bool,
+    #[serde(default)]
+    require_code_owner_reviews: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct CurrentRestrictions {
+    users: Vec<Login>,
+    teams: Vec<Slug>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Login {
+    login: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Slug {
+    slug: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn protection_drift() {
+        let desired = Protection {
+            required_status_checks: Some(StatusChecks {
+                strict: true,
+                contexts: vec!["ci/test".into(), "ci/lint".into()],
+            }),
+            enforce_admins: true,
+            required_pull_request_reviews: None,
+            restrictions: None,
+        };
+        assert!(!protection_matches(&desired, None));
+        let current: CurrentProtection = serde_json::from_str(
+            r#"{
+              "url": "https://api.github.com/repos/octocat/Hello-World/branches/master/protection",
+              "required_status_checks": {"strict": true, "contexts": ["ci/lint", "ci/test"]},
+              "enforce_admins": {"url": "https://api.github.com/repos/octocat/Hello-World/branches/master/protection/enforce_admins", "enabled": true}
+            }"#,
+        )
+        .unwrap();
+        assert!(protection_matches(&desired, Some(&current)));
+        let lax = Protection {
+            enforce_admins: false,
+            ..desired
+        };
+        assert!(!protection_matches(&lax, Some(&current)));
+    }
+
+    #[test]
+    fn edit_drifted_settings() {
+        let drift = vec![
+            Drift::Setting {
+                setting: Setting::DeleteBranchOnMerge,
+                expected: true,
+                actual: Some(false),
+            },
+            Drift::BranchProtection {
+                branch: "master".into(),
+            },
+        ];
+        let options = edit_options("hubcaps", &drift).unwrap();
+        assert_eq!(options.delete_branch_on_merge, Some(true));
+        assert_eq!(options.has_wiki, None);
+        assert!(edit_options("hubcaps", &drift[1..]).is_none());
+    }
+}
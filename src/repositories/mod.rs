@@ -3,29 +3,54 @@ extern crate futures;
 extern crate serde_json;
 
 use std::collections::HashMap;
+use std::error::Error as StdError;
 use std::fmt;
+use std::str::FromStr;
 
-use futures::future;
+use futures::{future, Future as StdFuture, Stream as StdStream};
 use hyper::client::connect::Connect;
-use url::{form_urlencoded, Url};
+use hyper::StatusCode;
+use url::form_urlencoded;
 
-use branches::Branches;
+use actions::Actions;
+use blame::{Blame, BlameRange, BLAME_QUERY};
+use branches::{Branch, Branches};
 use checks::CheckRuns;
+use code_scanning::CodeScanning;
+use codeowners::{self, CodeOwners};
+use compare::Compare;
 use content::Content;
+use custom_properties::CustomProperties;
+use dependabot::Dependabot;
+use dependency_graph::DependencyGraph;
 use deployments::Deployments;
+use discussions::Discussions;
+use environments::Environment;
+use events::Events;
 use git::Git;
+use graphql::{extract, json_vars};
 use hooks::Hooks;
+use ids::{IssueNumber, RepoId};
 use issues::{IssueRef, Issues};
 use keys::Keys;
 use labels::Labels;
+use projects::Projects;
 use pulls::PullRequests;
 use releases::Releases;
-use statuses::Statuses;
+use rulesets::{Ruleset, RulesetOptions, Rulesets};
+use secret_scanning::SecretScanning;
+use security_advisories::SecurityAdvisories;
+use serde_json::Value;
+use source_import::SourceImport;
+use stars::Stargazers;
+use stats::Stats;
+use statuses::{RefStatus, Statuses};
+use tag_protection::TagProtections;
 use teams::RepoTeams;
 use traffic::Traffic;
 use users::Contributors;
 use users::User;
-use {unfold, Future, Github, SortDirection, Stream};
+use {unfold, Error, ErrorKind, Future, Github, SortDirection, Stream};
 
 fn identity<T>(x: T) -> T {
     x
@@ -338,6 +363,65 @@ impl<C: Clone + Connect + 'static> OrganizationRepositories<C> {
     }
 }
 
+/// A repository's `owner/name` slug, like `softprops/hubcaps`
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RepoSlug {
+    pub owner: String,
+    pub repo: String,
+}
+
+impl fmt::Display for RepoSlug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.owner, self.repo)
+    }
+}
+
+/// An error parsing a `RepoSlug`
+#[derive(Debug, PartialEq)]
+pub struct RepoSlugParseError(String);
+
+impl fmt::Display for RepoSlugParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid repository {:?}, expected owner/name", self.0)
+    }
+}
+
+impl StdError for RepoSlugParseError {
+    fn description(&self) -> &str {
+        "invalid repository slug"
+    }
+}
+
+impl FromStr for RepoSlug {
+    type Err = RepoSlugParseError;
+
+    fn from_str(s: &str) -> ::std::result::Result<RepoSlug, RepoSlugParseError> {
+        let err = || RepoSlugParseError(s.to_owned());
+        let mut parts = s.trim().splitn(2, '/');
+        let owner = parts.next().unwrap_or_default();
+        let repo = parts.next().ok_or_else(err)?;
+        // owners are alphanumeric with single hyphens, which may not lead or trail
+        let valid_owner = !owner.is_empty()
+            && !owner.starts_with('-')
+            && !owner.ends_with('-')
+            && !owner.contains("--")
+            && owner.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+        let valid_repo = !repo.is_empty()
+            && repo != "."
+            && repo != ".."
+            && repo
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.');
+        if !valid_owner || !valid_repo {
+            return Err(err());
+        }
+        Ok(RepoSlug {
+            owner: owner.into(),
+            repo: repo.into(),
+        })
+    }
+}
+
 pub struct Repository<C>
 where
     C: Clone + Connect + 'static,
@@ -361,6 +445,11 @@ impl<C: Clone + Connect + 'static> Repository<C> {
         }
     }
 
+    /// create a reference to the repository named by `slug`
+    pub fn from_slug(github: Github<C>, slug: RepoSlug) -> Self {
+        Self::new(github, slug.owner, slug.repo)
+    }
+
     fn path(&self, more: &str) -> String {
         format!("/repos/{}/{}{}", self.owner, self.repo, more)
     }
@@ -370,6 +459,13 @@ impl<C: Clone + Connect + 'static> Repository<C> {
         self.github.get(&self.path(""))
     }
 
+    /// Returns a map of the
+    /// [languages](https://developer.github.com/v3/repos/#list-languages) the repository is
+    /// implemented in to the number of bytes of code written in each
+    pub fn languages(&self) -> Future<HashMap<String, u64>> {
+        self.github.get(&self.path("/languages"))
+    }
+
     /// https://developer.github.com/v3/repos/#edit
     pub fn edit(&self, options: &RepoEditOptions) -> Future<Repo> {
         // Note that this intentionally calls POST rather than PATCH,
@@ -378,11 +474,186 @@ impl<C: Clone + Connect + 'static> Repository<C> {
         self.github.post(&self.path(""), json!(options))
     }
 
+    /// archive this repository, making it read-only. writes to an archived
+    /// repository fail with `ErrorKind::ArchivedRepository`
+    pub fn archive_repo(&self) -> Future<Repo> {
+        self.edit(
+            &RepoEditOptions::builder(self.repo.as_str())
+                .archived(true)
+                .build(),
+        )
+    }
+
+    /// unarchive this repository, allowing writes again
+    pub fn unarchive(&self) -> Future<Repo> {
+        self.edit(
+            &RepoEditOptions::builder(self.repo.as_str())
+                .archived(false)
+                .build(),
+        )
+    }
+
+    /// enable Git LFS for this repository, so files can be pushed to Git LFS
+    /// storage. Github enables it asynchronously
+    pub fn enable_lfs(&self) -> Future<()> {
+        self.github.put_no_response(&self.path("/lfs"), Vec::new())
+    }
+
+    /// disable Git LFS for this repository
+    pub fn disable_lfs(&self) -> Future<()> {
+        self.github.delete(&self.path("/lfs"))
+    }
+
+    /// sync a branch of this fork with its upstream repository, without
+    /// a local clone. a branch which can't be synced due to a merge
+    /// conflict yields `MergeUpstream::Conflict` rather than an error
+    ///
+    /// https://docs.github.com/en/rest/branches/branches#sync-a-fork-branch-with-the-upstream-repository
+    pub fn merge_upstream<B>(&self, branch: B) -> Future<MergeUpstream>
+    where
+        B: Into<String>,
+    {
+        let mut payload = HashMap::new();
+        payload.insert("branch", branch.into());
+        Box::new(
+            self.github
+                .post(&self.path("/merge-upstream"), json!(payload))
+                .map(MergeUpstream::Synced)
+                .or_else(|err| match err {
+                    Error(
+                        ErrorKind::Fault {
+                            code: StatusCode::CONFLICT,
+                            error,
+                        },
+                        _,
+                    ) => Ok(MergeUpstream::Conflict(error.message)),
+                    otherwise => Err(otherwise),
+                }),
+        )
+    }
+
     /// get a reference to branch operations
     pub fn branches(&self) -> Branches<C> {
         Branches::new(self.github.clone(), self.owner.as_str(), self.repo.as_str())
     }
 
+    /// rename this repository's default branch, as when migrating from
+    /// `master` to `main`. Besides the branch protection rules Github
+    /// updates when renaming a branch, this repository's rulesets which
+    /// target the old branch by name are updated to target the new one
+    pub fn rename_default_branch<N>(&self, new_name: N) -> Future<Branch>
+    where
+        N: Into<String>,
+    {
+        let new_name = new_name.into();
+        let repository =
+            Repository::new(self.github.clone(), self.owner.clone(), self.repo.clone());
+        Box::new(self.get().and_then(move |repo| {
+            let old_name = repo.default_branch;
+            repository
+                .branches()
+                .rename(old_name.as_str(), new_name.as_str())
+                .and_then(move |branch| {
+                    repository
+                        .retarget_rulesets(&old_name, &new_name)
+                        .map(|_| branch)
+                })
+        }))
+    }
+
+    /// point the rulesets defined on this repository which include or
+    /// exclude the branch `old_name` at `new_name` instead
+    fn retarget_rulesets(&self, old_name: &str, new_name: &str) -> Future<()> {
+        let (lookup, rulesets) = (self.rulesets(), self.rulesets());
+        let source = format!("{}/{}", self.owner, self.repo);
+        let (old_name, new_name) = (old_name.to_owned(), new_name.to_owned());
+        Box::new(
+            self.rulesets()
+                .iter()
+                .filter(move |ruleset| ruleset.source == source)
+                .and_then(move |ruleset| lookup.get(ruleset.id))
+                .filter_map(move |ruleset| {
+                    retargeted(&ruleset, &old_name, &new_name).map(|options| (ruleset.id, options))
+                })
+                .collect()
+                .and_then(move |updates| {
+                    future::join_all(
+                        updates
+                            .into_iter()
+                            .map(move |(id, options)| rulesets.update(id, &options))
+                            .collect::<Vec<_>>(),
+                    )
+                })
+                .map(|_| ()),
+        )
+    }
+
+    /// get the blame of a file at a reference, like a branch, tag or commit
+    /// sha: the ranges of its lines along with the commits which last
+    /// changed them. Github only exposes blame through its GraphQL api
+    pub fn blame<P, R>(&self, path: P, reference: R) -> Future<Vec<BlameRange>>
+    where
+        P: Into<String>,
+        R: Into<String>,
+    {
+        let variables = json_vars(&[
+            ("owner", Value::String(self.owner.clone())),
+            ("repo", Value::String(self.repo.clone())),
+            ("reference", Value::String(reference.into())),
+            ("path", Value::String(path.into())),
+        ]);
+        Box::new(
+            self.github
+                .graphql()
+                .query::<_, Value>(BLAME_QUERY, &variables)
+                .and_then(|data| {
+                    extract::<Blame>(&data, "/repository/object/blame").map(|blame| blame.ranges)
+                }),
+        )
+    }
+
+    /// get the rules of the repository's `CODEOWNERS` file on its default
+    /// branch. repositories without one have no rules
+    pub fn codeowners(&self) -> Future<CodeOwners> {
+        codeowners::fetch(
+            self.github.clone(),
+            self.owner.clone(),
+            self.repo.clone(),
+            None,
+        )
+    }
+
+    /// compare two commits, branches or tags of the repository
+    pub fn compare<B, H>(&self, base: B, head: H) -> Compare<C>
+    where
+        B: Into<String>,
+        H: Into<String>,
+    {
+        Compare::new(
+            self.github.clone(),
+            self.owner.as_str(),
+            self.repo.as_str(),
+            base,
+            head,
+        )
+    }
+
+    /// compare a commit, branch or tag of the repository with one of a fork
+    /// owned by `head_owner`, or any repository in its network
+    pub fn compare_fork<B, O, H>(&self, base: B, head_owner: O, head: H) -> Compare<C>
+    where
+        B: Into<String>,
+        O: Into<String>,
+        H: Into<String>,
+    {
+        self.compare(base, format!("{}:{}", head_owner.into(), head.into()))
+    }
+
+    /// get a reference to the repository's discussions
+    pub fn discussions(&self) -> Discussions<C> {
+        Discussions::new(self.github.clone(), self.owner.as_str(), self.repo.as_str())
+    }
+
     /// get a reference to content operations
     pub fn content(&self) -> Content<C> {
         Content::new(self.github.clone(), self.owner.as_str(), self.repo.as_str())
@@ -404,8 +675,24 @@ impl<C: Clone + Connect + 'static> Repository<C> {
         Deployments::new(self.github.clone(), self.owner.as_str(), self.repo.as_str())
     }
 
+    /// get a reference to a deployment environment of this repository
+    pub fn environment<N>(&self, name: N) -> Environment<C>
+    where
+        N: Into<String>,
+    {
+        Environment::new(
+            self.github.clone(),
+            self.owner.as_str(),
+            self.repo.as_str(),
+            name,
+        )
+    }
+
     /// get a reference to a specific github issue associated with this repository ref
-    pub fn issue(&self, number: u64) -> IssueRef<C> {
+    pub fn issue<N>(&self, number: N) -> IssueRef<C>
+    where
+        N: Into<IssueNumber>,
+    {
         IssueRef::new(
             self.github.clone(),
             self.owner.as_str(),
@@ -424,6 +711,30 @@ impl<C: Clone + Connect + 'static> Repository<C> {
         CheckRuns::new(self.github.clone(), self.owner.as_str(), self.repo.as_str())
     }
 
+    /// get a reference to [code scanning](https://docs.github.com/en/rest/code-scanning)
+    /// operations associated with this repository ref
+    pub fn code_scanning(&self) -> CodeScanning<C> {
+        CodeScanning::new(self.github.clone(), self.owner.as_str(), self.repo.as_str())
+    }
+
+    /// get a reference to [Github Actions](https://docs.github.com/en/rest/actions)
+    /// operations associated with this repository ref
+    pub fn actions(&self) -> Actions<C> {
+        Actions::new(self.github.clone(), self.owner.as_str(), self.repo.as_str())
+    }
+
+    /// get a reference to [Dependabot](https://docs.github.com/en/rest/dependabot)
+    /// alerts and secrets associated with this repository ref
+    pub fn dependabot(&self) -> Dependabot<C> {
+        Dependabot::new(self.github.clone(), self.owner.as_str(), self.repo.as_str())
+    }
+
+    /// get a reference to the [dependency graph](https://docs.github.com/en/rest/dependency-graph)
+    /// of this repository ref
+    pub fn dependency_graph(&self) -> DependencyGraph<C> {
+        DependencyGraph::new(self.github.clone(), self.owner.as_str(), self.repo.as_str())
+    }
+
     /// get a reference to [deploy keys](https://developer.github.com/v3/repos/keys/)
     /// associated with this repository ref
     pub fn keys(&self) -> Keys<C> {
@@ -441,18 +752,80 @@ impl<C: Clone + Connect + 'static> Repository<C> {
         PullRequests::new(self.github.clone(), self.owner.as_str(), self.repo.as_str())
     }
 
+    /// get a reference to classic [projects](https://docs.github.com/en/rest/projects)
+    /// associated with this repository ref
+    pub fn projects(&self) -> Projects<C> {
+        Projects::new(self.github.clone(), self.path("/projects"))
+    }
+
     /// get a reference to [releases](https://developer.github.com/v3/repos/releases/)
     /// associated with this repository ref
     pub fn releases(&self) -> Releases<C> {
         Releases::new(self.github.clone(), self.owner.as_str(), self.repo.as_str())
     }
 
+    /// get a reference to the [events](https://docs.github.com/en/rest/activity/events)
+    /// of this repository ref
+    pub fn events(&self) -> Events<C> {
+        Events::new(self.github.clone(), self.path("/events"))
+    }
+
+    /// get a reference to [rulesets](https://docs.github.com/en/rest/repos/rules)
+    /// associated with this repository ref
+    pub fn rulesets(&self) -> Rulesets<C> {
+        Rulesets::new(self.github.clone(), self.path(""))
+    }
+
+    /// get a reference to the [custom property](https://docs.github.com/en/rest/repos/custom-properties)
+    /// values of this repository ref
+    pub fn custom_properties(&self) -> CustomProperties<C> {
+        CustomProperties::new(self.github.clone(), self.owner.as_str(), self.repo.as_str())
+    }
+
+    /// get a reference to [secret scanning](https://docs.github.com/en/rest/secret-scanning)
+    /// operations associated with this repository ref
+    pub fn secret_scanning(&self) -> SecretScanning<C> {
+        SecretScanning::new(self.github.clone(), self.owner.as_str(), self.repo.as_str())
+    }
+
+    /// get a reference to [security advisories](https://docs.github.com/en/rest/security-advisories/repository-advisories)
+    /// associated with this repository ref
+    pub fn security_advisories(&self) -> SecurityAdvisories<C> {
+        SecurityAdvisories::new(self.github.clone(), self.owner.as_str(), self.repo.as_str())
+    }
+
+    /// get a reference to the [source import](https://docs.github.com/en/rest/migrations/source-imports)
+    /// of this repository ref
+    pub fn source_import(&self) -> SourceImport<C> {
+        SourceImport::new(self.github.clone(), self.owner.as_str(), self.repo.as_str())
+    }
+
+    /// get a reference to [tag protection](https://docs.github.com/en/rest/repos/tags)
+    /// states associated with this repository ref
+    pub fn tag_protections(&self) -> TagProtections<C> {
+        TagProtections::new(self.github.clone(), self.owner.as_str(), self.repo.as_str())
+    }
+
     /// get a reference to [statuses](https://developer.github.com/v3/repos/statuses/)
     /// associated with this repository ref
     pub fn statuses(&self) -> Statuses<C> {
         Statuses::new(self.github.clone(), self.owner.as_str(), self.repo.as_str())
     }
 
+    /// get the commit statuses and check runs of a git sha, branch or tag
+    /// at once, to tell whether it is green
+    pub fn ref_status(&self, reference: &str) -> Future<RefStatus> {
+        Box::new(
+            self.statuses()
+                .combined(reference)
+                .join(self.checkruns().list_for_ref(reference))
+                .map(|(combined, checks)| RefStatus {
+                    combined,
+                    check_runs: checks.check_runs,
+                }),
+        )
+    }
+
     /// get a reference to [teams](https://developer.github.com/v3/repos/#list-teams)
     /// associated with this repository ref
     pub fn teams(&self) -> RepoTeams<C> {
@@ -471,13 +844,85 @@ impl<C: Clone + Connect + 'static> Repository<C> {
     pub fn traffic(&self) -> Traffic<C> {
         Traffic::new(self.github.clone(), self.owner.as_str(), self.repo.as_str())
     }
+
+    /// get a reference to the [statistics](https://docs.github.com/en/rest/metrics/statistics)
+    /// of this repository
+    pub fn stats(&self) -> Stats<C> {
+        Stats::new(self.github.clone(), self.owner.as_str(), self.repo.as_str())
+    }
+
+    /// get a reference to the stargazers of this repository
+    pub fn stargazers(&self) -> Stargazers<C> {
+        Stargazers::new(self.github.clone(), self.owner.as_str(), self.repo.as_str())
+    }
+}
+
+/// the options which update a ruleset including or excluding the branch
+/// `old_name` by name to do the same for `new_name`, if it does
+fn retargeted(ruleset: &Ruleset, old_name: &str, new_name: &str) -> Option<RulesetOptions> {
+    let old_ref = format!("refs/heads/{}", old_name);
+    let new_ref = format!("refs/heads/{}", new_name);
+    let mut conditions = ruleset.conditions.clone()?;
+    let mut changed = false;
+    for key in &["include", "exclude"] {
+        let patterns = conditions
+            .pointer_mut(&format!("/ref_name/{}", key))
+            .and_then(|patterns| patterns.as_array_mut());
+        for pattern in patterns
+            .into_iter()
+            .flat_map(|patterns| patterns.iter_mut())
+        {
+            if pattern.as_str() == Some(old_ref.as_str()) {
+                *pattern = serde_json::Value::String(new_ref.clone());
+                changed = true;
+            }
+        }
+    }
+    if !changed {
+        return None;
+    }
+    Some(RulesetOptions {
+        name: ruleset.name.clone(),
+        target: ruleset.target,
+        enforcement: Some(ruleset.enforcement),
+        bypass_actors: ruleset.bypass_actors.clone(),
+        conditions: Some(conditions),
+        rules: ruleset.rules.clone(),
+    })
 }
 
 // representations (todo: replace with derive_builder)
 
+/// The outcome of syncing a fork's branch with its upstream repository
+#[derive(Debug)]
+pub enum MergeUpstream {
+    Synced(MergedUpstream),
+    /// the branch has diverged from upstream in a way which can't be
+    /// merged automatically, with Github's explanation
+    Conflict(String),
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MergedUpstream {
+    pub message: String,
+    pub merge_type: MergeType,
+    /// the upstream branch which was merged, like `octocat:main`
+    pub base_branch: String,
+}
+
+/// How a fork's branch was synced with upstream
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MergeType {
+    Merge,
+    FastForward,
+    /// the branch was already up to date
+    None,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct Repo {
-    pub id: u64,
+    pub id: RepoId,
     pub owner: User,
     pub name: String,
     pub full_name: String,
@@ -529,6 +974,8 @@ pub struct Repo {
     pub trees_url: String,
     pub homepage: Option<String>,
     pub language: Option<String>,
+    #[serde(default)]
+    pub archived: bool,
     pub forks_count: u64,
     pub stargazers_count: u64,
     pub watchers_count: u64,
@@ -539,6 +986,12 @@ pub struct Repo {
     pub has_wiki: bool,
     pub has_pages: bool,
     pub has_downloads: bool,
+    /// merge settings are only reported to users who may administer the
+    /// repository, and are absent from listings
+    pub allow_squash_merge: Option<bool>,
+    pub allow_merge_commit: Option<bool>,
+    pub allow_rebase_merge: Option<bool>,
+    pub delete_branch_on_merge: Option<bool>,
     pub pushed_at: String,
     pub created_at: String,
     pub updated_at: String, // permissions: Permissions
@@ -551,14 +1004,26 @@ impl Repo {
     ///
     /// The keys are the language names, and the values are the number of bytes of code written in
     /// that language.
+    #[deprecated(since = "0.6.0", note = "use Repository::languages instead")]
     #[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))] // shippied public API
     pub fn languages<C>(&self, github: Github<C>) -> Future<HashMap<String, i64>>
     where
         C: Clone + Connect + 'static,
     {
-        let url = Url::parse(&self.languages_url).unwrap();
-        let uri: String = url.path().into();
-        github.get(&uri)
+        let (owner, repo) = match self.full_name.find('/') {
+            Some(slash) => (&self.full_name[..slash], &self.full_name[slash + 1..]),
+            None => return Box::new(future::err("invalid repository name".into())),
+        };
+        Box::new(
+            Repository::new(github, owner, repo)
+                .languages()
+                .map(|languages| {
+                    languages
+                        .into_iter()
+                        .map(|(language, bytes)| (language, bytes as i64))
+                        .collect()
+                }),
+        )
     }
 }
 
@@ -824,6 +1289,10 @@ pub struct RepoEditOptions {
     pub allow_merge_commit: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub allow_rebase_merge: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delete_branch_on_merge: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived: Option<bool>,
 }
 
 impl RepoEditOptions {
@@ -859,6 +1328,8 @@ impl RepoEditOptions {
             allow_squash_merge,
             allow_merge_commit,
             allow_rebase_merge,
+            delete_branch_on_merge: None,
+            archived: None,
         }
     }
 
@@ -939,20 +1410,36 @@ impl RepoEditOptionsBuilder {
         self
     }
 
+    /// delete head branches once their pull requests are merged
+    pub fn delete_branch_on_merge(&mut self, delete_branch_on_merge: bool) -> &mut Self {
+        self.0.delete_branch_on_merge = Some(delete_branch_on_merge);
+        self
+    }
+
+    /// archiving a repository makes it read-only
+    pub fn archived(&mut self, archived: bool) -> &mut Self {
+        self.0.archived = Some(archived);
+        self
+    }
+
     pub fn build(&self) -> RepoEditOptions {
-        RepoEditOptions::new(
-            self.0.name.as_str(),
-            self.0.description.clone(),
-            self.0.homepage.clone(),
-            self.0.private,
-            self.0.has_issues,
-            self.0.has_projects,
-            self.0.has_wiki,
-            self.0.default_branch.clone(),
-            self.0.allow_squash_merge,
-            self.0.allow_merge_commit,
-            self.0.allow_rebase_merge,
-        )
+        RepoEditOptions {
+            delete_branch_on_merge: self.0.delete_branch_on_merge,
+            archived: self.0.archived,
+            ..RepoEditOptions::new(
+                self.0.name.as_str(),
+                self.0.description.clone(),
+                self.0.homepage.clone(),
+                self.0.private,
+                self.0.has_issues,
+                self.0.has_projects,
+                self.0.has_wiki,
+                self.0.default_branch.clone(),
+                self.0.allow_squash_merge,
+                self.0.allow_merge_commit,
+                self.0.allow_rebase_merge,
+            )
+        }
     }
 }
 
@@ -993,6 +1480,24 @@ impl OrgRepoListOptionsBuilder {
         self
     }
 
+    pub fn sort(&mut self, sort: Sort) -> &mut Self {
+        self.0.params.insert("sort", sort.to_string());
+        self
+    }
+
+    pub fn asc(&mut self) -> &mut Self {
+        self.direction(SortDirection::Asc)
+    }
+
+    pub fn desc(&mut self) -> &mut Self {
+        self.direction(SortDirection::Desc)
+    }
+
+    pub fn direction(&mut self, direction: SortDirection) -> &mut Self {
+        self.0.params.insert("direction", direction.to_string());
+        self
+    }
+
     pub fn build(&self) -> OrgRepoListOptions {
         OrgRepoListOptions {
             params: self.0.params.clone(),
@@ -1099,9 +1604,106 @@ impl OrganizationRepoListOptionsBuilder {
         self
     }
 
+    pub fn sort(&mut self, sort: Sort) -> &mut Self {
+        self.0.params.insert("sort", sort.to_string());
+        self
+    }
+
+    pub fn asc(&mut self) -> &mut Self {
+        self.direction(SortDirection::Asc)
+    }
+
+    pub fn desc(&mut self) -> &mut Self {
+        self.direction(SortDirection::Desc)
+    }
+
+    pub fn direction(&mut self, direction: SortDirection) -> &mut Self {
+        self.0.params.insert("direction", direction.to_string());
+        self
+    }
+
     pub fn build(&self) -> OrganizationRepoListOptions {
         OrganizationRepoListOptions {
             params: self.0.params.clone(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn organization_repo_list_reqs() {
+        let options = OrganizationRepoListOptions::builder()
+            .sort(Sort::FullName)
+            .asc()
+            .build();
+        let query = options.serialize().unwrap();
+        assert!(query.contains("sort=full_name"));
+        assert!(query.contains("direction=asc"));
+    }
+
+    #[test]
+    fn parse_repo_slug() {
+        assert_eq!(
+            "softprops/hubcaps".parse::<RepoSlug>(),
+            Ok(RepoSlug {
+                owner: "softprops".into(),
+                repo: "hubcaps".into(),
+            })
+        );
+        assert_eq!(
+            "rust-lang/rust.vim"
+                .parse::<RepoSlug>()
+                .map(|s| s.to_string()),
+            Ok("rust-lang/rust.vim".into())
+        );
+        for invalid in &[
+            "hubcaps",
+            "/hubcaps",
+            "softprops/",
+            "-soft/hubcaps",
+            "a/b/c",
+            "a/..",
+        ] {
+            assert!(invalid.parse::<RepoSlug>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn deserialize_merged_upstream() {
+        let merged: MergedUpstream = serde_json::from_str(
+            r#"{
+              "message": "Successfully fetched and fast-forwarded from upstream octocat:main.",
+              "merge_type": "fast-forward",
+              "base_branch": "octocat:main"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(merged.merge_type, MergeType::FastForward);
+    }
+
+    #[test]
+    fn retarget_ruleset() {
+        let ruleset: Ruleset = serde_json::from_str(
+            r#"{
+              "id": 42,
+              "name": "protect master",
+              "source": "softprops/hubcaps",
+              "enforcement": "active",
+              "conditions": {"ref_name": {
+                "include": ["refs/heads/master", "~DEFAULT_BRANCH"],
+                "exclude": []
+              }}
+            }"#,
+        )
+        .unwrap();
+        let options = retargeted(&ruleset, "master", "main").unwrap();
+        assert_eq!(
+            options.conditions.unwrap()["ref_name"]["include"],
+            serde_json::Value::from(vec!["refs/heads/main", "~DEFAULT_BRANCH"])
+        );
+        assert!(retargeted(&ruleset, "develop", "main").is_none());
+    }
+}
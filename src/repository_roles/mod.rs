@@ -0,0 +1,208 @@
+//! Custom repository roles interface
+//!
+//! Custom repository roles extend one of the base roles with fine-grained
+//! permissions. See the [github docs](https://docs.github.com/en/rest/orgs/custom-roles)
+//! for more information
+
+use std::fmt;
+
+use futures::{future, Future as StdFuture};
+use hyper::client::connect::Connect;
+use serde_json;
+
+use {Future, Github};
+
+/// The role a custom repository role builds upon
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BaseRole {
+    Read,
+    Triage,
+    Write,
+    Maintain,
+}
+
+impl fmt::Display for BaseRole {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BaseRole::Read => "read",
+            BaseRole::Triage => "triage",
+            BaseRole::Write => "write",
+            BaseRole::Maintain => "maintain",
+        }
+        .fmt(f)
+    }
+}
+
+/// Provides access to the custom repository roles of an organization
+pub struct RepositoryRoles<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    org: String,
+}
+
+impl<C: Clone + Connect + 'static> RepositoryRoles<C> {
+    #[doc(hidden)]
+    pub fn new<O>(github: Github<C>, org: O) -> Self
+    where
+        O: Into<String>,
+    {
+        RepositoryRoles {
+            github,
+            org: org.into(),
+        }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!("/orgs/{}/custom-repository-roles{}", self.org, more)
+    }
+
+    /// list the custom repository roles of this organization
+    pub fn list(&self) -> Future<Vec<RepositoryRole>> {
+        Box::new(
+            self.github
+                .get::<RepositoryRoleList>(&self.path(""))
+                .map(|list| list.custom_roles),
+        )
+    }
+
+    /// get a custom repository role by id
+    pub fn get(&self, id: u64) -> Future<RepositoryRole> {
+        self.github.get(&self.path(&format!("/{}", id)))
+    }
+
+    /// create a custom repository role
+    pub fn create(&self, options: &RepositoryRoleOptions) -> Future<RepositoryRole> {
+        self.github.post(&self.path(""), json!(options))
+    }
+
+    /// update a custom repository role
+    pub fn edit(&self, id: u64, options: &RepositoryRoleOptions) -> Future<RepositoryRole> {
+        self.github
+            .patch(&self.path(&format!("/{}", id)), json!(options))
+    }
+
+    /// delete a custom repository role
+    pub fn delete(&self, id: u64) -> Future<()> {
+        self.github.delete(&self.path(&format!("/{}", id)))
+    }
+
+    /// list the fine-grained permissions custom repository roles may be granted
+    pub fn permissions(&self) -> Future<Vec<FineGrainedPermission>> {
+        self.github.get(&format!(
+            "/orgs/{}/repository-fine-grained-permissions",
+            self.org
+        ))
+    }
+}
+
+// representations
+
+#[derive(Debug, Deserialize)]
+struct RepositoryRoleList {
+    custom_roles: Vec<RepositoryRole>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RepositoryRole {
+    pub id: u64,
+    pub name: String,
+    pub description: Option<String>,
+    pub base_role: Option<BaseRole>,
+    #[serde(default)]
+    pub permissions: Vec<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FineGrainedPermission {
+    pub name: String,
+    pub description: String,
+}
+
+/// options for creating or updating a custom repository role
+#[derive(Debug, Default, Serialize)]
+pub struct RepositoryRoleOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_role: Option<BaseRole>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<Vec<String>>,
+}
+
+impl RepositoryRoleOptions {
+    pub fn builder() -> RepositoryRoleOptionsBuilder {
+        RepositoryRoleOptionsBuilder::default()
+    }
+}
+
+#[derive(Default)]
+pub struct RepositoryRoleOptionsBuilder(RepositoryRoleOptions);
+
+impl RepositoryRoleOptionsBuilder {
+    /// required when creating a role
+    pub fn name<N>(&mut self, name: N) -> &mut Self
+    where
+        N: Into<String>,
+    {
+        self.0.name = Some(name.into());
+        self
+    }
+
+    pub fn description<D>(&mut self, description: D) -> &mut Self
+    where
+        D: Into<String>,
+    {
+        self.0.description = Some(description.into());
+        self
+    }
+
+    /// required when creating a role
+    pub fn base_role(&mut self, base_role: BaseRole) -> &mut Self {
+        self.0.base_role = Some(base_role);
+        self
+    }
+
+    /// fine-grained permissions, like `delete_alerts_code_scanning`.
+    /// required when creating a role
+    pub fn permissions<P>(&mut self, permissions: Vec<P>) -> &mut Self
+    where
+        P: Into<String>,
+    {
+        self.0.permissions = Some(permissions.into_iter().map(|p| p.into()).collect());
+        self
+    }
+
+    pub fn build(&self) -> RepositoryRoleOptions {
+        RepositoryRoleOptions {
+            name: self.0.name.clone(),
+            description: self.0.description.clone(),
+            base_role: self.0.base_role,
+            permissions: self.0.permissions.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn role_reqs() {
+        let options = RepositoryRoleOptions::builder()
+            .name("security-engineer")
+            .base_role(BaseRole::Maintain)
+            .permissions(vec!["delete_alerts_code_scanning"])
+            .build();
+        assert_eq!(
+            serde_json::to_string(&options).unwrap(),
+            r#"{"name":"security-engineer","base_role":"maintain","permissions":["delete_alerts_code_scanning"]}"#
+        )
+    }
+}
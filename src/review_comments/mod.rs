@@ -6,7 +6,8 @@ extern crate serde_json;
 use hyper::client::connect::Connect;
 
 use futures::future;
-use users::User;
+use ids::PullNumber;
+use users::{AuthorAssociation, User};
 use {Future, Github};
 
 /// A structure for interfacing with a review comments
@@ -17,21 +18,22 @@ where
     github: Github<C>,
     owner: String,
     repo: String,
-    number: u64,
+    number: PullNumber,
 }
 
 impl<C: Clone + Connect + 'static> ReviewComments<C> {
     #[doc(hidden)]
-    pub fn new<O, R>(github: Github<C>, owner: O, repo: R, number: u64) -> Self
+    pub fn new<O, R, N>(github: Github<C>, owner: O, repo: R, number: N) -> Self
     where
         O: Into<String>,
         R: Into<String>,
+        N: Into<PullNumber>,
     {
         ReviewComments {
             github,
             owner: owner.into(),
             repo: repo.into(),
-            number,
+            number: number.into(),
         }
     }
 
@@ -63,6 +65,16 @@ pub struct ReviewCommentOptions {
     pub position: usize,
 }
 
+string_enum! {
+    /// The side of a pull request's diff a review comment applies to
+    pub enum Side {
+        /// the deletions of the diff, in red
+        Left => "LEFT",
+        /// the additions or unchanged lines of the diff, in green or white
+        Right => "RIGHT",
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ReviewComment {
     pub id: u64,
@@ -74,9 +86,16 @@ pub struct ReviewComment {
     pub commit_id: String,
     pub original_commit_id: String,
     pub user: User,
+    pub author_association: AuthorAssociation,
     pub body: String,
     pub created_at: String,
     pub updated_at: String,
     pub html_url: String,
     pub pull_request_url: String,
+    /// the line of the diff the comment applies to, `None` once outdated
+    pub line: Option<u64>,
+    pub side: Option<Side>,
+    /// the first line of a multi-line comment
+    pub start_line: Option<u64>,
+    pub start_side: Option<Side>,
 }
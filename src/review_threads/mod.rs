@@ -0,0 +1,205 @@
+//! Review threads interface
+//!
+//! The review comments of a pull request are grouped in threads, which can be
+//! resolved once addressed. Threads and their resolution are only exposed
+//! through Github's GraphQL api. Threads are addressed by their GraphQL node ids
+//!
+//! See the [github docs](https://docs.github.com/en/graphql/reference/objects#pullrequestreviewthread)
+//! for more information.
+
+use futures::Future as StdFuture;
+use hyper::client::connect::Connect;
+use serde_json::Value;
+
+use graphql::{extract, json_vars, paginate, GraphQL};
+use ids::PullNumber;
+use review_comments::Side;
+use {Future, Github};
+
+const THREADS_QUERY: &str = r#"
+query($owner: String!, $repo: String!, $number: Int!, $cursor: String) {
+  repository(owner: $owner, name: $repo) {
+    pullRequest(number: $number) {
+      reviewThreads(first: 100, after: $cursor) {
+        nodes {
+          id
+          isResolved
+          isOutdated
+          isCollapsed
+          path
+          line
+          startLine
+          diffSide
+          resolvedBy { login }
+          comments(first: 100) {
+            nodes { id databaseId body url author { login } }
+          }
+        }
+        pageInfo { hasNextPage endCursor }
+      }
+    }
+  }
+}"#;
+
+const RESOLVE_MUTATION: &str = r#"
+mutation($thread: ID!) {
+  resolveReviewThread(input: {threadId: $thread}) {
+    thread { id isResolved }
+  }
+}"#;
+
+const UNRESOLVE_MUTATION: &str = r#"
+mutation($thread: ID!) {
+  unresolveReviewThread(input: {threadId: $thread}) {
+    thread { id isResolved }
+  }
+}"#;
+
+/// Provides access to the review threads of a pull request
+pub struct ReviewThreads<C>
+where
+    C: Clone + Connect + 'static,
+{
+    graphql: GraphQL<C>,
+    owner: String,
+    repo: String,
+    number: PullNumber,
+}
+
+impl<C: Clone + Connect + 'static> ReviewThreads<C> {
+    #[doc(hidden)]
+    pub fn new<O, R, N>(github: Github<C>, owner: O, repo: R, number: N) -> Self
+    where
+        O: Into<String>,
+        R: Into<String>,
+        N: Into<PullNumber>,
+    {
+        ReviewThreads {
+            graphql: GraphQL::new(github),
+            owner: owner.into(),
+            repo: repo.into(),
+            number: number.into(),
+        }
+    }
+
+    /// list all review threads of the pull request, including the first
+    /// 100 comments of each
+    pub fn list(&self) -> Future<Vec<ReviewThread>> {
+        paginate(
+            self.graphql.clone(),
+            THREADS_QUERY.to_owned(),
+            json_vars(&[
+                ("owner", Value::String(self.owner.clone())),
+                ("repo", Value::String(self.repo.clone())),
+                ("number", self.number.0.into()),
+            ]),
+            "/repository/pullRequest/reviewThreads".to_owned(),
+        )
+    }
+
+    /// mark a review thread as resolved, collapsing it
+    pub fn resolve<T>(&self, thread_id: T) -> Future<()>
+    where
+        T: Into<String>,
+    {
+        self.set_resolved(RESOLVE_MUTATION, "/resolveReviewThread", thread_id.into())
+    }
+
+    /// mark a resolved review thread as unresolved
+    pub fn unresolve<T>(&self, thread_id: T) -> Future<()>
+    where
+        T: Into<String>,
+    {
+        self.set_resolved(
+            UNRESOLVE_MUTATION,
+            "/unresolveReviewThread",
+            thread_id.into(),
+        )
+    }
+
+    fn set_resolved(&self, mutation: &str, pointer: &'static str, thread_id: String) -> Future<()> {
+        let variables = json_vars(&[("thread", Value::String(thread_id))]);
+        Box::new(
+            self.graphql
+                .query::<_, Value>(mutation, &variables)
+                .and_then(move |data| extract::<Value>(&data, pointer).map(|_| ())),
+        )
+    }
+}
+
+// representations
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewThread {
+    /// the thread's node id
+    pub id: String,
+    pub is_resolved: bool,
+    /// whether the diff the thread comments on has since changed
+    pub is_outdated: bool,
+    pub is_collapsed: bool,
+    pub path: String,
+    /// the line of the diff the thread comments on, `None` once outdated
+    pub line: Option<u64>,
+    /// the first line of a multi-line comment
+    pub start_line: Option<u64>,
+    pub diff_side: Side,
+    pub resolved_by: Option<Login>,
+    pub comments: ReviewThreadComments,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReviewThreadComments {
+    pub nodes: Vec<ReviewThreadComment>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewThreadComment {
+    /// the comment's node id
+    pub id: String,
+    /// the comment's id in the rest api
+    pub database_id: Option<u64>,
+    pub body: String,
+    pub url: String,
+    /// `None` for comments of deleted users
+    pub author: Option<Login>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Login {
+    pub login: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn deserialize_review_thread() {
+        let thread: ReviewThread = serde_json::from_str(
+            r#"{
+              "id": "PRRT_1",
+              "isResolved": false,
+              "isOutdated": true,
+              "isCollapsed": false,
+              "path": "src/lib.rs",
+              "line": null,
+              "startLine": null,
+              "diffSide": "RIGHT",
+              "resolvedBy": null,
+              "comments": {"nodes": [{
+                "id": "PRRC_1",
+                "databaseId": 1,
+                "body": "nit",
+                "url": "https://github.com/softprops/hubcaps/pull/1#discussion_r1",
+                "author": {"login": "softprops"}
+              }]}
+            }"#,
+        )
+        .unwrap();
+        assert!(thread.is_outdated);
+        assert_eq!(thread.comments.nodes[0].database_id, Some(1));
+    }
+}
@@ -0,0 +1,392 @@
+//! Rulesets interface
+//!
+//! Rulesets govern how branches, tags and pushes of repositories may be
+//! changed. See the [github docs](https://docs.github.com/en/rest/repos/rules) for more information
+
+use std::collections::HashMap;
+use std::fmt;
+
+use futures::future;
+use hyper::client::connect::Connect;
+use serde_json;
+use url::form_urlencoded;
+
+use {unfold, Future, Github, Stream};
+
+fn identity<T>(x: T) -> T {
+    x
+}
+
+/// What a ruleset applies to
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Target {
+    Branch,
+    Tag,
+    Push,
+}
+
+/// Whether a ruleset is enforced
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Enforcement {
+    Disabled,
+    Active,
+    /// report violations in rule insights without blocking them
+    Evaluate,
+}
+
+/// The result of evaluating rule suites
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleSuiteResult {
+    Pass,
+    Fail,
+    Bypass,
+    All,
+}
+
+impl fmt::Display for RuleSuiteResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RuleSuiteResult::Pass => "pass",
+            RuleSuiteResult::Fail => "fail",
+            RuleSuiteResult::Bypass => "bypass",
+            RuleSuiteResult::All => "all",
+        }
+        .fmt(f)
+    }
+}
+
+/// Provides access to the rulesets of a repository or organization
+pub struct Rulesets<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    owner: String,
+}
+
+impl<C: Clone + Connect + 'static> Rulesets<C> {
+    #[doc(hidden)]
+    pub fn new<O>(github: Github<C>, owner: O) -> Self
+    where
+        O: Into<String>,
+    {
+        Rulesets {
+            github,
+            owner: owner.into(),
+        }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!("{}/rulesets{}", self.owner, more)
+    }
+
+    /// list rulesets
+    pub fn list(&self) -> Future<Vec<Ruleset>> {
+        self.github.get(&self.path(""))
+    }
+
+    /// provides a stream over all pages of rulesets
+    pub fn iter(&self) -> Stream<Ruleset> {
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&self.path("")),
+            identity,
+        )
+    }
+
+    /// get a ruleset by id
+    pub fn get(&self, id: u64) -> Future<Ruleset> {
+        self.github.get(&self.path(&format!("/{}", id)))
+    }
+
+    /// create a ruleset
+    pub fn create(&self, options: &RulesetOptions) -> Future<Ruleset> {
+        self.github.post(&self.path(""), json!(options))
+    }
+
+    /// update a ruleset
+    pub fn update(&self, id: u64, options: &RulesetOptions) -> Future<Ruleset> {
+        self.github
+            .put(&self.path(&format!("/{}", id)), json!(options))
+    }
+
+    /// delete a ruleset
+    pub fn delete(&self, id: u64) -> Future<()> {
+        self.github.delete(&self.path(&format!("/{}", id)))
+    }
+
+    /// list the results of evaluating rulesets against pushes
+    pub fn rule_suites(&self, options: &RuleSuiteListOptions) -> Future<Vec<RuleSuite>> {
+        let mut uri = vec![self.path("/rule-suites")];
+        if let Some(query) = options.serialize() {
+            uri.push(query);
+        }
+        self.github.get(&uri.join("?"))
+    }
+
+    /// get a rule suite by id, including the evaluation of each rule
+    pub fn rule_suite(&self, id: u64) -> Future<RuleSuite> {
+        self.github.get(&self.path(&format!("/rule-suites/{}", id)))
+    }
+}
+
+// representations
+
+#[derive(Debug, Deserialize)]
+pub struct Ruleset {
+    pub id: u64,
+    pub name: String,
+    pub target: Option<Target>,
+    /// either `Repository` or `Organization`
+    pub source_type: Option<String>,
+    pub source: String,
+    pub enforcement: Enforcement,
+    pub bypass_actors: Option<Vec<BypassActor>>,
+    pub current_user_can_bypass: Option<String>,
+    pub node_id: Option<String>,
+    pub conditions: Option<serde_json::Value>,
+    pub rules: Option<Vec<Rule>>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+/// An actor allowed to bypass a ruleset
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BypassActor {
+    /// the id of the actor. ignored for `OrganizationAdmin` actors
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor_id: Option<u64>,
+    /// one of `Integration`, `OrganizationAdmin`, `RepositoryRole`,
+    /// `Team` or `DeployKey`
+    pub actor_type: String,
+    /// either `always` or `pull_request`
+    pub bypass_mode: String,
+}
+
+/// A rule of a ruleset, like `deletion` or `required_linear_history`.
+/// The parameters of a rule depend on its type
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Rule {
+    #[serde(rename = "type")]
+    pub rule_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<serde_json::Value>,
+}
+
+impl Rule {
+    pub fn new<T>(rule_type: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Rule {
+            rule_type: rule_type.into(),
+            parameters: None,
+        }
+    }
+
+    pub fn with_parameters<T>(rule_type: T, parameters: serde_json::Value) -> Self
+    where
+        T: Into<String>,
+    {
+        Rule {
+            rule_type: rule_type.into(),
+            parameters: Some(parameters),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RuleSuite {
+    pub id: u64,
+    pub actor_id: Option<u64>,
+    pub actor_name: Option<String>,
+    pub before_sha: String,
+    pub after_sha: String,
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    pub repository_id: u64,
+    pub repository_name: String,
+    pub pushed_at: String,
+    pub result: RuleSuiteResult,
+    pub evaluation_result: Option<RuleSuiteResult>,
+    /// only included when getting a single rule suite
+    pub rule_evaluations: Option<Vec<serde_json::Value>>,
+}
+
+/// options for creating or updating a ruleset
+#[derive(Debug, Default, Serialize)]
+pub struct RulesetOptions {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<Target>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enforcement: Option<Enforcement>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bypass_actors: Option<Vec<BypassActor>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conditions: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rules: Option<Vec<Rule>>,
+}
+
+impl RulesetOptions {
+    pub fn builder<N>(name: N, enforcement: Enforcement) -> RulesetOptionsBuilder
+    where
+        N: Into<String>,
+    {
+        RulesetOptionsBuilder(RulesetOptions {
+            name: name.into(),
+            enforcement: Some(enforcement),
+            ..Default::default()
+        })
+    }
+}
+
+pub struct RulesetOptionsBuilder(RulesetOptions);
+
+impl RulesetOptionsBuilder {
+    pub fn target(&mut self, target: Target) -> &mut Self {
+        self.0.target = Some(target);
+        self
+    }
+
+    pub fn bypass_actor(&mut self, actor: BypassActor) -> &mut Self {
+        self.0
+            .bypass_actors
+            .get_or_insert_with(Vec::new)
+            .push(actor);
+        self
+    }
+
+    /// the refs and repositories a ruleset applies to, like
+    /// `{"ref_name":{"include":["~DEFAULT_BRANCH"],"exclude":[]}}`
+    pub fn conditions(&mut self, conditions: serde_json::Value) -> &mut Self {
+        self.0.conditions = Some(conditions);
+        self
+    }
+
+    pub fn rule(&mut self, rule: Rule) -> &mut Self {
+        self.0.rules.get_or_insert_with(Vec::new).push(rule);
+        self
+    }
+
+    pub fn build(&self) -> RulesetOptions {
+        RulesetOptions {
+            name: self.0.name.clone(),
+            target: self.0.target,
+            enforcement: self.0.enforcement,
+            bypass_actors: self.0.bypass_actors.clone(),
+            conditions: self.0.conditions.clone(),
+            rules: self.0.rules.clone(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct RuleSuiteListOptions {
+    params: HashMap<&'static str, String>,
+}
+
+impl RuleSuiteListOptions {
+    pub fn builder() -> RuleSuiteListOptionsBuilder {
+        RuleSuiteListOptionsBuilder::default()
+    }
+
+    /// serialize options as a string. returns None if no options are defined
+    pub fn serialize(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded)
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct RuleSuiteListOptionsBuilder(RuleSuiteListOptions);
+
+impl RuleSuiteListOptionsBuilder {
+    /// only rule suites of pushes to a ref, like `main`
+    pub fn git_ref<R>(&mut self, git_ref: R) -> &mut Self
+    where
+        R: Into<String>,
+    {
+        self.0.params.insert("ref", git_ref.into());
+        self
+    }
+
+    /// only rule suites of pushes to a repository. organization rulesets only
+    pub fn repository_name<R>(&mut self, name: R) -> &mut Self
+    where
+        R: Into<String>,
+    {
+        self.0.params.insert("repository_name", name.into());
+        self
+    }
+
+    /// one of `hour`, `day`, `week` or `month`
+    pub fn time_period<T>(&mut self, period: T) -> &mut Self
+    where
+        T: Into<String>,
+    {
+        self.0.params.insert("time_period", period.into());
+        self
+    }
+
+    pub fn actor_name<A>(&mut self, actor: A) -> &mut Self
+    where
+        A: Into<String>,
+    {
+        self.0.params.insert("actor_name", actor.into());
+        self
+    }
+
+    pub fn rule_suite_result(&mut self, result: RuleSuiteResult) -> &mut Self {
+        self.0
+            .params
+            .insert("rule_suite_result", result.to_string());
+        self
+    }
+
+    pub fn per_page(&mut self, n: usize) -> &mut Self {
+        self.0.params.insert("per_page", n.to_string());
+        self
+    }
+
+    pub fn build(&self) -> RuleSuiteListOptions {
+        RuleSuiteListOptions {
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ruleset_reqs() {
+        let options = RulesetOptions::builder("main", Enforcement::Active)
+            .target(Target::Branch)
+            .rule(Rule::new("deletion"))
+            .rule(Rule::with_parameters(
+                "pull_request",
+                json_value(r#"{"required_approving_review_count":1}"#),
+            ))
+            .build();
+        assert_eq!(
+            serde_json::to_string(&options).unwrap(),
+            r#"{"name":"main","target":"branch","enforcement":"active","rules":[{"type":"deletion"},{"type":"pull_request","parameters":{"required_approving_review_count":1}}]}"#
+        )
+    }
+
+    fn json_value(s: &str) -> serde_json::Value {
+        serde_json::from_str(s).unwrap()
+    }
+}
@@ -0,0 +1,345 @@
+//! SCIM interface
+//!
+//! Provisions the users of an organization or enterprise from an identity
+//! provider. See the [github docs](https://docs.github.com/en/enterprise-cloud@latest/rest/scim/scim)
+//! for more information
+
+use std::collections::HashMap;
+
+use futures::future;
+use hyper::client::connect::Connect;
+use hyper::Method;
+use serde_json;
+use url::form_urlencoded;
+
+use {Future, Github};
+
+const USER_SCHEMA: &str = "urn:ietf:params:scim:schemas:core:2.0:User";
+const PATCH_OP_SCHEMA: &str = "urn:ietf:params:scim:api:messages:2.0:PatchOp";
+
+/// Provides access to the SCIM provisioned users of an organization or enterprise
+pub struct Scim<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    path: String,
+}
+
+impl<C: Clone + Connect + 'static> Scim<C> {
+    #[doc(hidden)]
+    pub fn new<P>(github: Github<C>, path: P) -> Self
+    where
+        P: Into<String>,
+    {
+        Scim {
+            github,
+            path: path.into(),
+        }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!("{}/Users{}", self.path, more)
+    }
+
+    /// list provisioned users
+    pub fn list(&self, options: &ScimListOptions) -> Future<ScimUserList> {
+        let mut uri = vec![self.path("")];
+        if let Some(query) = options.serialize() {
+            uri.push(query);
+        }
+        self.github.scim(Method::GET, &uri.join("?"), None)
+    }
+
+    /// get a provisioned user by their SCIM id
+    pub fn get(&self, scim_user_id: &str) -> Future<ScimUser> {
+        self.github
+            .scim(Method::GET, &self.path(&format!("/{}", scim_user_id)), None)
+    }
+
+    /// provision a user, inviting them to the organization or enterprise
+    pub fn provision(&self, user: &ScimUserOptions) -> Future<ScimUser> {
+        self.github
+            .scim(Method::POST, &self.path(""), Some(json!(user)))
+    }
+
+    /// replace all of a provisioned user's attributes
+    pub fn update(&self, scim_user_id: &str, user: &ScimUserOptions) -> Future<ScimUser> {
+        self.github.scim(
+            Method::PUT,
+            &self.path(&format!("/{}", scim_user_id)),
+            Some(json!(user)),
+        )
+    }
+
+    /// apply a list of operations to a provisioned user's attributes
+    pub fn patch(&self, scim_user_id: &str, operations: Vec<Operation>) -> Future<ScimUser> {
+        let payload = PatchOp {
+            schemas: vec![PATCH_OP_SCHEMA],
+            operations,
+        };
+        self.github.scim(
+            Method::PATCH,
+            &self.path(&format!("/{}", scim_user_id)),
+            Some(json!(payload)),
+        )
+    }
+
+    /// deprovision a user, removing them from the organization or enterprise
+    pub fn deprovision(&self, scim_user_id: &str) -> Future<()> {
+        self.github
+            .delete(&self.path(&format!("/{}", scim_user_id)))
+    }
+}
+
+// representations
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimUserList {
+    pub schemas: Vec<String>,
+    pub total_results: u64,
+    pub items_per_page: u64,
+    pub start_index: u64,
+    #[serde(rename = "Resources")]
+    pub resources: Vec<ScimUser>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimUser {
+    pub schemas: Vec<String>,
+    pub id: String,
+    pub external_id: Option<String>,
+    pub user_name: Option<String>,
+    pub display_name: Option<String>,
+    pub name: Option<ScimName>,
+    #[serde(default)]
+    pub emails: Vec<ScimEmail>,
+    pub active: bool,
+    pub meta: Option<ScimMeta>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimName {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub given_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub family_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub formatted: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScimEmail {
+    pub value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary: Option<bool>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub email_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimMeta {
+    pub resource_type: Option<String>,
+    pub created: Option<String>,
+    pub last_modified: Option<String>,
+    pub location: Option<String>,
+}
+
+/// A SCIM patch operation
+#[derive(Debug, Serialize)]
+pub struct Operation {
+    /// one of `add`, `remove` or `replace`
+    pub op: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<serde_json::Value>,
+}
+
+impl Operation {
+    pub fn new<O>(op: O, path: Option<&str>, value: Option<serde_json::Value>) -> Self
+    where
+        O: Into<String>,
+    {
+        Operation {
+            op: op.into(),
+            path: path.map(|p| p.to_owned()),
+            value,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct PatchOp {
+    schemas: Vec<&'static str>,
+    #[serde(rename = "Operations")]
+    operations: Vec<Operation>,
+}
+
+/// options for provisioning or updating a user
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimUserOptions {
+    pub schemas: Vec<String>,
+    pub user_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    pub name: ScimName,
+    pub emails: Vec<ScimEmail>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active: Option<bool>,
+}
+
+impl ScimUserOptions {
+    pub fn builder<U>(user_name: U) -> ScimUserOptionsBuilder
+    where
+        U: Into<String>,
+    {
+        ScimUserOptionsBuilder(ScimUserOptions {
+            schemas: vec![USER_SCHEMA.to_owned()],
+            user_name: user_name.into(),
+            external_id: None,
+            display_name: None,
+            name: ScimName::default(),
+            emails: Vec::new(),
+            active: None,
+        })
+    }
+}
+
+pub struct ScimUserOptionsBuilder(ScimUserOptions);
+
+impl ScimUserOptionsBuilder {
+    /// the user's id in the identity provider
+    pub fn external_id<E>(&mut self, external_id: E) -> &mut Self
+    where
+        E: Into<String>,
+    {
+        self.0.external_id = Some(external_id.into());
+        self
+    }
+
+    pub fn display_name<D>(&mut self, display_name: D) -> &mut Self
+    where
+        D: Into<String>,
+    {
+        self.0.display_name = Some(display_name.into());
+        self
+    }
+
+    pub fn name<G, F>(&mut self, given_name: G, family_name: F) -> &mut Self
+    where
+        G: Into<String>,
+        F: Into<String>,
+    {
+        self.0.name.given_name = Some(given_name.into());
+        self.0.name.family_name = Some(family_name.into());
+        self
+    }
+
+    pub fn email<E>(&mut self, email: E, primary: bool) -> &mut Self
+    where
+        E: Into<String>,
+    {
+        self.0.emails.push(ScimEmail {
+            value: email.into(),
+            primary: Some(primary),
+            email_type: None,
+        });
+        self
+    }
+
+    pub fn active(&mut self, active: bool) -> &mut Self {
+        self.0.active = Some(active);
+        self
+    }
+
+    pub fn build(&self) -> ScimUserOptions {
+        ScimUserOptions {
+            schemas: self.0.schemas.clone(),
+            user_name: self.0.user_name.clone(),
+            external_id: self.0.external_id.clone(),
+            display_name: self.0.display_name.clone(),
+            name: self.0.name.clone(),
+            emails: self.0.emails.clone(),
+            active: self.0.active,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ScimListOptions {
+    params: HashMap<&'static str, String>,
+}
+
+impl ScimListOptions {
+    pub fn builder() -> ScimListOptionsBuilder {
+        ScimListOptionsBuilder::default()
+    }
+
+    /// serialize options as a string. returns None if no options are defined
+    pub fn serialize(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded)
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ScimListOptionsBuilder(ScimListOptions);
+
+impl ScimListOptionsBuilder {
+    /// the 1-based index of the first result
+    pub fn start_index(&mut self, index: u64) -> &mut Self {
+        self.0.params.insert("startIndex", index.to_string());
+        self
+    }
+
+    pub fn count(&mut self, count: u64) -> &mut Self {
+        self.0.params.insert("count", count.to_string());
+        self
+    }
+
+    /// a SCIM filter, like `userName eq "octocat"`
+    pub fn filter<F>(&mut self, filter: F) -> &mut Self
+    where
+        F: Into<String>,
+    {
+        self.0.params.insert("filter", filter.into());
+        self
+    }
+
+    pub fn build(&self) -> ScimListOptions {
+        ScimListOptions {
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn provision_reqs() {
+        let options = ScimUserOptions::builder("octocat")
+            .name("Mona", "Octocat")
+            .email("mona@example.com", true)
+            .build();
+        assert_eq!(
+            serde_json::to_string(&options).unwrap(),
+            r#"{"schemas":["urn:ietf:params:scim:schemas:core:2.0:User"],"userName":"octocat","name":{"givenName":"Mona","familyName":"Octocat"},"emails":[{"value":"mona@example.com","primary":true}]}"#
+        )
+    }
+}
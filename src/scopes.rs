@@ -0,0 +1,177 @@
+//! OAuth scopes interface
+//!
+//! Responses to requests authenticated with OAuth app tokens and classic
+//! personal access tokens list the scopes the token was granted in the
+//! `X-OAuth-Scopes` header, and the scopes the endpoint accepts in the
+//! `X-Accepted-OAuth-Scopes` header. Use `Github::required_scopes_check` to
+//! fail fast when a token lacks the scopes an application needs.
+//!
+//! See the [github docs](https://docs.github.com/en/apps/oauth-apps/building-oauth-apps/scopes-for-oauth-apps)
+//! for more information.
+
+use hyper::HeaderMap;
+
+const X_OAUTH_SCOPES: &str = "x-oauth-scopes";
+const X_ACCEPTED_OAUTH_SCOPES: &str = "x-accepted-oauth-scopes";
+
+string_enum! {
+    /// A scope an OAuth token may be granted
+    pub enum Scope {
+        /// full access to public and private repositories
+        Repo => "repo",
+        RepoStatus => "repo:status",
+        RepoDeployment => "repo_deployment",
+        PublicRepo => "public_repo",
+        RepoInvite => "repo:invite",
+        SecurityEvents => "security_events",
+        AdminRepoHook => "admin:repo_hook",
+        WriteRepoHook => "write:repo_hook",
+        ReadRepoHook => "read:repo_hook",
+        AdminOrg => "admin:org",
+        WriteOrg => "write:org",
+        ReadOrg => "read:org",
+        AdminPublicKey => "admin:public_key",
+        WritePublicKey => "write:public_key",
+        ReadPublicKey => "read:public_key",
+        AdminOrgHook => "admin:org_hook",
+        Gist => "gist",
+        Notifications => "notifications",
+        User => "user",
+        ReadUser => "read:user",
+        UserEmail => "user:email",
+        UserFollow => "user:follow",
+        Project => "project",
+        ReadProject => "read:project",
+        DeleteRepo => "delete_repo",
+        WritePackages => "write:packages",
+        ReadPackages => "read:packages",
+        DeletePackages => "delete:packages",
+        AdminGpgKey => "admin:gpg_key",
+        WriteGpgKey => "write:gpg_key",
+        ReadGpgKey => "read:gpg_key",
+        Codespace => "codespace",
+        Workflow => "workflow",
+        AdminEnterprise => "admin:enterprise",
+        ManageRunnersEnterprise => "manage_runners:enterprise",
+        ManageBillingEnterprise => "manage_billing:enterprise",
+        ReadEnterprise => "read:enterprise",
+        AuditLog => "audit_log",
+        ReadAuditLog => "read:audit_log",
+        Copilot => "copilot",
+        ManageBillingCopilot => "manage_billing:copilot",
+    }
+}
+
+impl Scope {
+    /// the scopes this scope directly includes
+    fn includes(&self) -> Vec<Scope> {
+        match *self {
+            Scope::Repo => vec![
+                Scope::RepoStatus,
+                Scope::RepoDeployment,
+                Scope::PublicRepo,
+                Scope::RepoInvite,
+                Scope::SecurityEvents,
+            ],
+            Scope::AdminRepoHook => vec![Scope::WriteRepoHook],
+            Scope::WriteRepoHook => vec![Scope::ReadRepoHook],
+            Scope::AdminOrg => vec![Scope::WriteOrg],
+            Scope::WriteOrg => vec![Scope::ReadOrg],
+            Scope::AdminPublicKey => vec![Scope::WritePublicKey],
+            Scope::WritePublicKey => vec![Scope::ReadPublicKey],
+            Scope::User => vec![Scope::ReadUser, Scope::UserEmail, Scope::UserFollow],
+            Scope::Project => vec![Scope::ReadProject],
+            Scope::WritePackages => vec![Scope::ReadPackages],
+            Scope::AdminGpgKey => vec![Scope::WriteGpgKey],
+            Scope::WriteGpgKey => vec![Scope::ReadGpgKey],
+            Scope::AdminEnterprise => vec![
+                Scope::ManageRunnersEnterprise,
+                Scope::ManageBillingEnterprise,
+                Scope::ReadEnterprise,
+            ],
+            Scope::AuditLog => vec![Scope::ReadAuditLog],
+            Scope::Copilot => vec![Scope::ManageBillingCopilot],
+            _ => Vec::new(),
+        }
+    }
+
+    /// whether a token granted this scope may act with `other`, because
+    /// they're the same scope or this scope includes it, like `repo`
+    /// includes `public_repo`
+    pub fn grants(&self, other: &Scope) -> bool {
+        self == other || self.includes().iter().any(|scope| scope.grants(other))
+    }
+}
+
+/// The scopes reported by a response
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OAuthScopes {
+    /// the scopes the token was granted. `None` for tokens which aren't
+    /// scoped, like Github App tokens and fine-grained personal access tokens
+    pub granted: Option<Vec<Scope>>,
+    /// the scopes the endpoint accepts
+    pub accepted: Option<Vec<Scope>>,
+}
+
+impl OAuthScopes {
+    /// parse the scopes reported by `headers`
+    pub fn from_headers(headers: &HeaderMap) -> OAuthScopes {
+        let scopes = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| {
+                    value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|scope| !scope.is_empty())
+                        .map(Scope::from)
+                        .collect()
+                })
+        };
+        OAuthScopes {
+            granted: scopes(X_OAUTH_SCOPES),
+            accepted: scopes(X_ACCEPTED_OAUTH_SCOPES),
+        }
+    }
+
+    /// the scopes of `required` which weren't granted. nothing is missing
+    /// for tokens which aren't scoped
+    pub fn missing(&self, required: &[Scope]) -> Vec<Scope> {
+        match self.granted {
+            Some(ref granted) => required
+                .iter()
+                .filter(|scope| !granted.iter().any(|g| g.grants(scope)))
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_scopes() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(
+            OAuthScopes::from_headers(&headers).missing(&[Scope::Repo]),
+            vec![]
+        );
+        headers.insert(X_OAUTH_SCOPES, "repo, write:org, gist".parse().unwrap());
+        headers.insert(X_ACCEPTED_OAUTH_SCOPES, "".parse().unwrap());
+        let scopes = OAuthScopes::from_headers(&headers);
+        assert_eq!(scopes.accepted, Some(vec![]));
+        assert_eq!(
+            scopes.missing(&[
+                Scope::PublicRepo,
+                Scope::ReadOrg,
+                Scope::AdminOrg,
+                Scope::Workflow
+            ]),
+            vec![Scope::AdminOrg, Scope::Workflow]
+        );
+    }
+}
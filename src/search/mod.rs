@@ -7,12 +7,17 @@ use hyper::client::connect::Connect;
 use serde::de::DeserializeOwned;
 use url::{self, form_urlencoded};
 
+use issues::IssueState;
 use labels::Label;
-use users::User;
+use users::{AuthorAssociation, User};
 use {unfold, Future, Github, SortDirection, Stream};
 
+mod open_prs;
+mod query;
 mod repos;
 
+pub use self::open_prs::*;
+pub use self::query::*;
 pub use self::repos::*;
 
 /// Sort directions for pull requests
@@ -209,7 +214,8 @@ pub struct IssuesItem {
     pub title: String,
     pub user: User,
     pub labels: Vec<Label>,
-    pub state: String,
+    pub state: IssueState,
+    pub author_association: AuthorAssociation,
     pub locked: bool,
     pub assignee: Option<User>,
     pub assignees: Vec<User>,
@@ -0,0 +1,238 @@
+use futures::Stream as StdStream;
+use hyper::client::connect::Connect;
+
+use super::{IssuesItem, IssuesSort, Search, SearchIssuesOptions, SearchQuery};
+use ids::PullNumber;
+use {SortDirection, Stream};
+
+string_enum! {
+    /// The review states pull requests may be searched by
+    pub enum ReviewStatus {
+        /// no review was requested or submitted
+        Unreviewed => "none",
+        /// an approving review is required before merging
+        Required => "required",
+        Approved => "approved",
+        ChangesRequested => "changes_requested",
+    }
+}
+
+/// Options which narrow a search for an organization's open pull requests.
+/// The organization, `is:pr` and `is:open` qualifiers are always included
+#[derive(Clone, Debug, Default)]
+pub struct OpenPrSearchOptions {
+    qualifiers: SearchQuery,
+    sort: Option<IssuesSort>,
+    order: Option<SortDirection>,
+}
+
+impl OpenPrSearchOptions {
+    pub fn builder() -> OpenPrSearchOptionsBuilder {
+        OpenPrSearchOptionsBuilder::default()
+    }
+
+    /// the search query for the open pull requests of `org`
+    pub fn query(&self, org: &str) -> String {
+        let mut query = SearchQuery::new();
+        query.org(org).is_pr().qualifier("is", "open");
+        let qualifiers = self.qualifiers.to_string();
+        if qualifiers.is_empty() {
+            query.to_string()
+        } else {
+            format!("{} {}", query, qualifiers)
+        }
+    }
+
+    fn search_options(&self) -> SearchIssuesOptions {
+        let mut options = SearchIssuesOptions::builder();
+        options.per_page(100);
+        if let Some(sort) = self.sort {
+            options.sort(sort);
+        }
+        if let Some(order) = self.order {
+            options.order(order);
+        }
+        options.build()
+    }
+}
+
+#[derive(Default)]
+pub struct OpenPrSearchOptionsBuilder(OpenPrSearchOptions);
+
+impl OpenPrSearchOptionsBuilder {
+    /// only match pull requests in this review state
+    pub fn review(&mut self, review: ReviewStatus) -> &mut Self {
+        self.0.qualifiers.qualifier("review", review.as_str());
+        self
+    }
+
+    /// only match draft pull requests, or only those ready for review
+    pub fn draft(&mut self, draft: bool) -> &mut Self {
+        self.0.qualifiers.qualifier("draft", draft.to_string());
+        self
+    }
+
+    /// only match pull requests awaiting a review from this user
+    pub fn review_requested<U>(&mut self, user: U) -> &mut Self
+    where
+        U: AsRef<str>,
+    {
+        self.0.qualifiers.qualifier("review-requested", user);
+        self
+    }
+
+    /// only match pull requests awaiting a review from this team, named
+    /// like `org/team-slug`
+    pub fn team_review_requested<T>(&mut self, team: T) -> &mut Self
+    where
+        T: AsRef<str>,
+    {
+        self.0.qualifiers.qualifier("team-review-requested", team);
+        self
+    }
+
+    pub fn author<A>(&mut self, author: A) -> &mut Self
+    where
+        A: AsRef<str>,
+    {
+        self.0.qualifiers.author(author);
+        self
+    }
+
+    pub fn assignee<A>(&mut self, assignee: A) -> &mut Self
+    where
+        A: AsRef<str>,
+    {
+        self.0.qualifiers.assignee(assignee);
+        self
+    }
+
+    pub fn label<L>(&mut self, label: L) -> &mut Self
+    where
+        L: AsRef<str>,
+    {
+        self.0.qualifiers.label(label);
+        self
+    }
+
+    /// exclude pull requests with this label
+    pub fn exclude_label<L>(&mut self, label: L) -> &mut Self
+    where
+        L: AsRef<str>,
+    {
+        self.0.qualifiers.exclude("label", label);
+        self
+    }
+
+    /// only match pull requests last updated before a date or time, like
+    /// `2019-01-01`, to find stale ones
+    pub fn updated_before<D>(&mut self, date: D) -> &mut Self
+    where
+        D: AsRef<str>,
+    {
+        self.0.qualifiers.updated_before(date);
+        self
+    }
+
+    /// add any other qualifier, like `base:main`
+    pub fn qualifier<V>(&mut self, name: &str, value: V) -> &mut Self
+    where
+        V: AsRef<str>,
+    {
+        self.0.qualifiers.qualifier(name, value);
+        self
+    }
+
+    pub fn sort(&mut self, sort: IssuesSort) -> &mut Self {
+        self.0.sort = Some(sort);
+        self
+    }
+
+    pub fn order(&mut self, direction: SortDirection) -> &mut Self {
+        self.0.order = Some(direction);
+        self
+    }
+
+    pub fn build(&self) -> OpenPrSearchOptions {
+        self.0.clone()
+    }
+}
+
+pub(crate) fn search_open_prs<C>(
+    search: Search<C>,
+    org: &str,
+    options: &OpenPrSearchOptions,
+) -> Stream<PrSummary>
+where
+    C: Clone + Connect + 'static,
+{
+    Box::new(
+        search
+            .issues()
+            .iter(options.query(org), &options.search_options())
+            .map(PrSummary::from),
+    )
+}
+
+// representations
+
+/// A summary of an open pull request found by a search
+#[derive(Debug)]
+pub struct PrSummary {
+    pub owner: String,
+    pub repo: String,
+    pub number: PullNumber,
+    pub title: String,
+    pub html_url: String,
+    pub author: String,
+    pub labels: Vec<String>,
+    pub assignees: Vec<String>,
+    pub comments: u64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<IssuesItem> for PrSummary {
+    fn from(item: IssuesItem) -> PrSummary {
+        let (owner, repo) = item.repo_tuple();
+        PrSummary {
+            owner,
+            repo,
+            number: PullNumber::from(item.number),
+            title: item.title,
+            html_url: item.html_url,
+            author: item.user.login,
+            labels: item.labels.into_iter().map(|label| label.name).collect(),
+            assignees: item
+                .assignees
+                .into_iter()
+                .map(|assignee| assignee.login)
+                .collect(),
+            comments: item.comments,
+            created_at: item.created_at,
+            updated_at: item.updated_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_pr_query() {
+        assert_eq!(
+            OpenPrSearchOptions::default().query("rust-lang"),
+            "org:rust-lang is:pr is:open"
+        );
+        let options = OpenPrSearchOptions::builder()
+            .review(ReviewStatus::Required)
+            .draft(false)
+            .exclude_label("do not merge")
+            .build();
+        assert_eq!(
+            options.query("rust-lang"),
+            r#"org:rust-lang is:pr is:open review:required draft:false -label:"do not merge""#
+        );
+    }
+}
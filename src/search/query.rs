@@ -0,0 +1,288 @@
+use std::fmt;
+
+use issues::IssueState;
+
+/// Builds a search query from keywords and qualifiers, quoting values as
+/// Github's [search syntax](https://docs.github.com/en/search-github/getting-started-with-searching-on-github/understanding-the-search-syntax)
+/// requires. Queries convert into the `q` of a search
+///
+/// ```
+/// # use hubcaps::issues::IssueState;
+/// # use hubcaps::search::SearchQuery;
+/// let query = SearchQuery::new()
+///     .repo("softprops/hubcaps")
+///     .label("good first issue")
+///     .state(IssueState::Open)
+///     .created_after("2019-01-01")
+///     .to_string();
+/// assert_eq!(
+///     query,
+///     r#"repo:softprops/hubcaps label:"good first issue" state:open created:>2019-01-01"#
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SearchQuery {
+    terms: Vec<String>,
+}
+
+impl SearchQuery {
+    pub fn new() -> Self {
+        SearchQuery::default()
+    }
+
+    /// match a keyword or phrase
+    pub fn keyword<K>(&mut self, keyword: K) -> &mut Self
+    where
+        K: AsRef<str>,
+    {
+        self.terms.push(quote(keyword.as_ref()));
+        self
+    }
+
+    /// match results whose qualifier has a value, like `is:pr`
+    pub fn qualifier<V>(&mut self, name: &str, value: V) -> &mut Self
+    where
+        V: AsRef<str>,
+    {
+        self.terms
+            .push(format!("{}:{}", name, quote(value.as_ref())));
+        self
+    }
+
+    /// exclude results whose qualifier has a value, like `-label:wontfix`
+    pub fn exclude<V>(&mut self, name: &str, value: V) -> &mut Self
+    where
+        V: AsRef<str>,
+    {
+        self.terms
+            .push(format!("-{}:{}", name, quote(value.as_ref())));
+        self
+    }
+
+    /// match results in a repository, named like `owner/repo`
+    pub fn repo<R>(&mut self, repo: R) -> &mut Self
+    where
+        R: AsRef<str>,
+    {
+        self.qualifier("repo", repo)
+    }
+
+    /// match results in an organization's repositories
+    pub fn org<O>(&mut self, org: O) -> &mut Self
+    where
+        O: AsRef<str>,
+    {
+        self.qualifier("org", org)
+    }
+
+    /// match results in a user's repositories
+    pub fn user<U>(&mut self, user: U) -> &mut Self
+    where
+        U: AsRef<str>,
+    {
+        self.qualifier("user", user)
+    }
+
+    pub fn label<L>(&mut self, label: L) -> &mut Self
+    where
+        L: AsRef<str>,
+    {
+        self.qualifier("label", label)
+    }
+
+    pub fn author<A>(&mut self, author: A) -> &mut Self
+    where
+        A: AsRef<str>,
+    {
+        self.qualifier("author", author)
+    }
+
+    pub fn assignee<A>(&mut self, assignee: A) -> &mut Self
+    where
+        A: AsRef<str>,
+    {
+        self.qualifier("assignee", assignee)
+    }
+
+    pub fn mentions<U>(&mut self, user: U) -> &mut Self
+    where
+        U: AsRef<str>,
+    {
+        self.qualifier("mentions", user)
+    }
+
+    /// match results a user authored, is assigned to, is mentioned in or commented on
+    pub fn involves<U>(&mut self, user: U) -> &mut Self
+    where
+        U: AsRef<str>,
+    {
+        self.qualifier("involves", user)
+    }
+
+    pub fn language<L>(&mut self, language: L) -> &mut Self
+    where
+        L: AsRef<str>,
+    {
+        self.qualifier("language", language)
+    }
+
+    pub fn state(&mut self, state: IssueState) -> &mut Self {
+        self.qualifier("state", state.as_str())
+    }
+
+    /// match issues, but not pull requests
+    pub fn is_issue(&mut self) -> &mut Self {
+        self.qualifier("is", "issue")
+    }
+
+    /// match pull requests, but not issues
+    pub fn is_pr(&mut self) -> &mut Self {
+        self.qualifier("is", "pr")
+    }
+
+    /// match results created after a date or time, like `2019-01-01`
+    /// or `2019-01-01T12:00:00Z`
+    pub fn created_after<D>(&mut self, date: D) -> &mut Self
+    where
+        D: AsRef<str>,
+    {
+        self.range("created", ">", date.as_ref())
+    }
+
+    /// match results created before a date or time
+    pub fn created_before<D>(&mut self, date: D) -> &mut Self
+    where
+        D: AsRef<str>,
+    {
+        self.range("created", "<", date.as_ref())
+    }
+
+    /// match results updated after a date or time
+    pub fn updated_after<D>(&mut self, date: D) -> &mut Self
+    where
+        D: AsRef<str>,
+    {
+        self.range("updated", ">", date.as_ref())
+    }
+
+    /// match results updated before a date or time
+    pub fn updated_before<D>(&mut self, date: D) -> &mut Self
+    where
+        D: AsRef<str>,
+    {
+        self.range("updated", "<", date.as_ref())
+    }
+
+    /// match results closed after a date or time
+    pub fn closed_after<D>(&mut self, date: D) -> &mut Self
+    where
+        D: AsRef<str>,
+    {
+        self.range("closed", ">", date.as_ref())
+    }
+
+    /// qualifies a date or time, whose `:`s needn't be quoted, by a range
+    /// operator
+    fn range(&mut self, name: &str, operator: &str, date: &str) -> &mut Self {
+        let is_date = !date.is_empty()
+            && date
+                .chars()
+                .all(|c| c.is_ascii_digit() || "-:.+TZ".contains(c));
+        let date = if is_date {
+            date.to_owned()
+        } else {
+            quote(date)
+        };
+        self.terms.push(format!("{}:{}{}", name, operator, date));
+        self
+    }
+}
+
+impl fmt::Display for SearchQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.terms.join(" ").fmt(f)
+    }
+}
+
+impl<'a> From<&'a SearchQuery> for String {
+    fn from(query: &'a SearchQuery) -> String {
+        query.to_string()
+    }
+}
+
+impl<'a> From<&'a mut SearchQuery> for String {
+    fn from(query: &'a mut SearchQuery) -> String {
+        query.to_string()
+    }
+}
+
+/// quotes values Github would otherwise split or interpret, like those
+/// containing qualifiers, exclusions or the `AND`, `OR` and `NOT` operators,
+/// escaping any quotes and backslashes they contain
+fn quote(value: &str) -> String {
+    let needs_quotes = value.is_empty()
+        || value.starts_with('-')
+        || value == "AND"
+        || value == "OR"
+        || value == "NOT"
+        || value.chars().any(|c| {
+            c.is_whitespace() || c == '"' || c == '\\' || c == '(' || c == ')' || c == ':'
+        });
+    if !needs_quotes {
+        return value.to_owned();
+    }
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        if c == '"' || c == '\\' {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quoting() {
+        assert_eq!(quote("bug"), "bug");
+        assert_eq!(quote("good first issue"), r#""good first issue""#);
+        assert_eq!(quote(r#"say "hi""#), r#""say \"hi\"""#);
+        assert_eq!(quote(""), r#""""#);
+        assert_eq!(quote("is:pr"), r#""is:pr""#);
+        assert_eq!(quote("-wontfix"), r#""-wontfix""#);
+        assert_eq!(quote("NOT"), r#""NOT""#);
+        assert_eq!(quote("OR"), r#""OR""#);
+        assert_eq!(quote("AND"), r#""AND""#);
+        assert_eq!(quote("android"), "android");
+        assert_eq!(quote("re-run"), "re-run");
+    }
+
+    #[test]
+    fn search_query() {
+        let query = SearchQuery::new()
+            .keyword("memory leak")
+            .repo("softprops/hubcaps")
+            .is_pr()
+            .exclude("label", "wontfix")
+            .updated_before("2019-06-01")
+            .to_string();
+        assert_eq!(
+            query,
+            r#""memory leak" repo:softprops/hubcaps is:pr -label:wontfix updated:<2019-06-01"#
+        );
+        let query = SearchQuery::new()
+            .keyword("NOT")
+            .label("scope:api")
+            .created_after("2019-01-01T12:00:00Z")
+            .to_string();
+        assert_eq!(
+            query,
+            r#""NOT" label:"scope:api" created:>2019-01-01T12:00:00Z"#
+        );
+    }
+}
@@ -0,0 +1,398 @@
+//! Secret scanning interface
+//!
+//! See the [github docs](https://docs.github.com/en/rest/secret-scanning) for more information
+
+use std::collections::HashMap;
+use std::fmt;
+
+use futures::future;
+use hyper::client::connect::Connect;
+use serde_json;
+use url::form_urlencoded;
+
+use code_scanning::AlertRepository;
+use users::User;
+use {unfold, Future, Github, Stream};
+
+fn identity<T>(x: T) -> T {
+    x
+}
+
+/// The state of a secret scanning alert
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertState {
+    Open,
+    Resolved,
+}
+
+impl fmt::Display for AlertState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AlertState::Open => "open",
+            AlertState::Resolved => "resolved",
+        }
+        .fmt(f)
+    }
+}
+
+/// The reason a secret scanning alert was resolved
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Resolution {
+    FalsePositive,
+    WontFix,
+    Revoked,
+    UsedInTests,
+}
+
+impl fmt::Display for Resolution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Resolution::FalsePositive => "false_positive",
+            Resolution::WontFix => "wont_fix",
+            Resolution::Revoked => "revoked",
+            Resolution::UsedInTests => "used_in_tests",
+        }
+        .fmt(f)
+    }
+}
+
+/// Provides access to secret scanning operations for a repository
+pub struct SecretScanning<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    owner: String,
+    repo: String,
+}
+
+impl<C: Clone + Connect + 'static> SecretScanning<C> {
+    #[doc(hidden)]
+    pub fn new<O, R>(github: Github<C>, owner: O, repo: R) -> Self
+    where
+        O: Into<String>,
+        R: Into<String>,
+    {
+        SecretScanning {
+            github,
+            owner: owner.into(),
+            repo: repo.into(),
+        }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!("/repos/{}/{}{}", self.owner, self.repo, more)
+    }
+
+    /// list secret scanning alerts
+    pub fn list(&self, options: &AlertListOptions) -> Future<Vec<Alert>> {
+        let mut uri = vec![self.path("/secret-scanning/alerts")];
+        if let Some(query) = options.serialize() {
+            uri.push(query);
+        }
+        self.github.get(&uri.join("?"))
+    }
+
+    /// provides a stream over all pages of secret scanning alerts
+    pub fn iter(&self, options: &AlertListOptions) -> Stream<Alert> {
+        let mut uri = vec![self.path("/secret-scanning/alerts")];
+        if let Some(query) = options.serialize() {
+            uri.push(query);
+        }
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&uri.join("?")),
+            identity,
+        )
+    }
+
+    /// get a secret scanning alert by number
+    pub fn get(&self, number: u64) -> Future<Alert> {
+        self.github
+            .get(&self.path(&format!("/secret-scanning/alerts/{}", number)))
+    }
+
+    /// update the state of a secret scanning alert, typically to resolve it
+    pub fn update(&self, number: u64, options: &AlertUpdateOptions) -> Future<Alert> {
+        self.github.patch(
+            &self.path(&format!("/secret-scanning/alerts/{}", number)),
+            json!(options),
+        )
+    }
+
+    /// list the places a secret of an alert was found
+    pub fn locations(&self, number: u64) -> Future<Vec<Location>> {
+        self.github
+            .get(&self.path(&format!("/secret-scanning/alerts/{}/locations", number)))
+    }
+
+    /// provides a stream over all pages of the places a secret of an alert was found
+    pub fn iter_locations(&self, number: u64) -> Stream<Location> {
+        unfold(
+            self.github.clone(),
+            self.github
+                .get_pages(&self.path(&format!("/secret-scanning/alerts/{}/locations", number))),
+            identity,
+        )
+    }
+
+    /// list requests to bypass push protection for this repository
+    pub fn bypass_requests(&self) -> Future<Vec<BypassRequest>> {
+        self.github
+            .get(&self.path("/bypass-requests/secret-scanning"))
+    }
+}
+
+/// Provides access to secret scanning alerts across the repositories
+/// of an organization or enterprise
+pub struct OrgSecretScanning<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    path: String,
+}
+
+impl<C: Clone + Connect + 'static> OrgSecretScanning<C> {
+    #[doc(hidden)]
+    pub fn new<P>(github: Github<C>, path: P) -> Self
+    where
+        P: Into<String>,
+    {
+        OrgSecretScanning {
+            github,
+            path: path.into(),
+        }
+    }
+
+    fn path(&self, options: &AlertListOptions) -> String {
+        let mut uri = vec![format!("{}/secret-scanning/alerts", self.path)];
+        if let Some(query) = options.serialize() {
+            uri.push(query);
+        }
+        uri.join("?")
+    }
+
+    /// list secret scanning alerts
+    pub fn list(&self, options: &AlertListOptions) -> Future<Vec<Alert>> {
+        self.github.get(&self.path(options))
+    }
+
+    /// provides a stream over all pages of secret scanning alerts
+    pub fn iter(&self, options: &AlertListOptions) -> Stream<Alert> {
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&self.path(options)),
+            identity,
+        )
+    }
+}
+
+// representations
+
+#[derive(Debug, Deserialize)]
+pub struct Alert {
+    pub number: u64,
+    pub url: String,
+    pub html_url: String,
+    pub locations_url: String,
+    pub state: String,
+    pub created_at: String,
+    pub updated_at: Option<String>,
+    pub resolution: Option<String>,
+    pub resolved_at: Option<String>,
+    pub resolved_by: Option<User>,
+    pub resolution_comment: Option<String>,
+    pub secret_type: String,
+    pub secret_type_display_name: Option<String>,
+    pub secret: Option<String>,
+    /// one of `active`, `inactive` or `unknown`
+    pub validity: Option<String>,
+    pub push_protection_bypassed: Option<bool>,
+    pub push_protection_bypassed_by: Option<User>,
+    pub push_protection_bypassed_at: Option<String>,
+    /// only included when listing an organization's or enterprise's alerts
+    pub repository: Option<AlertRepository>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Location {
+    /// one of `commit`, `issue_title`, `issue_body`, `issue_comment`,
+    /// `discussion_title`, `discussion_body`, `discussion_comment`,
+    /// `pull_request_title`, `pull_request_body`, `pull_request_comment`,
+    /// `pull_request_review` or `pull_request_review_comment`
+    #[serde(rename = "type")]
+    pub location_type: String,
+    pub details: LocationDetails,
+}
+
+/// The details of a location. Which fields are present depends on the
+/// location's type. Commit locations include a path, lines and columns
+/// whereas other locations refer to the url of where the secret was found
+#[derive(Debug, Deserialize)]
+pub struct LocationDetails {
+    pub path: Option<String>,
+    pub start_line: Option<u32>,
+    pub end_line: Option<u32>,
+    pub start_column: Option<u32>,
+    pub end_column: Option<u32>,
+    pub blob_sha: Option<String>,
+    pub blob_url: Option<String>,
+    pub commit_sha: Option<String>,
+    pub commit_url: Option<String>,
+    pub issue_title_url: Option<String>,
+    pub issue_body_url: Option<String>,
+    pub issue_comment_url: Option<String>,
+    pub discussion_title_url: Option<String>,
+    pub discussion_body_url: Option<String>,
+    pub discussion_comment_url: Option<String>,
+    pub pull_request_title_url: Option<String>,
+    pub pull_request_body_url: Option<String>,
+    pub pull_request_comment_url: Option<String>,
+    pub pull_request_review_url: Option<String>,
+    pub pull_request_review_comment_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BypassRequest {
+    pub id: u64,
+    pub number: u64,
+    /// one of `pending`, `denied`, `approved`, `cancelled`, `completed` or `expired`
+    pub status: String,
+    pub requester_comment: Option<String>,
+    pub created_at: String,
+    pub expires_at: Option<String>,
+    pub url: String,
+    pub html_url: Option<String>,
+}
+
+#[derive(Default)]
+pub struct AlertListOptions {
+    params: HashMap<&'static str, String>,
+}
+
+impl AlertListOptions {
+    pub fn builder() -> AlertListOptionsBuilder {
+        AlertListOptionsBuilder::default()
+    }
+
+    /// serialize options as a string. returns None if no options are defined
+    pub fn serialize(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded)
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct AlertListOptionsBuilder(AlertListOptions);
+
+impl AlertListOptionsBuilder {
+    pub fn state(&mut self, state: AlertState) -> &mut Self {
+        self.0.params.insert("state", state.to_string());
+        self
+    }
+
+    /// a comma separated list of secret types
+    pub fn secret_type<S>(&mut self, secret_type: S) -> &mut Self
+    where
+        S: Into<String>,
+    {
+        self.0.params.insert("secret_type", secret_type.into());
+        self
+    }
+
+    pub fn resolution(&mut self, resolution: Resolution) -> &mut Self {
+        self.0.params.insert("resolution", resolution.to_string());
+        self
+    }
+
+    /// one of `active`, `inactive` or `unknown`
+    pub fn validity<V>(&mut self, validity: V) -> &mut Self
+    where
+        V: Into<String>,
+    {
+        self.0.params.insert("validity", validity.into());
+        self
+    }
+
+    pub fn per_page(&mut self, n: usize) -> &mut Self {
+        self.0.params.insert("per_page", n.to_string());
+        self
+    }
+
+    pub fn build(&self) -> AlertListOptions {
+        AlertListOptions {
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+/// options for updating a secret scanning alert
+#[derive(Debug, Serialize)]
+pub struct AlertUpdateOptions {
+    pub state: AlertState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<Resolution>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolution_comment: Option<String>,
+}
+
+impl AlertUpdateOptions {
+    pub fn builder(state: AlertState) -> AlertUpdateOptionsBuilder {
+        AlertUpdateOptionsBuilder(AlertUpdateOptions {
+            state,
+            resolution: None,
+            resolution_comment: None,
+        })
+    }
+}
+
+pub struct AlertUpdateOptionsBuilder(AlertUpdateOptions);
+
+impl AlertUpdateOptionsBuilder {
+    /// required when resolving an alert
+    pub fn resolution(&mut self, resolution: Resolution) -> &mut Self {
+        self.0.resolution = Some(resolution);
+        self
+    }
+
+    pub fn resolution_comment<C>(&mut self, comment: C) -> &mut Self
+    where
+        C: Into<String>,
+    {
+        self.0.resolution_comment = Some(comment.into());
+        self
+    }
+
+    pub fn build(&self) -> AlertUpdateOptions {
+        AlertUpdateOptions {
+            state: self.0.state,
+            resolution: self.0.resolution,
+            resolution_comment: self.0.resolution_comment.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alert_update_reqs() {
+        let options = AlertUpdateOptions::builder(AlertState::Resolved)
+            .resolution(Resolution::UsedInTests)
+            .build();
+        assert_eq!(
+            serde_json::to_string(&options).unwrap(),
+            r#"{"state":"resolved","resolution":"used_in_tests"}"#
+        )
+    }
+}
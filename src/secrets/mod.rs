@@ -0,0 +1,286 @@
+//! Secrets interface
+//!
+//! Dependabot and Actions share the same flow for managing secrets.
+//! Secret values are encrypted with a sealed box using the public key of the
+//! repository or organization before they are sent to Github. With the `secrets`
+//! feature enabled, `Secrets::set` takes care of fetching the public key
+//! and encrypting a secret for you.
+//!
+//! See the [github docs](https://docs.github.com/en/rest/dependabot/secrets) for more information
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use futures::future;
+#[cfg(feature = "secrets")]
+use futures::Future as StdFuture;
+use hyper::client::connect::Connect;
+use serde_json;
+
+#[cfg(feature = "secrets")]
+use base64;
+#[cfg(feature = "secrets")]
+use sodiumoxide::crypto::{box_, sealedbox};
+
+#[cfg(feature = "secrets")]
+use Error;
+use {Future, Github};
+
+/// Which repositories of an organization may access an organization secret
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Visibility {
+    All,
+    Private,
+    Selected,
+}
+
+impl fmt::Display for Visibility {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Visibility::All => "all",
+            Visibility::Private => "private",
+            Visibility::Selected => "selected",
+        }
+        .fmt(f)
+    }
+}
+
+/// Provides access to the secrets of a repository or organization
+pub struct Secrets<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    path: String,
+}
+
+impl<C: Clone + Connect + 'static> Secrets<C> {
+    #[doc(hidden)]
+    pub fn new<P>(github: Github<C>, path: P) -> Self
+    where
+        P: Into<String>,
+    {
+        Secrets {
+            github,
+            path: path.into(),
+        }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!("{}{}", self.path, more)
+    }
+
+    /// get the public key secrets must be encrypted with before they are created or updated
+    pub fn public_key(&self) -> Future<PublicKey> {
+        self.github.get(&self.path("/public-key"))
+    }
+
+    /// list secrets. values are never returned
+    pub fn list(&self) -> Future<SecretList> {
+        self.github.get(&self.path(""))
+    }
+
+    /// get a secret's metadata by name
+    pub fn get<N>(&self, name: N) -> Future<Secret>
+    where
+        N: Into<String>,
+    {
+        self.github.get(&self.path(&format!("/{}", name.into())))
+    }
+
+    /// create or update a secret with a value already encrypted with
+    /// this repository's or organization's public key
+    pub fn create_or_update<N>(&self, name: N, options: &SecretOptions) -> Future<()>
+    where
+        N: Into<String>,
+    {
+        self.github
+            .put_no_response(&self.path(&format!("/{}", name.into())), json!(options))
+    }
+
+    /// encrypt and store a secret. This fetches the public key to encrypt
+    /// the value with first. Organization secrets are visible to private
+    /// repositories. Use `create_or_update` for control over their visibility
+    #[cfg(feature = "secrets")]
+    pub fn set<N, V>(&self, name: N, value: V) -> Future<()>
+    where
+        N: Into<String>,
+        V: AsRef<[u8]>,
+    {
+        let github = self.github.clone();
+        let uri = self.path(&format!("/{}", name.into()));
+        let is_org = self.path.starts_with("/orgs/");
+        let value = value.as_ref().to_vec();
+        Box::new(self.public_key().and_then(move |key| {
+            let encrypted_value = match encrypt(&key, &value) {
+                Ok(encrypted) => encrypted,
+                Err(err) => return Box::new(future::err(err)) as Future<()>,
+            };
+            let mut options = SecretOptions::builder(encrypted_value, key.key_id);
+            if is_org {
+                options.visibility(Visibility::Private);
+            }
+            let options = options.build();
+            github.put_no_response(&uri, json!(options))
+        }))
+    }
+
+    /// delete a secret by name
+    pub fn delete<N>(&self, name: N) -> Future<()>
+    where
+        N: Into<String>,
+    {
+        self.github.delete(&self.path(&format!("/{}", name.into())))
+    }
+
+    /// list the repositories that may access an organization secret
+    /// with `selected` visibility
+    pub fn selected_repositories<N>(&self, name: N) -> Future<SelectedRepositories>
+    where
+        N: Into<String>,
+    {
+        self.github
+            .get(&self.path(&format!("/{}/repositories", name.into())))
+    }
+
+    /// replace the repositories that may access an organization secret
+    /// with `selected` visibility
+    pub fn set_selected_repositories<N>(&self, name: N, repository_ids: Vec<u64>) -> Future<()>
+    where
+        N: Into<String>,
+    {
+        let mut payload = BTreeMap::new();
+        payload.insert("selected_repository_ids", repository_ids);
+        self.github.put_no_response(
+            &self.path(&format!("/{}/repositories", name.into())),
+            json!(payload),
+        )
+    }
+}
+
+/// encrypts a secret's value with a sealed box using a repository's
+/// or organization's public key, returning the base64 encoded result
+#[cfg(feature = "secrets")]
+pub fn encrypt(key: &PublicKey, value: &[u8]) -> ::Result<String> {
+    // init is idempotent and only selects the fastest implementations available
+    let _ = ::sodiumoxide::init();
+    let decoded = base64::decode(&key.key)
+        .map_err(|err| Error::from(format!("invalid public key {}: {}", key.key_id, err)))?;
+    let public_key = box_::PublicKey::from_slice(&decoded)
+        .ok_or_else(|| Error::from(format!("invalid public key {}", key.key_id)))?;
+    Ok(base64::encode(&sealedbox::seal(value, &public_key)))
+}
+
+// representations
+
+#[derive(Debug, Deserialize)]
+pub struct PublicKey {
+    pub key_id: String,
+    /// the base64 encoded public key
+    pub key: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SecretList {
+    pub total_count: u64,
+    pub secrets: Vec<Secret>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Secret {
+    pub name: String,
+    pub created_at: String,
+    pub updated_at: String,
+    /// only included for organization secrets
+    pub visibility: Option<Visibility>,
+    pub selected_repositories_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SelectedRepositories {
+    pub total_count: u64,
+    pub repositories: Vec<::serde_json::Value>,
+}
+
+/// options for creating or updating a secret
+#[derive(Debug, Default, Serialize)]
+pub struct SecretOptions {
+    pub encrypted_value: String,
+    pub key_id: String,
+    /// required for organization secrets
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<Visibility>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selected_repository_ids: Option<Vec<u64>>,
+}
+
+impl SecretOptions {
+    pub fn builder<V, K>(encrypted_value: V, key_id: K) -> SecretOptionsBuilder
+    where
+        V: Into<String>,
+        K: Into<String>,
+    {
+        SecretOptionsBuilder(SecretOptions {
+            encrypted_value: encrypted_value.into(),
+            key_id: key_id.into(),
+            ..Default::default()
+        })
+    }
+}
+
+pub struct SecretOptionsBuilder(SecretOptions);
+
+impl SecretOptionsBuilder {
+    pub fn visibility(&mut self, visibility: Visibility) -> &mut Self {
+        self.0.visibility = Some(visibility);
+        self
+    }
+
+    /// the repositories that may access a secret with `selected` visibility
+    pub fn selected_repository_ids(&mut self, ids: Vec<u64>) -> &mut Self {
+        self.0.selected_repository_ids = Some(ids);
+        self
+    }
+
+    pub fn build(&self) -> SecretOptions {
+        SecretOptions {
+            encrypted_value: self.0.encrypted_value.clone(),
+            key_id: self.0.key_id.clone(),
+            visibility: self.0.visibility,
+            selected_repository_ids: self.0.selected_repository_ids.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secret_reqs() {
+        let options = SecretOptions::builder("c2VjcmV0", "123")
+            .visibility(Visibility::Selected)
+            .selected_repository_ids(vec![1, 2])
+            .build();
+        assert_eq!(
+            serde_json::to_string(&options).unwrap(),
+            r#"{"encrypted_value":"c2VjcmV0","key_id":"123","visibility":"selected","selected_repository_ids":[1,2]}"#
+        )
+    }
+
+    #[cfg(feature = "secrets")]
+    #[test]
+    fn encrypt_roundtrip() {
+        let (pk, sk) = box_::gen_keypair();
+        let key = PublicKey {
+            key_id: "1".into(),
+            key: base64::encode(pk.as_ref()),
+        };
+        let encrypted = base64::decode(&encrypt(&key, b"hunter2").unwrap()).unwrap();
+        assert_eq!(
+            sealedbox::open(&encrypted, &pk, &sk).unwrap(),
+            b"hunter2".to_vec()
+        )
+    }
+}
@@ -0,0 +1,720 @@
+//! Security advisories interface
+//!
+//! See the [github docs](https://docs.github.com/en/rest/security-advisories) for more information
+
+use std::collections::HashMap;
+use std::fmt;
+
+use futures::future;
+use hyper::client::connect::Connect;
+use serde_json;
+use url::form_urlencoded;
+
+use repositories::Repo;
+use users::User;
+use {unfold, Future, Github, SortDirection, Stream};
+
+fn identity<T>(x: T) -> T {
+    x
+}
+
+/// The state of a repository security advisory
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum State {
+    Triage,
+    Draft,
+    Published,
+    Closed,
+}
+
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            State::Triage => "triage",
+            State::Draft => "draft",
+            State::Published => "published",
+            State::Closed => "closed",
+        }
+        .fmt(f)
+    }
+}
+
+/// The severity of an advisory
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Critical,
+    High,
+    Medium,
+    Low,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Severity::Critical => "critical",
+            Severity::High => "high",
+            Severity::Medium => "medium",
+            Severity::Low => "low",
+        }
+        .fmt(f)
+    }
+}
+
+/// Provides access to the security advisories of a repository
+pub struct SecurityAdvisories<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    owner: String,
+    repo: String,
+}
+
+impl<C: Clone + Connect + 'static> SecurityAdvisories<C> {
+    #[doc(hidden)]
+    pub fn new<O, R>(github: Github<C>, owner: O, repo: R) -> Self
+    where
+        O: Into<String>,
+        R: Into<String>,
+    {
+        SecurityAdvisories {
+            github,
+            owner: owner.into(),
+            repo: repo.into(),
+        }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!(
+            "/repos/{}/{}/security-advisories{}",
+            self.owner, self.repo, more
+        )
+    }
+
+    /// list the security advisories of this repository
+    pub fn list(&self, options: &AdvisoryListOptions) -> Future<Vec<SecurityAdvisory>> {
+        let mut uri = vec![self.path("")];
+        if let Some(query) = options.serialize() {
+            uri.push(query);
+        }
+        self.github.get(&uri.join("?"))
+    }
+
+    /// provides a stream over all pages of this repository's security advisories
+    pub fn iter(&self, options: &AdvisoryListOptions) -> Stream<SecurityAdvisory> {
+        let mut uri = vec![self.path("")];
+        if let Some(query) = options.serialize() {
+            uri.push(query);
+        }
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&uri.join("?")),
+            identity,
+        )
+    }
+
+    /// create a draft security advisory
+    pub fn create(&self, options: &AdvisoryOptions) -> Future<SecurityAdvisory> {
+        self.github.post(&self.path(""), json!(options))
+    }
+
+    /// get a security advisory by its GHSA id
+    pub fn get(&self, ghsa_id: &str) -> Future<SecurityAdvisory> {
+        self.github.get(&self.path(&format!("/{}", ghsa_id)))
+    }
+
+    /// update a security advisory. publishing an advisory is done by
+    /// updating its state to `Published`
+    pub fn edit(&self, ghsa_id: &str, options: &AdvisoryEditOptions) -> Future<SecurityAdvisory> {
+        self.github
+            .patch(&self.path(&format!("/{}", ghsa_id)), json!(options))
+    }
+
+    /// request a CVE id for a security advisory from Github
+    pub fn request_cve(&self, ghsa_id: &str) -> Future<()> {
+        self.github
+            .post_no_response(&self.path(&format!("/{}/cve", ghsa_id)), Vec::new())
+    }
+
+    /// create a temporary private fork to collaborate on fixing a security advisory
+    pub fn create_fork(&self, ghsa_id: &str) -> Future<Repo> {
+        self.github
+            .post(&self.path(&format!("/{}/forks", ghsa_id)), Vec::new())
+    }
+}
+
+/// Provides access to the security advisories across the repositories of an organization
+pub struct OrgSecurityAdvisories<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    org: String,
+}
+
+impl<C: Clone + Connect + 'static> OrgSecurityAdvisories<C> {
+    #[doc(hidden)]
+    pub fn new<O>(github: Github<C>, org: O) -> Self
+    where
+        O: Into<String>,
+    {
+        OrgSecurityAdvisories {
+            github,
+            org: org.into(),
+        }
+    }
+
+    fn path(&self, options: &AdvisoryListOptions) -> String {
+        let mut uri = vec![format!("/orgs/{}/security-advisories", self.org)];
+        if let Some(query) = options.serialize() {
+            uri.push(query);
+        }
+        uri.join("?")
+    }
+
+    /// list the security advisories of this organization's repositories
+    pub fn list(&self, options: &AdvisoryListOptions) -> Future<Vec<SecurityAdvisory>> {
+        self.github.get(&self.path(options))
+    }
+
+    /// provides a stream over all pages of this organization's security advisories
+    pub fn iter(&self, options: &AdvisoryListOptions) -> Stream<SecurityAdvisory> {
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&self.path(options)),
+            identity,
+        )
+    }
+}
+
+/// Provides access to the global security advisories of the Github Advisory Database
+pub struct Advisories<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+}
+
+impl<C: Clone + Connect + 'static> Advisories<C> {
+    #[doc(hidden)]
+    pub fn new(github: Github<C>) -> Self {
+        Self { github }
+    }
+
+    fn path(&self, options: &GlobalAdvisoryListOptions) -> String {
+        let mut uri = vec!["/advisories".to_owned()];
+        if let Some(query) = options.serialize() {
+            uri.push(query);
+        }
+        uri.join("?")
+    }
+
+    /// list global security advisories
+    pub fn list(&self, options: &GlobalAdvisoryListOptions) -> Future<Vec<GlobalAdvisory>> {
+        self.github.get(&self.path(options))
+    }
+
+    /// provides a stream over all pages of global security advisories
+    pub fn iter(&self, options: &GlobalAdvisoryListOptions) -> Stream<GlobalAdvisory> {
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&self.path(options)),
+            identity,
+        )
+    }
+
+    /// get a global security advisory by its GHSA id
+    pub fn get(&self, ghsa_id: &str) -> Future<GlobalAdvisory> {
+        self.github.get(&format!("/advisories/{}", ghsa_id))
+    }
+}
+
+// representations
+
+#[derive(Debug, Deserialize)]
+pub struct SecurityAdvisory {
+    pub ghsa_id: String,
+    pub cve_id: Option<String>,
+    pub url: String,
+    pub html_url: String,
+    pub summary: String,
+    pub description: Option<String>,
+    pub severity: Option<Severity>,
+    pub state: State,
+    pub author: Option<User>,
+    pub publisher: Option<User>,
+    pub identifiers: Vec<Identifier>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub published_at: Option<String>,
+    pub closed_at: Option<String>,
+    pub withdrawn_at: Option<String>,
+    pub vulnerabilities: Option<Vec<Vulnerability>>,
+    pub cvss: Option<Cvss>,
+    pub cwe_ids: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GlobalAdvisory {
+    pub ghsa_id: String,
+    pub cve_id: Option<String>,
+    pub url: String,
+    pub html_url: String,
+    pub repository_advisory_url: Option<String>,
+    pub summary: String,
+    pub description: Option<String>,
+    /// one of `reviewed`, `unreviewed` or `malware`
+    #[serde(rename = "type")]
+    pub advisory_type: String,
+    /// one of `critical`, `high`, `medium`, `low` or `unknown`
+    pub severity: String,
+    pub source_code_location: Option<String>,
+    pub identifiers: Option<Vec<Identifier>>,
+    pub references: Option<Vec<String>>,
+    pub published_at: String,
+    pub updated_at: String,
+    pub github_reviewed_at: Option<String>,
+    pub nvd_published_at: Option<String>,
+    pub withdrawn_at: Option<String>,
+    pub vulnerabilities: Option<Vec<GlobalVulnerability>>,
+    pub cvss: Option<Cvss>,
+    pub cwes: Option<Vec<Cwe>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Identifier {
+    /// either `CVE` or `GHSA`
+    #[serde(rename = "type")]
+    pub identifier_type: String,
+    pub value: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Cvss {
+    pub vector_string: Option<String>,
+    pub score: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Cwe {
+    pub cwe_id: String,
+    pub name: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct Package {
+    /// the package's ecosystem, like `rust`, `npm` or `pip`
+    pub ecosystem: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// A vulnerable package of a repository security advisory
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct Vulnerability {
+    pub package: Option<Package>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vulnerable_version_range: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub patched_versions: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vulnerable_functions: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GlobalVulnerability {
+    pub package: Option<Package>,
+    pub vulnerable_version_range: Option<String>,
+    pub first_patched_version: Option<String>,
+    pub vulnerable_functions: Option<Vec<String>>,
+}
+
+#[derive(Default)]
+pub struct AdvisoryListOptions {
+    params: HashMap<&'static str, String>,
+}
+
+impl AdvisoryListOptions {
+    pub fn builder() -> AdvisoryListOptionsBuilder {
+        AdvisoryListOptionsBuilder::default()
+    }
+
+    /// serialize options as a string. returns None if no options are defined
+    pub fn serialize(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded)
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct AdvisoryListOptionsBuilder(AdvisoryListOptions);
+
+impl AdvisoryListOptionsBuilder {
+    pub fn state(&mut self, state: State) -> &mut Self {
+        self.0.params.insert("state", state.to_string());
+        self
+    }
+
+    /// one of `created`, `updated` or `published`
+    pub fn sort<S>(&mut self, sort: S) -> &mut Self
+    where
+        S: Into<String>,
+    {
+        self.0.params.insert("sort", sort.into());
+        self
+    }
+
+    pub fn direction(&mut self, direction: SortDirection) -> &mut Self {
+        self.0.params.insert("direction", direction.to_string());
+        self
+    }
+
+    pub fn per_page(&mut self, n: usize) -> &mut Self {
+        self.0.params.insert("per_page", n.to_string());
+        self
+    }
+
+    pub fn build(&self) -> AdvisoryListOptions {
+        AdvisoryListOptions {
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct GlobalAdvisoryListOptions {
+    params: HashMap<&'static str, String>,
+}
+
+impl GlobalAdvisoryListOptions {
+    pub fn builder() -> GlobalAdvisoryListOptionsBuilder {
+        GlobalAdvisoryListOptionsBuilder::default()
+    }
+
+    /// serialize options as a string. returns None if no options are defined
+    pub fn serialize(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded)
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct GlobalAdvisoryListOptionsBuilder(GlobalAdvisoryListOptions);
+
+impl GlobalAdvisoryListOptionsBuilder {
+    /// one of `reviewed`, `unreviewed` or `malware`. defaults to `reviewed`
+    pub fn advisory_type<T>(&mut self, advisory_type: T) -> &mut Self
+    where
+        T: Into<String>,
+    {
+        self.0.params.insert("type", advisory_type.into());
+        self
+    }
+
+    pub fn ecosystem<E>(&mut self, ecosystem: E) -> &mut Self
+    where
+        E: Into<String>,
+    {
+        self.0.params.insert("ecosystem", ecosystem.into());
+        self
+    }
+
+    pub fn severity(&mut self, severity: Severity) -> &mut Self {
+        self.0.params.insert("severity", severity.to_string());
+        self
+    }
+
+    pub fn cve_id<C>(&mut self, cve_id: C) -> &mut Self
+    where
+        C: Into<String>,
+    {
+        self.0.params.insert("cve_id", cve_id.into());
+        self
+    }
+
+    /// only advisories affecting a package, optionally at a version, like `serde@1.0.0`
+    pub fn affects<A>(&mut self, affects: A) -> &mut Self
+    where
+        A: Into<String>,
+    {
+        self.0.params.insert("affects", affects.into());
+        self
+    }
+
+    pub fn is_withdrawn(&mut self, withdrawn: bool) -> &mut Self {
+        self.0.params.insert("is_withdrawn", withdrawn.to_string());
+        self
+    }
+
+    pub fn direction(&mut self, direction: SortDirection) -> &mut Self {
+        self.0.params.insert("direction", direction.to_string());
+        self
+    }
+
+    pub fn per_page(&mut self, n: usize) -> &mut Self {
+        self.0.params.insert("per_page", n.to_string());
+        self
+    }
+
+    pub fn build(&self) -> GlobalAdvisoryListOptions {
+        GlobalAdvisoryListOptions {
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+/// options for creating a draft repository security advisory
+#[derive(Debug, Default, Serialize)]
+pub struct AdvisoryOptions {
+    pub summary: String,
+    pub description: String,
+    pub vulnerabilities: Vec<Vulnerability>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cve_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cwe_ids: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub severity: Option<Severity>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cvss_vector_string: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_private_fork: Option<bool>,
+}
+
+impl AdvisoryOptions {
+    pub fn builder<S, D>(summary: S, description: D) -> AdvisoryOptionsBuilder
+    where
+        S: Into<String>,
+        D: Into<String>,
+    {
+        AdvisoryOptionsBuilder(AdvisoryOptions {
+            summary: summary.into(),
+            description: description.into(),
+            ..Default::default()
+        })
+    }
+}
+
+pub struct AdvisoryOptionsBuilder(AdvisoryOptions);
+
+impl AdvisoryOptionsBuilder {
+    /// add a vulnerable package
+    pub fn vulnerability(&mut self, vulnerability: Vulnerability) -> &mut Self {
+        self.0.vulnerabilities.push(vulnerability);
+        self
+    }
+
+    pub fn cve_id<C>(&mut self, cve_id: C) -> &mut Self
+    where
+        C: Into<String>,
+    {
+        self.0.cve_id = Some(cve_id.into());
+        self
+    }
+
+    pub fn cwe_ids<C>(&mut self, cwe_ids: Vec<C>) -> &mut Self
+    where
+        C: Into<String>,
+    {
+        self.0.cwe_ids = Some(cwe_ids.into_iter().map(|c| c.into()).collect());
+        self
+    }
+
+    /// the severity of the advisory. mutually exclusive with `cvss_vector_string`
+    pub fn severity(&mut self, severity: Severity) -> &mut Self {
+        self.0.severity = Some(severity);
+        self
+    }
+
+    /// the CVSS vector the severity of the advisory is calculated from.
+    /// mutually exclusive with `severity`
+    pub fn cvss_vector_string<V>(&mut self, vector: V) -> &mut Self
+    where
+        V: Into<String>,
+    {
+        self.0.cvss_vector_string = Some(vector.into());
+        self
+    }
+
+    /// start a temporary private fork to collaborate on a fix in
+    pub fn start_private_fork(&mut self, fork: bool) -> &mut Self {
+        self.0.start_private_fork = Some(fork);
+        self
+    }
+
+    pub fn build(&self) -> AdvisoryOptions {
+        AdvisoryOptions {
+            summary: self.0.summary.clone(),
+            description: self.0.description.clone(),
+            vulnerabilities: self.0.vulnerabilities.clone(),
+            cve_id: self.0.cve_id.clone(),
+            cwe_ids: self.0.cwe_ids.clone(),
+            severity: self.0.severity,
+            cvss_vector_string: self.0.cvss_vector_string.clone(),
+            start_private_fork: self.0.start_private_fork,
+        }
+    }
+}
+
+/// options for updating a repository security advisory
+#[derive(Debug, Default, Serialize)]
+pub struct AdvisoryEditOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vulnerabilities: Option<Vec<Vulnerability>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cve_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cwe_ids: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub severity: Option<Severity>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cvss_vector_string: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<State>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collaborating_users: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collaborating_teams: Option<Vec<String>>,
+}
+
+impl AdvisoryEditOptions {
+    pub fn builder() -> AdvisoryEditOptionsBuilder {
+        AdvisoryEditOptionsBuilder::default()
+    }
+}
+
+#[derive(Default)]
+pub struct AdvisoryEditOptionsBuilder(AdvisoryEditOptions);
+
+impl AdvisoryEditOptionsBuilder {
+    pub fn summary<S>(&mut self, summary: S) -> &mut Self
+    where
+        S: Into<String>,
+    {
+        self.0.summary = Some(summary.into());
+        self
+    }
+
+    pub fn description<D>(&mut self, description: D) -> &mut Self
+    where
+        D: Into<String>,
+    {
+        self.0.description = Some(description.into());
+        self
+    }
+
+    /// replace the vulnerable packages
+    pub fn vulnerabilities(&mut self, vulnerabilities: Vec<Vulnerability>) -> &mut Self {
+        self.0.vulnerabilities = Some(vulnerabilities);
+        self
+    }
+
+    pub fn cve_id<C>(&mut self, cve_id: C) -> &mut Self
+    where
+        C: Into<String>,
+    {
+        self.0.cve_id = Some(cve_id.into());
+        self
+    }
+
+    pub fn cwe_ids<C>(&mut self, cwe_ids: Vec<C>) -> &mut Self
+    where
+        C: Into<String>,
+    {
+        self.0.cwe_ids = Some(cwe_ids.into_iter().map(|c| c.into()).collect());
+        self
+    }
+
+    pub fn severity(&mut self, severity: Severity) -> &mut Self {
+        self.0.severity = Some(severity);
+        self
+    }
+
+    pub fn cvss_vector_string<V>(&mut self, vector: V) -> &mut Self
+    where
+        V: Into<String>,
+    {
+        self.0.cvss_vector_string = Some(vector.into());
+        self
+    }
+
+    pub fn state(&mut self, state: State) -> &mut Self {
+        self.0.state = Some(state);
+        self
+    }
+
+    /// the logins of users to collaborate on the advisory with
+    pub fn collaborating_users<U>(&mut self, users: Vec<U>) -> &mut Self
+    where
+        U: Into<String>,
+    {
+        self.0.collaborating_users = Some(users.into_iter().map(|u| u.into()).collect());
+        self
+    }
+
+    /// the slugs of teams to collaborate on the advisory with
+    pub fn collaborating_teams<T>(&mut self, teams: Vec<T>) -> &mut Self
+    where
+        T: Into<String>,
+    {
+        self.0.collaborating_teams = Some(teams.into_iter().map(|t| t.into()).collect());
+        self
+    }
+
+    pub fn build(&self) -> AdvisoryEditOptions {
+        AdvisoryEditOptions {
+            summary: self.0.summary.clone(),
+            description: self.0.description.clone(),
+            vulnerabilities: self.0.vulnerabilities.clone(),
+            cve_id: self.0.cve_id.clone(),
+            cwe_ids: self.0.cwe_ids.clone(),
+            severity: self.0.severity,
+            cvss_vector_string: self.0.cvss_vector_string.clone(),
+            state: self.0.state,
+            collaborating_users: self.0.collaborating_users.clone(),
+            collaborating_teams: self.0.collaborating_teams.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn advisory_reqs() {
+        let options = AdvisoryOptions::builder("summary", "description")
+            .vulnerability(Vulnerability {
+                package: Some(Package {
+                    ecosystem: "rust".into(),
+                    name: Some("hubcaps".into()),
+                }),
+                vulnerable_version_range: Some("< 0.6.0".into()),
+                ..Default::default()
+            })
+            .severity(Severity::High)
+            .build();
+        assert_eq!(
+            serde_json::to_string(&options).unwrap(),
+            r#"{"summary":"summary","description":"description","vulnerabilities":[{"package":{"ecosystem":"rust","name":"hubcaps"},"vulnerable_version_range":"< 0.6.0"}],"severity":"high"}"#
+        )
+    }
+}
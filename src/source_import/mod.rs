@@ -0,0 +1,366 @@
+//! Source import interface
+//!
+//! Imports a repository from another version control system, like
+//! Subversion, Mercurial or TFVC, into Github. See the [github docs](https://docs.github.com/en/rest/migrations/source-imports)
+//! for more information
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use futures::future;
+use hyper::client::connect::Connect;
+use serde_json;
+
+use {Future, Github};
+
+/// The version control system of an import's source
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Vcs {
+    Subversion,
+    Git,
+    Mercurial,
+    Tfvc,
+}
+
+impl fmt::Display for Vcs {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Vcs::Subversion => "subversion",
+            Vcs::Git => "git",
+            Vcs::Mercurial => "mercurial",
+            Vcs::Tfvc => "tfvc",
+        }
+        .fmt(f)
+    }
+}
+
+/// Whether files larger than 100MB are imported with Git LFS
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LfsPreference {
+    OptIn,
+    OptOut,
+}
+
+/// Provides access to the source import of a repository
+pub struct SourceImport<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    owner: String,
+    repo: String,
+}
+
+impl<C: Clone + Connect + 'static> SourceImport<C> {
+    #[doc(hidden)]
+    pub fn new<O, R>(github: Github<C>, owner: O, repo: R) -> Self
+    where
+        O: Into<String>,
+        R: Into<String>,
+    {
+        SourceImport {
+            github,
+            owner: owner.into(),
+            repo: repo.into(),
+        }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!("/repos/{}/{}/import{}", self.owner, self.repo, more)
+    }
+
+    /// start importing into this repository
+    pub fn start(&self, options: &ImportOptions) -> Future<Import> {
+        self.github.put(&self.path(""), json!(options))
+    }
+
+    /// get the progress of this repository's import
+    pub fn get(&self) -> Future<Import> {
+        self.github.get(&self.path(""))
+    }
+
+    /// update the credentials or project of an import. an import whose
+    /// source was detected with more than one project or vcs is
+    /// resumed by choosing one
+    pub fn update(&self, options: &ImportUpdateOptions) -> Future<Import> {
+        self.github.patch(&self.path(""), json!(options))
+    }
+
+    /// stop this repository's import
+    pub fn cancel(&self) -> Future<()> {
+        self.github.delete(&self.path(""))
+    }
+
+    /// list the commit authors found in the source. pass the id of the
+    /// last author seen to only list authors found since
+    pub fn authors(&self, since: Option<u64>) -> Future<Vec<ImportAuthor>> {
+        let uri = match since {
+            Some(id) => self.path(&format!("/authors?since={}", id)),
+            None => self.path("/authors"),
+        };
+        self.github.get(&uri)
+    }
+
+    /// map a commit author of the source to a Github user's name and email
+    pub fn map_author(&self, author_id: u64, options: &AuthorOptions) -> Future<ImportAuthor> {
+        self.github.patch(
+            &self.path(&format!("/authors/{}", author_id)),
+            json!(options),
+        )
+    }
+
+    /// list the files larger than 100MB found in the source
+    pub fn large_files(&self) -> Future<Vec<LargeFile>> {
+        self.github.get(&self.path("/large_files"))
+    }
+
+    /// choose whether large files are imported with Git LFS
+    pub fn lfs_preference(&self, preference: LfsPreference) -> Future<Import> {
+        let mut payload = BTreeMap::new();
+        payload.insert("use_lfs", preference);
+        self.github.patch(&self.path("/lfs"), json!(payload))
+    }
+}
+
+// representations
+
+#[derive(Debug, Deserialize)]
+pub struct Import {
+    pub vcs: Option<String>,
+    pub use_lfs: Option<bool>,
+    pub vcs_url: String,
+    pub svc_root: Option<String>,
+    pub tfvc_project: Option<String>,
+    /// one of `auth`, `error`, `none`, `detecting`, `choose`, `auth_failed`,
+    /// `importing`, `mapping`, `waiting_to_push`, `pushing`, `complete`,
+    /// `setup`, `unknown`, `detection_found_multiple`, `detection_found_nothing`
+    /// or `detection_needs_auth`
+    pub status: String,
+    pub status_text: Option<String>,
+    pub failed_step: Option<String>,
+    pub error_message: Option<String>,
+    pub import_percent: Option<u32>,
+    pub commit_count: Option<u64>,
+    pub push_percent: Option<u32>,
+    pub has_large_files: Option<bool>,
+    pub large_files_size: Option<u64>,
+    pub large_files_count: Option<u64>,
+    /// the projects to choose from when the status is `detection_found_multiple`
+    pub project_choices: Option<Vec<ProjectChoice>>,
+    pub message: Option<String>,
+    pub authors_count: Option<u64>,
+    pub url: String,
+    pub html_url: String,
+    pub authors_url: String,
+    pub repository_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProjectChoice {
+    pub vcs: Option<String>,
+    pub tfvc_project: Option<String>,
+    pub human_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportAuthor {
+    pub id: u64,
+    /// the author's identifier in the source
+    pub remote_id: String,
+    pub remote_name: String,
+    pub email: String,
+    pub name: String,
+    pub url: String,
+    pub import_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LargeFile {
+    pub ref_name: String,
+    pub path: String,
+    pub oid: String,
+    pub size: u64,
+}
+
+/// options for starting a source import
+#[derive(Debug, Default, Serialize)]
+pub struct ImportOptions {
+    pub vcs_url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vcs: Option<Vcs>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vcs_username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vcs_password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tfvc_project: Option<String>,
+}
+
+impl ImportOptions {
+    pub fn builder<U>(vcs_url: U) -> ImportOptionsBuilder
+    where
+        U: Into<String>,
+    {
+        ImportOptionsBuilder(ImportOptions {
+            vcs_url: vcs_url.into(),
+            ..Default::default()
+        })
+    }
+}
+
+pub struct ImportOptionsBuilder(ImportOptions);
+
+impl ImportOptionsBuilder {
+    /// the source's vcs. detected when omitted
+    pub fn vcs(&mut self, vcs: Vcs) -> &mut Self {
+        self.0.vcs = Some(vcs);
+        self
+    }
+
+    pub fn credentials<U, P>(&mut self, username: U, password: P) -> &mut Self
+    where
+        U: Into<String>,
+        P: Into<String>,
+    {
+        self.0.vcs_username = Some(username.into());
+        self.0.vcs_password = Some(password.into());
+        self
+    }
+
+    /// the TFVC project to import. only applies to TFVC sources
+    pub fn tfvc_project<P>(&mut self, project: P) -> &mut Self
+    where
+        P: Into<String>,
+    {
+        self.0.tfvc_project = Some(project.into());
+        self
+    }
+
+    pub fn build(&self) -> ImportOptions {
+        ImportOptions {
+            vcs_url: self.0.vcs_url.clone(),
+            vcs: self.0.vcs,
+            vcs_username: self.0.vcs_username.clone(),
+            vcs_password: self.0.vcs_password.clone(),
+            tfvc_project: self.0.tfvc_project.clone(),
+        }
+    }
+}
+
+/// options for updating a source import
+#[derive(Debug, Default, Serialize)]
+pub struct ImportUpdateOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vcs: Option<Vcs>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vcs_username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vcs_password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tfvc_project: Option<String>,
+}
+
+impl ImportUpdateOptions {
+    pub fn builder() -> ImportUpdateOptionsBuilder {
+        ImportUpdateOptionsBuilder::default()
+    }
+}
+
+#[derive(Default)]
+pub struct ImportUpdateOptionsBuilder(ImportUpdateOptions);
+
+impl ImportUpdateOptionsBuilder {
+    pub fn vcs(&mut self, vcs: Vcs) -> &mut Self {
+        self.0.vcs = Some(vcs);
+        self
+    }
+
+    pub fn credentials<U, P>(&mut self, username: U, password: P) -> &mut Self
+    where
+        U: Into<String>,
+        P: Into<String>,
+    {
+        self.0.vcs_username = Some(username.into());
+        self.0.vcs_password = Some(password.into());
+        self
+    }
+
+    pub fn tfvc_project<P>(&mut self, project: P) -> &mut Self
+    where
+        P: Into<String>,
+    {
+        self.0.tfvc_project = Some(project.into());
+        self
+    }
+
+    pub fn build(&self) -> ImportUpdateOptions {
+        ImportUpdateOptions {
+            vcs: self.0.vcs,
+            vcs_username: self.0.vcs_username.clone(),
+            vcs_password: self.0.vcs_password.clone(),
+            tfvc_project: self.0.tfvc_project.clone(),
+        }
+    }
+}
+
+/// the Github identity to attribute a source commit author's commits to
+#[derive(Debug, Default, Serialize)]
+pub struct AuthorOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+impl AuthorOptions {
+    pub fn builder() -> AuthorOptionsBuilder {
+        AuthorOptionsBuilder::default()
+    }
+}
+
+#[derive(Default)]
+pub struct AuthorOptionsBuilder(AuthorOptions);
+
+impl AuthorOptionsBuilder {
+    pub fn email<E>(&mut self, email: E) -> &mut Self
+    where
+        E: Into<String>,
+    {
+        self.0.email = Some(email.into());
+        self
+    }
+
+    pub fn name<N>(&mut self, name: N) -> &mut Self
+    where
+        N: Into<String>,
+    {
+        self.0.name = Some(name.into());
+        self
+    }
+
+    pub fn build(&self) -> AuthorOptions {
+        AuthorOptions {
+            email: self.0.email.clone(),
+            name: self.0.name.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn import_reqs() {
+        let options = ImportOptions::builder("https://svn.example.com/project")
+            .vcs(Vcs::Subversion)
+            .credentials("octocat", "secret")
+            .build();
+        assert_eq!(
+            serde_json::to_string(&options).unwrap(),
+            r#"{"vcs_url":"https://svn.example.com/project","vcs":"subversion","vcs_username":"octocat","vcs_password":"secret"}"#
+        )
+    }
+}
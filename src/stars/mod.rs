@@ -1,10 +1,17 @@
 //! Stars interface
 
-use futures::Future as StdFuture;
+use futures::{future, stream, Future as StdFuture, Stream as StdStream};
 use hyper::client::connect::Connect;
 use hyper::StatusCode;
+use hyperx::header::{Link, RelationType};
+use url::Url;
 
-use {Error, ErrorKind, Future, Github};
+use users::User;
+use {unfold_media, Error, ErrorKind, Future, Github, MediaType, Stream};
+
+fn identity<T>(x: T) -> T {
+    x
+}
 
 pub struct Stars<C>
 where
@@ -65,3 +72,162 @@ impl<C: Clone + Connect + 'static> Stars<C> {
             .delete(&format!("/user/starred/{}/{}", owner.into(), repo.into()))
     }
 }
+
+/// Provides access to the stargazers of a repository, along with when they
+/// starred it
+pub struct Stargazers<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    owner: String,
+    repo: String,
+}
+
+impl<C: Clone + Connect + 'static> Stargazers<C> {
+    #[doc(hidden)]
+    pub fn new<O, R>(github: Github<C>, owner: O, repo: R) -> Self
+    where
+        O: Into<String>,
+        R: Into<String>,
+    {
+        Stargazers {
+            github,
+            owner: owner.into(),
+            repo: repo.into(),
+        }
+    }
+
+    /// provides a stream over all of the repository's stargazers, in the
+    /// order they starred it
+    pub fn iter(&self) -> Stream<Stargazer> {
+        let uri = format!(
+            "/repos/{}/{}/stargazers?per_page=100",
+            self.owner, self.repo
+        );
+        unfold_media(
+            self.github.clone(),
+            self.github.get_pages_media(&uri, MediaType::Star),
+            identity,
+            MediaType::Star,
+        )
+    }
+
+    /// provides a stream over the repository's stargazers who starred it at
+    /// or after `since`, an ISO 8601 timestamp like `2019-01-01T00:00:00Z`,
+    /// most recent first. pages are walked back from the last one, so only
+    /// the pages holding recent stars are requested
+    pub fn since<S>(&self, since: S) -> Stream<Stargazer>
+    where
+        S: Into<String>,
+    {
+        let since = since.into();
+        let uri = format!(
+            "/repos/{}/{}/stargazers?per_page=100",
+            self.owner, self.repo
+        );
+        let github = self.github.clone();
+        let last: Future<(Option<Link>, Vec<Stargazer>)> =
+            Box::new(self.github.get_pages_media(&uri, MediaType::Star).and_then(
+                move |(link, stargazers)| -> Future<(Option<Link>, Vec<Stargazer>)> {
+                    match link.as_ref().and_then(|l| related(l, RelationType::Last)) {
+                        Some(last) => github.get_pages_media(&last, MediaType::Star),
+                        None => Box::new(future::ok((link, stargazers))),
+                    }
+                },
+            ));
+        let github = self.github.clone();
+        Box::new(
+            stream::unfold(Some(last), move |page| {
+                let github = github.clone();
+                let since = since.clone();
+                page.map(|page| {
+                    page.map(move |(link, stargazers)| {
+                        let count = stargazers.len();
+                        let recent = stargazers
+                            .into_iter()
+                            .rev()
+                            .take_while(|stargazer| stargazer.starred_at >= since)
+                            .collect::<Vec<_>>();
+                        // stop at the first stargazer who starred it before `since`
+                        let prev = if recent.len() < count {
+                            None
+                        } else {
+                            link.as_ref().and_then(|l| related(l, RelationType::Prev))
+                        };
+                        let prev = prev.map(|prev| github.get_pages_media(&prev, MediaType::Star));
+                        (stream::iter_ok::<_, Error>(recent), prev)
+                    })
+                })
+            })
+            .flatten(),
+        )
+    }
+}
+
+/// the path and query of the page `link` relates to by `relation`
+fn related(link: &Link, relation: RelationType) -> Option<String> {
+    link.values()
+        .iter()
+        .find(|v| v.rel().unwrap_or(&[]).contains(&relation))
+        .and_then(|v| Url::parse(v.link()).ok())
+        .map(|url| [url.path(), url.query().unwrap_or_default()].join("?"))
+}
+
+// representations
+
+/// A user who starred a repository
+#[derive(Debug, Deserialize)]
+pub struct Stargazer {
+    pub starred_at: String,
+    pub user: User,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn deserialize_stargazer() {
+        let stargazer: Stargazer = serde_json::from_str(
+            r#"{
+              "starred_at": "2011-01-16T19:06:43Z",
+              "user": {
+                "login": "octocat",
+                "id": 1,
+                "avatar_url": "https://github.com/images/error/octocat_happy.gif",
+                "gravatar_id": "",
+                "url": "https://api.github.com/users/octocat",
+                "html_url": "https://github.com/octocat",
+                "followers_url": "https://api.github.com/users/octocat/followers",
+                "following_url": "https://api.github.com/users/octocat/following{/other_user}",
+                "gists_url": "https://api.github.com/users/octocat/gists{/gist_id}",
+                "starred_url": "https://api.github.com/users/octocat/starred{/owner}{/repo}",
+                "subscriptions_url": "https://api.github.com/users/octocat/subscriptions",
+                "organizations_url": "https://api.github.com/users/octocat/orgs",
+                "repos_url": "https://api.github.com/users/octocat/repos",
+                "events_url": "https://api.github.com/users/octocat/events{/privacy}",
+                "received_events_url": "https://api.github.com/users/octocat/received_events",
+                "type": "User",
+                "site_admin": false
+              }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(stargazer.starred_at, "2011-01-16T19:06:43Z");
+        assert_eq!(stargazer.user.login, "octocat");
+    }
+
+    #[test]
+    fn related_pages() {
+        let link = r#"<https://api.github.com/repositories/1/stargazers?per_page=100&page=3>; rel="prev", <https://api.github.com/repositories/1/stargazers?per_page=100&page=1>; rel="first""#
+            .parse::<Link>()
+            .unwrap();
+        assert_eq!(
+            related(&link, RelationType::Prev),
+            Some("/repositories/1/stargazers?per_page=100&page=3".into())
+        );
+        assert_eq!(related(&link, RelationType::Last), None);
+    }
+}
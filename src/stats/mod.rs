@@ -0,0 +1,178 @@
+//! Statistics interface
+//!
+//! Github computes repository statistics in the background, answering with
+//! a `202 Accepted` until they're ready. Requests for statistics are retried
+//! a few times while they're computed.
+//!
+//! See the [github docs](https://docs.github.com/en/rest/metrics/statistics)
+//! for more information.
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use futures::{future, Future as StdFuture, Stream as StdStream};
+use hyper::client::connect::Connect;
+use hyper::{HeaderMap, Method, StatusCode};
+use serde::de::DeserializeOwned;
+use serde_json;
+use tokio_timer::Delay;
+
+use repositories::RepoSlug;
+use {fault_from, Error, Future, Github};
+
+/// the most times statistics are requested while Github computes them
+const MAX_ATTEMPTS: u32 = 5;
+const RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// Provides access to the statistics of a repository
+pub struct Stats<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    owner: String,
+    repo: String,
+}
+
+impl<C: Clone + Connect + 'static> Stats<C> {
+    #[doc(hidden)]
+    pub fn new<O, R>(github: Github<C>, owner: O, repo: R) -> Self
+    where
+        O: Into<String>,
+        R: Into<String>,
+    {
+        Stats {
+            github,
+            owner: owner.into(),
+            repo: repo.into(),
+        }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!("/repos/{}/{}/stats{}", self.owner, self.repo, more)
+    }
+
+    /// the commits made to the default branch in each week of the last year,
+    /// oldest first
+    pub fn commit_activity(&self) -> Future<Vec<WeeklyCommits>> {
+        computed(self.github.clone(), self.path("/commit_activity"))
+    }
+}
+
+/// requests statistics at `uri`, retrying while Github computes them.
+/// repositories without commits have no statistics
+fn computed<C, D>(github: Github<C>, uri: String) -> Future<D>
+where
+    C: Clone + Connect + 'static,
+    D: DeserializeOwned + Default + Send + 'static,
+{
+    Box::new(future::loop_fn(1, move |attempt| {
+        github
+            .request_raw(Method::GET, &uri, None, HeaderMap::new())
+            .and_then(move |raw| -> Future<future::Loop<D, u32>> {
+                match raw.status {
+                    StatusCode::ACCEPTED if attempt < MAX_ATTEMPTS => {
+                        debug!(
+                            "statistics are being computed, retrying in {:?}",
+                            RETRY_DELAY
+                        );
+                        Box::new(
+                            Delay::new(Instant::now() + RETRY_DELAY)
+                                .map_err(|err| Error::from(format!("stats timer failed: {}", err)))
+                                .map(move |_| future::Loop::Continue(attempt + 1)),
+                        )
+                    }
+                    StatusCode::ACCEPTED => Box::new(future::err(Error::from(format!(
+                        "statistics were still being computed after {} attempts",
+                        MAX_ATTEMPTS
+                    )))),
+                    StatusCode::NO_CONTENT => {
+                        Box::new(future::ok(future::Loop::Break(D::default())))
+                    }
+                    status if status.is_success() => Box::new(future::result(
+                        serde_json::from_slice(&raw.body)
+                            .map(future::Loop::Break)
+                            .map_err(Error::from),
+                    )),
+                    status => Box::new(future::err(fault_from(status, &raw.body).into())),
+                }
+            })
+    }))
+}
+
+/// requests the commit activity of each of `repos` concurrently, and sums
+/// it by week
+pub(crate) fn commit_activity<C>(
+    github: &Github<C>,
+    repos: Vec<RepoSlug>,
+) -> Future<Vec<WeeklyCommits>>
+where
+    C: Clone + Connect + 'static,
+{
+    let requests = repos
+        .into_iter()
+        .map(|slug| Stats::new(github.clone(), slug.owner, slug.repo).commit_activity())
+        .collect::<Vec<_>>();
+    Box::new(github.batch().run(requests).collect().map(aggregate))
+}
+
+// representations
+
+/// The commits made in a week
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct WeeklyCommits {
+    /// the commits made on each day of the week, starting on sunday
+    pub days: Vec<u64>,
+    pub total: u64,
+    /// the start of the week, in seconds since the epoch
+    pub week: u64,
+}
+
+/// sums the commit activity of several repositories by week, oldest first
+pub fn aggregate<I>(activity: I) -> Vec<WeeklyCommits>
+where
+    I: IntoIterator<Item = Vec<WeeklyCommits>>,
+{
+    let mut weeks: BTreeMap<u64, WeeklyCommits> = BTreeMap::new();
+    for week in activity.into_iter().flat_map(|weeks| weeks) {
+        let sum = weeks.entry(week.week).or_insert_with(|| WeeklyCommits {
+            days: vec![0; 7],
+            total: 0,
+            week: week.week,
+        });
+        for (day, commits) in sum.days.iter_mut().zip(week.days) {
+            *day += commits;
+        }
+        sum.total += week.total;
+    }
+    weeks.into_iter().map(|(_, week)| week).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aggregate_weeks() {
+        let week = |week, days: Vec<u64>| WeeklyCommits {
+            total: days.iter().sum(),
+            days,
+            week,
+        };
+        let aggregated = aggregate(vec![
+            vec![
+                week(1_336_280_400, vec![0, 3, 26, 20, 39, 1, 0]),
+                week(1_336_885_200, vec![0, 1, 0, 0, 0, 0, 0]),
+            ],
+            vec![week(1_336_280_400, vec![0, 1, 1, 1, 1, 1, 0])],
+            Vec::new(),
+        ]);
+        assert_eq!(
+            aggregated,
+            vec![
+                week(1_336_280_400, vec![0, 4, 27, 21, 40, 2, 0]),
+                week(1_336_885_200, vec![0, 1, 0, 0, 0, 0, 0]),
+            ]
+        );
+    }
+}
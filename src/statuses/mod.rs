@@ -5,8 +5,14 @@ extern crate serde_json;
 
 use futures::future;
 use hyper::client::connect::Connect;
+
+use checks::{CheckRun, CheckRunState, Conclusion};
 use users::User;
-use {Future, Github};
+use {unfold, Future, Github, Stream};
+
+fn identity<T>(x: T) -> T {
+    x
+}
 
 /// interface for statuses associated with a repository
 pub struct Statuses<C>
@@ -42,22 +48,64 @@ impl<C: Clone + Connect + 'static> Statuses<C> {
             .post(&self.path(&format!("/{}", sha)), json!(status))
     }
 
-    /// lists all statuses associated with a given git sha
-    pub fn list(&self, sha: &str) -> Future<Vec<Status>> {
-        self.github.get(&format!(
-            "/repos/{}/{}/commits/{}/statuses",
-            self.owner, self.repo, sha
-        ))
+    fn ref_path(&self, reference: &str, more: &str) -> String {
+        format!(
+            "/repos/{}/{}/commits/{}/{}",
+            self.owner, self.repo, reference, more
+        )
+    }
+
+    /// lists the statuses associated with a given git sha, branch or tag,
+    /// most recent first
+    pub fn list(&self, reference: &str) -> Future<Vec<Status>> {
+        self.github.get(&self.ref_path(reference, "statuses"))
     }
 
-    /// list the combined statuses for a given git sha
-    /// fixme: give this a type
-    pub fn combined(&self, sha: &str) -> Future<String> {
-        self.github.get(&format!(
-            "/repos/{}/{}/commits/{}/status",
-            self.owner, self.repo, sha
-        ))
+    /// provides a stream over all pages of statuses associated with a given
+    /// git sha, branch or tag, most recent first
+    pub fn iter(&self, reference: &str) -> Stream<Status> {
+        unfold(
+            self.github.clone(),
+            self.github.get_pages(&self.ref_path(reference, "statuses")),
+            identity,
+        )
+    }
+
+    /// get the combined status of a given git sha, branch or tag: the
+    /// latest status of each context, and an overall state
+    pub fn combined(&self, reference: &str) -> Future<CombinedStatus> {
+        self.github.get(&self.ref_path(reference, "status"))
+    }
+}
+
+/// the state of a check run, as it contributes to the state of a ref
+fn check_run_state(status: Option<&CheckRunState>, conclusion: Option<&Conclusion>) -> State {
+    match (status, conclusion) {
+        (Some(&CheckRunState::Completed), Some(&Conclusion::Success))
+        | (Some(&CheckRunState::Completed), Some(&Conclusion::Neutral))
+        | (Some(&CheckRunState::Completed), Some(&Conclusion::Skipped)) => State::Success,
+        (Some(&CheckRunState::Completed), Some(_)) => State::Failure,
+        _ => State::Pending,
+    }
+}
+
+/// a failure wins over a pending state, which wins over success. refs
+/// without any statuses or checks are pending
+fn overall_state<I>(states: I) -> State
+where
+    I: IntoIterator<Item = State>,
+{
+    let mut overall = None;
+    for state in states {
+        overall = match (overall, state) {
+            (_, State::Failure) | (_, State::Error) | (Some(State::Failure), _) => {
+                Some(State::Failure)
+            }
+            (_, State::Pending) | (Some(State::Pending), _) => Some(State::Pending),
+            _ => Some(State::Success),
+        }
     }
+    overall.unwrap_or(State::Pending)
 }
 
 // representations (todo: replace with derive_builder)
@@ -67,14 +115,58 @@ pub struct Status {
     pub created_at: String,
     pub updated_at: String,
     pub state: State,
-    pub target_url: String,
-    pub description: String,
+    pub target_url: Option<String>,
+    pub description: Option<String>,
     pub id: u64,
     pub url: String,
     pub context: String,
     pub creator: User,
 }
 
+/// The commit statuses and check runs of a git ref
+#[derive(Debug)]
+pub struct RefStatus {
+    pub combined: CombinedStatus,
+    pub check_runs: Vec<CheckRun>,
+}
+
+impl RefStatus {
+    /// `failure` if any status or check run failed, `pending` if any has
+    /// yet to complete or there are none, `success` otherwise
+    pub fn state(&self) -> State {
+        let statuses = if self.combined.total_count > 0 {
+            Some(self.combined.state.clone())
+        } else {
+            None
+        };
+        overall_state(
+            statuses.into_iter().chain(
+                self.check_runs
+                    .iter()
+                    .map(|run| check_run_state(run.status.as_ref(), run.conclusion.as_ref())),
+            ),
+        )
+    }
+
+    /// true if every status and check run of the ref succeeded
+    pub fn is_success(&self) -> bool {
+        self.state() == State::Success
+    }
+}
+
+/// The latest status of each context of a git ref
+#[derive(Debug, Deserialize)]
+pub struct CombinedStatus {
+    /// `failure` if any context failed or errored, `pending` if any context
+    /// is pending or there are no statuses, `success` otherwise
+    pub state: State,
+    pub sha: String,
+    pub total_count: u64,
+    pub statuses: Vec<Status>,
+    pub commit_url: String,
+    pub url: String,
+}
+
 #[derive(Debug, Default, Serialize)]
 pub struct StatusOptions {
     state: State,
@@ -193,6 +285,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn ref_state() {
+        assert_eq!(overall_state(vec![]), State::Pending);
+        assert_eq!(
+            overall_state(vec![State::Success, State::Success]),
+            State::Success
+        );
+        assert_eq!(
+            overall_state(vec![State::Success, State::Pending]),
+            State::Pending
+        );
+        assert_eq!(
+            overall_state(vec![State::Failure, State::Pending]),
+            State::Failure
+        );
+        assert_eq!(
+            overall_state(vec![State::Pending, State::Error]),
+            State::Failure
+        );
+        assert_eq!(
+            check_run_state(Some(&CheckRunState::InProgress), None),
+            State::Pending
+        );
+        assert_eq!(
+            check_run_state(Some(&CheckRunState::Completed), Some(&Conclusion::Skipped)),
+            State::Success
+        );
+        assert_eq!(
+            check_run_state(Some(&CheckRunState::Completed), Some(&Conclusion::TimedOut)),
+            State::Failure
+        );
+    }
+
     #[test]
     fn deserialize_status_state() {
         for (json, value) in vec![
@@ -246,5 +371,4 @@ mod tests {
         ];
         test_encoding(tests)
     }
-
 }
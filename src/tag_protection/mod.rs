@@ -0,0 +1,75 @@
+//! Tag protection interface
+//!
+//! See the [github docs](https://docs.github.com/en/rest/repos/tags) for more information.
+//! Github is replacing tag protection with rulesets, see the `rulesets` module
+
+use std::collections::BTreeMap;
+
+use futures::future;
+use hyper::client::connect::Connect;
+use serde_json;
+
+use {Future, Github};
+
+/// Provides access to the tag protection states of a repository
+pub struct TagProtections<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    owner: String,
+    repo: String,
+}
+
+impl<C: Clone + Connect + 'static> TagProtections<C> {
+    #[doc(hidden)]
+    pub fn new<O, R>(github: Github<C>, owner: O, repo: R) -> Self
+    where
+        O: Into<String>,
+        R: Into<String>,
+    {
+        TagProtections {
+            github,
+            owner: owner.into(),
+            repo: repo.into(),
+        }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!(
+            "/repos/{}/{}/tags/protection{}",
+            self.owner, self.repo, more
+        )
+    }
+
+    /// list the tag protection states of this repository
+    pub fn list(&self) -> Future<Vec<TagProtection>> {
+        self.github.get(&self.path(""))
+    }
+
+    /// protect tags matching a pattern, like `v1.*`
+    pub fn create<P>(&self, pattern: P) -> Future<TagProtection>
+    where
+        P: Into<String>,
+    {
+        let mut payload = BTreeMap::new();
+        payload.insert("pattern", pattern.into());
+        self.github.post(&self.path(""), json!(payload))
+    }
+
+    /// delete a tag protection state by id
+    pub fn delete(&self, id: u64) -> Future<()> {
+        self.github.delete(&self.path(&format!("/{}", id)))
+    }
+}
+
+// representations
+
+#[derive(Debug, Deserialize)]
+pub struct TagProtection {
+    pub id: u64,
+    pub pattern: String,
+    pub enabled: Option<bool>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
@@ -0,0 +1,185 @@
+//! Team synchronization interface
+//!
+//! Team synchronization keeps the members of teams in sync with groups of an
+//! identity provider. Enterprise Managed Users link teams to external groups
+//! instead. See the [github docs](https://docs.github.com/en/rest/teams/team-sync)
+//! for more information
+
+use std::collections::BTreeMap;
+
+use futures::{future, Future as StdFuture};
+use hyper::client::connect::Connect;
+use serde_json;
+use url::form_urlencoded;
+
+use {Future, Github};
+
+/// Provides access to the identity provider groups and external groups
+/// of an organization
+pub struct TeamSync<C>
+where
+    C: Clone + Connect + 'static,
+{
+    github: Github<C>,
+    org: String,
+}
+
+impl<C: Clone + Connect + 'static> TeamSync<C> {
+    #[doc(hidden)]
+    pub fn new<O>(github: Github<C>, org: O) -> Self
+    where
+        O: Into<String>,
+    {
+        TeamSync {
+            github,
+            org: org.into(),
+        }
+    }
+
+    fn path(&self, more: &str) -> String {
+        format!("/orgs/{}{}", self.org, more)
+    }
+
+    /// list the identity provider groups available to the organization,
+    /// optionally filtered by name
+    pub fn groups(&self, q: Option<&str>) -> Future<Vec<IdpGroup>> {
+        let mut uri = vec![self.path("/team-sync/groups")];
+        if let Some(q) = q {
+            uri.push(
+                form_urlencoded::Serializer::new(String::new())
+                    .append_pair("q", q)
+                    .finish(),
+            );
+        }
+        Box::new(
+            self.github
+                .get::<IdpGroups>(&uri.join("?"))
+                .map(|list| list.groups),
+        )
+    }
+
+    /// list the identity provider groups a team is connected to
+    pub fn team_groups(&self, team_slug: &str) -> Future<Vec<IdpGroup>> {
+        Box::new(
+            self.github
+                .get::<IdpGroups>(
+                    &self.path(&format!("/teams/{}/team-sync/group-mappings", team_slug)),
+                )
+                .map(|list| list.groups),
+        )
+    }
+
+    /// replace the identity provider groups a team is connected to. an
+    /// empty list disconnects the team from all groups
+    pub fn set_team_groups(&self, team_slug: &str, groups: Vec<IdpGroup>) -> Future<Vec<IdpGroup>> {
+        let payload = IdpGroups { groups };
+        Box::new(
+            self.github
+                .patch::<IdpGroups>(
+                    &self.path(&format!("/teams/{}/team-sync/group-mappings", team_slug)),
+                    json!(payload),
+                )
+                .map(|list| list.groups),
+        )
+    }
+
+    /// list the external groups of an Enterprise Managed Users
+    /// organization, optionally filtered by display name
+    pub fn external_groups(&self, display_name: Option<&str>) -> Future<Vec<ExternalGroupInfo>> {
+        let mut uri = vec![self.path("/external-groups")];
+        if let Some(name) = display_name {
+            uri.push(
+                form_urlencoded::Serializer::new(String::new())
+                    .append_pair("display_name", name)
+                    .finish(),
+            );
+        }
+        Box::new(
+            self.github
+                .get::<ExternalGroups>(&uri.join("?"))
+                .map(|list| list.groups),
+        )
+    }
+
+    /// get an external group by id, including its members and linked teams
+    pub fn external_group(&self, group_id: u64) -> Future<ExternalGroup> {
+        self.github
+            .get(&self.path(&format!("/external-group/{}", group_id)))
+    }
+
+    /// list the external groups linked to a team
+    pub fn team_external_groups(&self, team_slug: &str) -> Future<Vec<ExternalGroupInfo>> {
+        Box::new(
+            self.github
+                .get::<ExternalGroups>(&self.path(&format!("/teams/{}/external-groups", team_slug)))
+                .map(|list| list.groups),
+        )
+    }
+
+    /// link an external group to a team, replacing the team's members
+    /// with the group's members
+    pub fn link_external_group(&self, team_slug: &str, group_id: u64) -> Future<ExternalGroup> {
+        let mut payload = BTreeMap::new();
+        payload.insert("group_id", group_id);
+        self.github.patch(
+            &self.path(&format!("/teams/{}/external-groups", team_slug)),
+            json!(payload),
+        )
+    }
+
+    /// unlink the external group of a team
+    pub fn unlink_external_group(&self, team_slug: &str) -> Future<()> {
+        self.github
+            .delete(&self.path(&format!("/teams/{}/external-groups", team_slug)))
+    }
+}
+
+// representations
+
+#[derive(Debug, Serialize, Deserialize)]
+struct IdpGroups {
+    groups: Vec<IdpGroup>,
+}
+
+/// A group of an identity provider
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IdpGroup {
+    pub group_id: String,
+    pub group_name: String,
+    pub group_description: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExternalGroups {
+    groups: Vec<ExternalGroupInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExternalGroupInfo {
+    pub group_id: u64,
+    pub group_name: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExternalGroup {
+    pub group_id: u64,
+    pub group_name: String,
+    pub updated_at: Option<String>,
+    pub teams: Vec<ExternalGroupTeam>,
+    pub members: Vec<ExternalGroupMember>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExternalGroupTeam {
+    pub team_id: u64,
+    pub team_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExternalGroupMember {
+    pub member_id: u64,
+    pub member_login: String,
+    pub member_name: String,
+    pub member_email: String,
+}
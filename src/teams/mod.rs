@@ -126,6 +126,29 @@ impl<C: Clone + Connect + 'static> OrgTeams<C> {
             json!(payload),
         )
     }
+
+    /// list the teams whose members are security managers of this org
+    /// learn more [here](https://docs.github.com/en/rest/orgs/security-managers)
+    pub fn security_managers(&self) -> Future<Vec<Team>> {
+        self.github
+            .get(&format!("/orgs/{}/security-managers", self.org))
+    }
+
+    /// make the members of a team security managers of this org
+    pub fn add_security_manager(&self, team_slug: &str) -> Future<()> {
+        self.github.put_no_response(
+            &format!("/orgs/{}/security-managers/teams/{}", self.org, team_slug),
+            Vec::new(),
+        )
+    }
+
+    /// remove the security manager role from a team
+    pub fn remove_security_manager(&self, team_slug: &str) -> Future<()> {
+        self.github.delete(&format!(
+            "/orgs/{}/security-managers/teams/{}",
+            self.org, team_slug
+        ))
+    }
 }
 
 // representations (todo: replace with derive_builder)
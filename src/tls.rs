@@ -0,0 +1,48 @@
+//! Construction of the https connector for the tls backend selected by
+//! cargo features. `tls` uses the platform's native tls implementation
+//! (OpenSSL on linux) while `rustls-tls` uses rustls with the Mozilla root
+//! certificates, which suits musl and other static builds. When both are
+//! enabled `tls` takes precedence
+
+use hyper::client::HttpConnector;
+
+use {Error, HttpsConnector, Result};
+
+#[cfg(feature = "tls")]
+pub fn connector(
+    http: HttpConnector,
+    root_certificates: &[Vec<u8>],
+) -> Result<HttpsConnector<HttpConnector>> {
+    use native_tls::{Certificate, TlsConnector};
+
+    let mut tls = TlsConnector::builder();
+    for pem in root_certificates {
+        tls.add_root_certificate(
+            Certificate::from_pem(pem)
+                .map_err(|err| Error::from(format!("invalid root certificate: {}", err)))?,
+        );
+    }
+    let tls = tls
+        .build()
+        .map_err(|err| Error::from(format!("failed to initialize tls: {}", err)))?;
+    Ok(HttpsConnector::from((http, tls)))
+}
+
+#[cfg(all(feature = "rustls-tls", not(feature = "tls")))]
+pub fn connector(
+    http: HttpConnector,
+    root_certificates: &[Vec<u8>],
+) -> Result<HttpsConnector<HttpConnector>> {
+    use rustls::ClientConfig;
+    use webpki_roots::TLS_SERVER_ROOTS;
+
+    let mut tls = ClientConfig::new();
+    tls.root_store.add_server_trust_anchors(&TLS_SERVER_ROOTS);
+    for pem in root_certificates {
+        match tls.root_store.add_pem_file(&mut &pem[..]) {
+            Ok((added, _)) if added > 0 => (),
+            _ => return Err(Error::from("invalid root certificate")),
+        }
+    }
+    Ok(HttpsConnector::from((http, tls)))
+}
@@ -4,6 +4,23 @@ use {unfold, Future, Github, Stream};
 
 use hyper::client::connect::Connect;
 
+string_enum! {
+    /// The relationship of the author of an issue, pull request or comment
+    /// to its repository
+    pub enum AuthorAssociation {
+        Collaborator => "COLLABORATOR",
+        Contributor => "CONTRIBUTOR",
+        /// has previously committed to another repository on Github
+        FirstTimer => "FIRST_TIMER",
+        FirstTimeContributor => "FIRST_TIME_CONTRIBUTOR",
+        /// a placeholder for an unclaimed user of an imported repository
+        Mannequin => "MANNEQUIN",
+        Member => "MEMBER",
+        None => "NONE",
+        Owner => "OWNER",
+    }
+}
+
 /// User information
 #[derive(Debug, Deserialize)]
 pub struct User {
@@ -133,4 +150,12 @@ impl<C: Clone + Connect + 'static> Contributors<C> {
             identity,
         )
     }
+
+    /// provides a stream over all pages of contributors, like `iter`, but
+    /// deserializes each page incrementally as it is received rather than
+    /// buffering it. prefer this for repositories with many contributors
+    pub fn iter_streamed(&self) -> Stream<User> {
+        self.github
+            .get_stream(&format!("/repos/{}/{}/contributors", self.owner, self.repo))
+    }
 }
@@ -0,0 +1,141 @@
+//! Webhooks interface
+//!
+//! Representations of the payloads Github delivers to webhook endpoints.
+//! The type of a delivery is identified by its `X-GitHub-Event` header.
+//! Deliveries should be authenticated with `verify` before they are parsed,
+//! or handed to a `Router`, which does both and dispatches them to handlers
+//!
+//! ```no_run
+//! extern crate hubcaps;
+//!
+//! use hubcaps::webhooks::{self, Event};
+//!
+//! # fn handle(event_header: &str, signature: &str, body: &[u8]) -> hubcaps::Result<()> {
+//! webhooks::verify("s3cr3t", body, signature)?;
+//! match Event::parse(event_header, body)? {
+//!     Event::Push(push) => println!("pushed to {}", push.git_ref),
+//!     Event::PullRequest(pr) => println!("pull {} {}", pr.number, pr.action),
+//!     other => println!("ignoring {}", other.name()),
+//! }
+//! # Ok(())
+//! # }
+//! # fn main() {}
+//! ```
+use serde_json;
+
+use Result;
+
+mod payloads;
+mod router;
+mod signature;
+
+pub use self::payloads::*;
+pub use self::router::Router;
+pub use self::signature::verify;
+
+/// A webhook delivery, dispatched by the value of its `X-GitHub-Event` header
+#[derive(Debug)]
+pub enum Event {
+    Ping(PingEvent),
+    Push(PushEvent),
+    Create(CreateEvent),
+    Delete(DeleteEvent),
+    PullRequest(PullRequestEvent),
+    Issues(IssuesEvent),
+    IssueComment(IssueCommentEvent),
+    CheckRun(CheckRunEvent),
+    Release(ReleaseEvent),
+    WorkflowRun(WorkflowRunEvent),
+    /// An event this crate does not yet provide a representation for
+    Unknown {
+        name: String,
+        payload: serde_json::Value,
+    },
+}
+
+impl Event {
+    /// Parses a webhook delivery body given the value of its
+    /// `X-GitHub-Event` header
+    pub fn parse(name: &str, payload: &[u8]) -> Result<Event> {
+        Ok(match name {
+            "ping" => Event::Ping(serde_json::from_slice(payload)?),
+            "push" => Event::Push(serde_json::from_slice(payload)?),
+            "create" => Event::Create(serde_json::from_slice(payload)?),
+            "delete" => Event::Delete(serde_json::from_slice(payload)?),
+            "pull_request" => Event::PullRequest(serde_json::from_slice(payload)?),
+            "issues" => Event::Issues(serde_json::from_slice(payload)?),
+            "issue_comment" => Event::IssueComment(serde_json::from_slice(payload)?),
+            "check_run" => Event::CheckRun(serde_json::from_slice(payload)?),
+            "release" => Event::Release(serde_json::from_slice(payload)?),
+            "workflow_run" => Event::WorkflowRun(serde_json::from_slice(payload)?),
+            other => Event::Unknown {
+                name: other.to_owned(),
+                payload: serde_json::from_slice(payload)?,
+            },
+        })
+    }
+
+    /// Returns the action which triggered this event, like `opened`, for
+    /// events which have one
+    pub fn action(&self) -> Option<&str> {
+        match *self {
+            Event::PullRequest(ref event) => Some(&event.action),
+            Event::Issues(ref event) => Some(&event.action),
+            Event::IssueComment(ref event) => Some(&event.action),
+            Event::CheckRun(ref event) => Some(&event.action),
+            Event::Release(ref event) => Some(&event.action),
+            Event::WorkflowRun(ref event) => Some(&event.action),
+            Event::Unknown { ref payload, .. } => payload["action"].as_str(),
+            Event::Ping(_) | Event::Push(_) | Event::Create(_) | Event::Delete(_) => None,
+        }
+    }
+
+    /// Returns the name of this event as it appears in the `X-GitHub-Event` header
+    pub fn name(&self) -> &str {
+        match *self {
+            Event::Ping(_) => "ping",
+            Event::Push(_) => "push",
+            Event::Create(_) => "create",
+            Event::Delete(_) => "delete",
+            Event::PullRequest(_) => "pull_request",
+            Event::Issues(_) => "issues",
+            Event::IssueComment(_) => "issue_comment",
+            Event::CheckRun(_) => "check_run",
+            Event::Release(_) => "release",
+            Event::WorkflowRun(_) => "workflow_run",
+            Event::Unknown { ref name, .. } => name,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ping() {
+        let event = Event::parse(
+            "ping",
+            br#"{"zen":"Keep it logically awesome.","hook_id":1}"#,
+        )
+        .unwrap();
+        match event {
+            Event::Ping(ping) => {
+                assert_eq!(ping.zen, "Keep it logically awesome.");
+                assert_eq!(ping.hook_id, 1);
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse_unknown() {
+        let event = Event::parse("star", br#"{"action":"created"}"#).unwrap();
+        assert_eq!(event.name(), "star");
+        assert_eq!(event.action(), Some("created"));
+        match event {
+            Event::Unknown { payload, .. } => assert_eq!(payload["action"], "created"),
+            other => panic!("unexpected event {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,199 @@
+//! Webhook payload representations
+
+use checks::CheckRun;
+use comments::Comment;
+use issues::Issue;
+use pulls::Pull;
+use releases::Release;
+use users::User;
+
+/// A repository as included in webhook payloads
+///
+/// Only fields common to all events are included. Notably `push` events
+/// report timestamps as unix epoch seconds where other events use ISO 8601
+/// strings, so those are omitted
+#[derive(Debug, Deserialize)]
+pub struct Repository {
+    pub id: u64,
+    pub name: String,
+    pub full_name: String,
+    pub owner: User,
+    pub private: bool,
+    pub html_url: String,
+    pub description: Option<String>,
+    pub fork: bool,
+    pub url: String,
+    pub clone_url: String,
+    pub ssh_url: String,
+    pub default_branch: String,
+}
+
+/// The account a Github App installation belongs to as referenced in webhook payloads
+#[derive(Debug, Deserialize)]
+pub struct InstallationRef {
+    pub id: u64,
+}
+
+/// A git author or committer identity
+#[derive(Debug, Deserialize)]
+pub struct GitUser {
+    pub name: String,
+    pub email: Option<String>,
+    pub username: Option<String>,
+}
+
+/// A commit as included in push event payloads
+#[derive(Debug, Deserialize)]
+pub struct PushCommit {
+    pub id: String,
+    pub tree_id: String,
+    pub distinct: bool,
+    pub message: String,
+    pub timestamp: String,
+    pub url: String,
+    pub author: GitUser,
+    pub committer: GitUser,
+    #[serde(default)]
+    pub added: Vec<String>,
+    #[serde(default)]
+    pub removed: Vec<String>,
+    #[serde(default)]
+    pub modified: Vec<String>,
+}
+
+/// Payload of a `ping` event, sent when a hook is created
+#[derive(Debug, Deserialize)]
+pub struct PingEvent {
+    pub zen: String,
+    pub hook_id: u64,
+    pub repository: Option<Repository>,
+    pub sender: Option<User>,
+}
+
+/// Payload of a `push` event
+#[derive(Debug, Deserialize)]
+pub struct PushEvent {
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    pub before: String,
+    pub after: String,
+    pub created: bool,
+    pub deleted: bool,
+    pub forced: bool,
+    pub base_ref: Option<String>,
+    pub compare: String,
+    pub commits: Vec<PushCommit>,
+    pub head_commit: Option<PushCommit>,
+    pub repository: Repository,
+    pub pusher: GitUser,
+    pub sender: User,
+    pub installation: Option<InstallationRef>,
+}
+
+/// Payload of a `create` event, sent when a branch or tag is created
+#[derive(Debug, Deserialize)]
+pub struct CreateEvent {
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    /// either `branch` or `tag`
+    pub ref_type: String,
+    pub master_branch: String,
+    pub description: Option<String>,
+    pub repository: Repository,
+    pub sender: User,
+    pub installation: Option<InstallationRef>,
+}
+
+/// Payload of a `delete` event, sent when a branch or tag is deleted
+#[derive(Debug, Deserialize)]
+pub struct DeleteEvent {
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    /// either `branch` or `tag`
+    pub ref_type: String,
+    pub repository: Repository,
+    pub sender: User,
+    pub installation: Option<InstallationRef>,
+}
+
+/// Payload of a `pull_request` event
+#[derive(Debug, Deserialize)]
+pub struct PullRequestEvent {
+    pub action: String,
+    pub number: u64,
+    pub pull_request: Pull,
+    pub repository: Repository,
+    pub sender: User,
+    pub installation: Option<InstallationRef>,
+}
+
+/// Payload of an `issues` event
+#[derive(Debug, Deserialize)]
+pub struct IssuesEvent {
+    pub action: String,
+    pub issue: Issue,
+    pub repository: Repository,
+    pub sender: User,
+    pub installation: Option<InstallationRef>,
+}
+
+/// Payload of an `issue_comment` event, sent for comments on both
+/// issues and pull requests
+#[derive(Debug, Deserialize)]
+pub struct IssueCommentEvent {
+    pub action: String,
+    pub issue: Issue,
+    pub comment: Comment,
+    pub repository: Repository,
+    pub sender: User,
+    pub installation: Option<InstallationRef>,
+}
+
+/// Payload of a `check_run` event
+#[derive(Debug, Deserialize)]
+pub struct CheckRunEvent {
+    pub action: String,
+    pub check_run: CheckRun,
+    pub repository: Repository,
+    pub sender: User,
+    pub installation: Option<InstallationRef>,
+}
+
+/// Payload of a `release` event
+#[derive(Debug, Deserialize)]
+pub struct ReleaseEvent {
+    pub action: String,
+    pub release: Release,
+    pub repository: Repository,
+    pub sender: User,
+    pub installation: Option<InstallationRef>,
+}
+
+/// A Github Actions workflow run as included in webhook payloads
+#[derive(Debug, Deserialize)]
+pub struct WorkflowRun {
+    pub id: u64,
+    pub name: Option<String>,
+    pub workflow_id: u64,
+    pub run_number: u64,
+    pub run_attempt: Option<u64>,
+    pub event: String,
+    pub status: Option<String>,
+    pub conclusion: Option<String>,
+    pub head_branch: Option<String>,
+    pub head_sha: String,
+    pub url: String,
+    pub html_url: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Payload of a `workflow_run` event
+#[derive(Debug, Deserialize)]
+pub struct WorkflowRunEvent {
+    pub action: String,
+    pub workflow_run: WorkflowRun,
+    pub repository: Repository,
+    pub sender: User,
+    pub installation: Option<InstallationRef>,
+}
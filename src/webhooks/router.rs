@@ -0,0 +1,186 @@
+//! Webhook delivery routing
+
+use std::collections::HashMap;
+
+use hyper::HeaderMap;
+
+use webhooks::{verify, Event};
+use {Error, ErrorKind, Result};
+
+const X_GITHUB_EVENT: &str = "x-github-event";
+const X_HUB_SIGNATURE_256: &str = "x-hub-signature-256";
+const X_HUB_SIGNATURE: &str = "x-hub-signature";
+
+type Handler<R> = Box<Fn(Event) -> R + Send + Sync>;
+
+/// Routes webhook deliveries to handlers by their event and action
+///
+/// A router verifies each delivery's signature and parses its payload before
+/// handing the `Event` to the most specific handler registered for it: one
+/// registered for its event and action, then one for its event, then the
+/// fallback. It only needs a delivery's headers and body, so it works with
+/// any http server
+///
+/// ```no_run
+/// extern crate hubcaps;
+/// extern crate hyper;
+///
+/// use hubcaps::webhooks::{Event, Router};
+/// use hyper::HeaderMap;
+///
+/// # fn handle(headers: &HeaderMap, body: &[u8]) -> hubcaps::Result<()> {
+/// let mut router = Router::new("s3cr3t");
+/// router
+///     .on_action("pull_request", "opened", |event| {
+///         if let Event::PullRequest(pr) = event {
+///             println!("opened pull {}", pr.number)
+///         }
+///     })
+///     .on("push", |_| println!("pushed"))
+///     .fallback(|event| println!("ignoring {}", event.name()));
+/// router.handle(headers, body)?;
+/// # Ok(())
+/// # }
+/// # fn main() {}
+/// ```
+pub struct Router<R> {
+    secret: Vec<u8>,
+    routes: HashMap<(String, Option<String>), Handler<R>>,
+    fallback: Option<Handler<R>>,
+}
+
+impl<R> Router<R> {
+    /// create a router for deliveries signed with `secret`, the secret the
+    /// hook was configured with
+    pub fn new<S>(secret: S) -> Self
+    where
+        S: Into<Vec<u8>>,
+    {
+        Router {
+            secret: secret.into(),
+            routes: HashMap::new(),
+            fallback: None,
+        }
+    }
+
+    /// handle deliveries of `event`, like `push`, whatever their action
+    pub fn on<E, F>(&mut self, event: E, handler: F) -> &mut Self
+    where
+        E: Into<String>,
+        F: Fn(Event) -> R + Send + Sync + 'static,
+    {
+        self.routes.insert((event.into(), None), Box::new(handler));
+        self
+    }
+
+    /// handle deliveries of `event` with `action`, like `pull_request`
+    /// deliveries for pull requests which were `opened`
+    pub fn on_action<E, A, F>(&mut self, event: E, action: A, handler: F) -> &mut Self
+    where
+        E: Into<String>,
+        A: Into<String>,
+        F: Fn(Event) -> R + Send + Sync + 'static,
+    {
+        self.routes
+            .insert((event.into(), Some(action.into())), Box::new(handler));
+        self
+    }
+
+    /// handle deliveries no other handler was registered for
+    pub fn fallback<F>(&mut self, handler: F) -> &mut Self
+    where
+        F: Fn(Event) -> R + Send + Sync + 'static,
+    {
+        self.fallback = Some(Box::new(handler));
+        self
+    }
+
+    /// verify, parse and route a delivery given its headers and body.
+    /// returns `None` when no handler was registered for it
+    pub fn handle(&self, headers: &HeaderMap, body: &[u8]) -> Result<Option<R>> {
+        let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+        let event = header(X_GITHUB_EVENT)
+            .ok_or_else(|| Error::from(format!("missing {} header", X_GITHUB_EVENT)))?;
+        let signature = header(X_HUB_SIGNATURE_256)
+            .or_else(|| header(X_HUB_SIGNATURE))
+            .ok_or(ErrorKind::InvalidSignature)?;
+        self.dispatch(event, signature, body)
+    }
+
+    /// verify, parse and route a delivery given the values of its
+    /// `X-GitHub-Event` and `X-Hub-Signature-256` headers and its body.
+    /// returns `None` when no handler was registered for it
+    pub fn dispatch(&self, event: &str, signature: &str, body: &[u8]) -> Result<Option<R>> {
+        verify(&self.secret, body, signature)?;
+        let event = Event::parse(event, body)?;
+        let name = event.name().to_owned();
+        let action = event.action().map(str::to_owned);
+        let handler = self
+            .routes
+            .get(&(name.clone(), action))
+            .or_else(|| self.routes.get(&(name, None)))
+            .or_else(|| self.fallback.as_ref());
+        Ok(handler.map(|handler| handler(event)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hmac::{Hmac, Mac};
+    use hyper::header::HeaderValue;
+    use sha2::Sha256;
+
+    const SECRET: &str = "s3cr3t";
+
+    fn sign(body: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_varkey(SECRET.as_bytes()).unwrap();
+        mac.input(body);
+        let code = mac.result().code();
+        let hex: String = code.iter().map(|byte| format!("{:02x}", byte)).collect();
+        format!("sha256={}", hex)
+    }
+
+    fn router() -> Router<&'static str> {
+        let mut router = Router::new(SECRET);
+        router
+            .on_action("label", "created", |_| "created")
+            .on("label", |_| "label")
+            .on("ping", |_| "ping");
+        router
+    }
+
+    #[test]
+    fn routes_by_event_and_action() {
+        let mut router = router();
+        // deliveries of events without a representation are routed by the
+        // action in their payload
+        let dispatch = |router: &Router<&'static str>, event: &str, body: &[u8]| {
+            router.dispatch(event, &sign(body), body).unwrap()
+        };
+        let created = br#"{"action":"created"}"#;
+        let deleted = br#"{"action":"deleted"}"#;
+        assert_eq!(dispatch(&router, "label", created), Some("created"));
+        assert_eq!(dispatch(&router, "label", deleted), Some("label"));
+        assert_eq!(dispatch(&router, "star", created), None);
+        router.fallback(|_| "fallback");
+        assert_eq!(dispatch(&router, "star", created), Some("fallback"));
+    }
+
+    #[test]
+    fn handles_headers() {
+        let body = br#"{"zen":"Keep it logically awesome.","hook_id":1}"#;
+        let mut headers = HeaderMap::new();
+        headers.insert(X_GITHUB_EVENT, HeaderValue::from_static("ping"));
+        match router().handle(&headers, body) {
+            Err(Error(ErrorKind::InvalidSignature, _)) => (),
+            other => panic!("expected invalid signature: {:?}", other),
+        }
+        headers.insert(X_HUB_SIGNATURE_256, sign(body).parse().unwrap());
+        assert_eq!(router().handle(&headers, body).unwrap(), Some("ping"));
+        match router().handle(&headers, br#"{"zen":"tampered","hook_id":1}"#) {
+            Err(Error(ErrorKind::InvalidSignature, _)) => (),
+            other => panic!("expected invalid signature: {:?}", other),
+        }
+    }
+}
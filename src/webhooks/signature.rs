@@ -0,0 +1,101 @@
+//! Webhook payload signature verification
+
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+use sha2::Sha256;
+
+use {ErrorKind, Result};
+
+/// Verifies a webhook delivery body against the value of its
+/// `X-Hub-Signature-256` header, or the legacy `X-Hub-Signature` header,
+/// using the secret the hook was configured with
+///
+/// Digests are compared in constant time. Returns an `ErrorKind::InvalidSignature`
+/// error when the signature is malformed or does not match
+///
+/// ```no_run
+/// # fn handle(body: &[u8], header: &str) -> hubcaps::Result<()> {
+/// hubcaps::webhooks::verify("s3cr3t", body, header)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn verify<S, B>(secret: S, body: B, signature: &str) -> Result<()>
+where
+    S: AsRef<[u8]>,
+    B: AsRef<[u8]>,
+{
+    let (secret, body) = (secret.as_ref(), body.as_ref());
+    if signature.starts_with("sha256=") {
+        let code = decode_hex(&signature["sha256=".len()..])?;
+        let mut mac =
+            Hmac::<Sha256>::new_varkey(secret).map_err(|_| ErrorKind::InvalidSignature)?;
+        mac.input(body);
+        mac.verify(&code)
+            .map_err(|_| ErrorKind::InvalidSignature.into())
+    } else if signature.starts_with("sha1=") {
+        let code = decode_hex(&signature["sha1=".len()..])?;
+        let mut mac = Hmac::<Sha1>::new_varkey(secret).map_err(|_| ErrorKind::InvalidSignature)?;
+        mac.input(body);
+        mac.verify(&code)
+            .map_err(|_| ErrorKind::InvalidSignature.into())
+    } else {
+        Err(ErrorKind::InvalidSignature.into())
+    }
+}
+
+fn decode_hex(hex: &str) -> Result<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return Err(ErrorKind::InvalidSignature.into());
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            hex.get(i..i + 2)
+                .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                .ok_or_else(|| ErrorKind::InvalidSignature.into())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "It's a Secret to Everybody";
+    const BODY: &str = "Hello, World!";
+
+    #[test]
+    fn verify_sha256() {
+        assert!(verify(
+            SECRET,
+            BODY,
+            "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17"
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn verify_sha1() {
+        assert!(verify(
+            SECRET,
+            BODY,
+            "sha1=01dc10d0c83e72ed246219cdd91669667fe2ca59"
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn verify_mismatch() {
+        for signature in vec![
+            "sha256=857107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17",
+            "sha256=not-hex",
+            "md5=01dc10d0c83e72ed246219cdd91669667fe2ca59",
+            "",
+        ] {
+            match verify(SECRET, BODY, signature) {
+                Err(::Error(ErrorKind::InvalidSignature, _)) => (),
+                other => panic!("expected invalid signature for {}: {:?}", signature, other),
+            }
+        }
+    }
+}